@@ -0,0 +1,245 @@
+//! Rendering to e-paper displays.
+//!
+//! Refreshing an e-paper display is slow, so changes should be batched. Partial refreshes are
+//! faster than full ones, but they leave ghosting artifacts behind that are only removed by a full
+//! refresh.
+//!
+//! Draw the window into a [`FrameBuffer`] as usual. The changes accumulate in the framebuffer until
+//! the [`RefreshScheduler`] decides that it's time to refresh the display:
+//!
+//! ```rust,ignore
+//! let mut window = Window::new(
+//!     EgCanvas::new(FrameBuffer::<BinaryColor, 296, 128>::new(BinaryColor::Off)),
+//!     gui,
+//! );
+//! let mut scheduler = RefreshScheduler::new(clock)
+//!     .min_interval(500)
+//!     .max_partial_refreshes(10);
+//!
+//! loop {
+//!     window.update();
+//!     window.measure();
+//!     window.arrange();
+//!     window.draw().unwrap();
+//!
+//!     let framebuffer = &mut window.canvas.target;
+//!     match scheduler.refresh(framebuffer) {
+//!         Some(Refresh::Full) => {
+//!             framebuffer.flush(&mut epd_buffer).unwrap();
+//!             epd.update_and_display_frame(&mut spi, epd_buffer.buffer(), &mut delay)?;
+//!         }
+//!         Some(Refresh::Partial(area)) => {
+//!             framebuffer.flush(&mut epd_buffer).unwrap();
+//!             epd.update_partial_frame(&mut spi, epd_buffer.buffer(), area.top_left.x as u32, ..)?;
+//!         }
+//!         None => {}
+//!     }
+//! }
+//! ```
+
+use embedded_graphics::primitives::Rectangle;
+use embedded_gui::time::{Instant, TimeSource};
+
+use crate::framebuffer::{FrameBuffer, RawColor};
+
+/// The kind of refresh the display should do.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Refresh {
+    /// Refresh the whole display, removing ghosting artifacts.
+    Full,
+
+    /// Refresh only the given area of the display.
+    Partial(Rectangle),
+}
+
+/// Decides when and how an e-paper display should be refreshed.
+pub struct RefreshScheduler<T>
+where
+    T: TimeSource,
+{
+    time_source: T,
+    min_interval: u32,
+    max_partial_refreshes: u32,
+    full_refresh_ratio: u32,
+    partial_refreshes: u32,
+    last_refresh: Option<Instant>,
+    force_full: bool,
+}
+
+impl<T> RefreshScheduler<T>
+where
+    T: TimeSource,
+{
+    pub fn new(time_source: T) -> Self {
+        Self {
+            time_source,
+            min_interval: 0,
+            max_partial_refreshes: 5,
+            full_refresh_ratio: 50,
+            partial_refreshes: 0,
+            last_refresh: None,
+            force_full: true,
+        }
+    }
+
+    /// Sets the minimum time between refreshes, in milliseconds. Changes made in the meantime are
+    /// collected and displayed by the next refresh.
+    pub fn min_interval(mut self, millis: u32) -> Self {
+        self.min_interval = millis;
+        self
+    }
+
+    /// Sets the number of partial refreshes after which a full refresh is done to remove ghosting.
+    pub fn max_partial_refreshes(mut self, count: u32) -> Self {
+        self.max_partial_refreshes = count;
+        self
+    }
+
+    /// Sets the portion of the display, in percent, that needs to change to do a full refresh
+    /// instead of a partial one.
+    pub fn full_refresh_ratio(mut self, percent: u32) -> Self {
+        self.full_refresh_ratio = percent;
+        self
+    }
+
+    /// Makes the next refresh a full one.
+    pub fn request_full_refresh(&mut self) {
+        self.force_full = true;
+    }
+
+    /// Returns whether, and how the display should be refreshed with the contents of
+    /// `framebuffer`.
+    ///
+    /// When this method returns `Some`, the caller is expected to flush the framebuffer and refresh
+    /// the display. Otherwise, the changes remain in the framebuffer until a later call.
+    pub fn refresh<C, const WIDTH: usize, const HEIGHT: usize>(
+        &mut self,
+        framebuffer: &FrameBuffer<C, WIDTH, HEIGHT>,
+    ) -> Option<Refresh>
+    where
+        C: RawColor,
+    {
+        let now = self.time_source.now();
+        if let Some(last_refresh) = self.last_refresh {
+            if now.duration_since(last_refresh) < self.min_interval {
+                return None;
+            }
+        }
+
+        let dirty = match framebuffer.dirty_area() {
+            Some(area) => area,
+            // A full refresh also cleans the display when nothing has changed.
+            None if self.force_full && self.last_refresh.is_some() => {
+                return self.refreshed(now, Refresh::Full)
+            }
+            None => return None,
+        };
+
+        let dirty_pixels = dirty.size.width as u64 * dirty.size.height as u64;
+        let all_pixels = (WIDTH * HEIGHT) as u64;
+
+        let full = self.force_full
+            || self.partial_refreshes >= self.max_partial_refreshes
+            || dirty_pixels * 100 >= all_pixels * self.full_refresh_ratio as u64;
+
+        if full {
+            self.refreshed(now, Refresh::Full)
+        } else {
+            self.refreshed(now, Refresh::Partial(dirty))
+        }
+    }
+
+    fn refreshed(&mut self, now: Instant, refresh: Refresh) -> Option<Refresh> {
+        self.last_refresh = Some(now);
+
+        match refresh {
+            Refresh::Full => {
+                self.force_full = false;
+                self.partial_refreshes = 0;
+            }
+            Refresh::Partial(_) => self.partial_refreshes += 1,
+        }
+
+        Some(refresh)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use core::cell::Cell;
+
+    use embedded_graphics::{
+        geometry::{Point, Size},
+        mock_display::MockDisplay,
+        pixelcolor::BinaryColor,
+        primitives::Rectangle,
+        Drawable, Pixel,
+    };
+    use embedded_gui::time::{Instant, TimeSource};
+
+    use crate::{
+        epaper::{Refresh, RefreshScheduler},
+        framebuffer::FrameBuffer,
+    };
+
+    struct Clock(Cell<u32>);
+
+    impl TimeSource for Clock {
+        fn now(&self) -> Instant {
+            Instant::from_millis(self.0.get())
+        }
+    }
+
+    #[test]
+    fn refresh_decisions() {
+        let clock = Clock(Cell::new(0));
+        let mut framebuffer = FrameBuffer::<BinaryColor, 16, 16>::new(BinaryColor::Off);
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        let mut scheduler = RefreshScheduler::new(&clock)
+            .min_interval(100)
+            .max_partial_refreshes(2);
+
+        assert_eq!(scheduler.refresh(&framebuffer), Some(Refresh::Full));
+        framebuffer.flush(&mut display).unwrap();
+
+        // Changes are batched
+        Pixel(Point::new(1, 1), BinaryColor::On)
+            .draw(&mut framebuffer)
+            .unwrap();
+        assert_eq!(scheduler.refresh(&framebuffer), None);
+
+        clock.0.set(100);
+        Pixel(Point::new(3, 2), BinaryColor::On)
+            .draw(&mut framebuffer)
+            .unwrap();
+        assert_eq!(
+            scheduler.refresh(&framebuffer),
+            Some(Refresh::Partial(Rectangle::new(
+                Point::new(1, 1),
+                Size::new(3, 2)
+            )))
+        );
+        framebuffer.flush(&mut display).unwrap();
+
+        // Nothing changed
+        clock.0.set(200);
+        assert_eq!(scheduler.refresh(&framebuffer), None);
+
+        Pixel(Point::new(5, 5), BinaryColor::On)
+            .draw(&mut framebuffer)
+            .unwrap();
+        assert!(matches!(
+            scheduler.refresh(&framebuffer),
+            Some(Refresh::Partial(_))
+        ));
+        framebuffer.flush(&mut display).unwrap();
+
+        // Too many partial refreshes
+        clock.0.set(300);
+        Pixel(Point::new(6, 5), BinaryColor::On)
+            .draw(&mut framebuffer)
+            .unwrap();
+        assert_eq!(scheduler.refresh(&framebuffer), Some(Refresh::Full));
+    }
+}
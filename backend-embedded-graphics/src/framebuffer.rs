@@ -0,0 +1,573 @@
+//! In-memory framebuffer.
+//!
+//! Drawing directly to a display can cause visible flicker, e.g. when a widget first clears its
+//! background and then draws its content. It may also waste bandwidth, because every frame redraws
+//! pixels that didn't change. A [`FrameBuffer`] collects the frame in memory, and
+//! [`FrameBuffer::flush`] only sends the areas that changed since the previous flush to the display.
+//!
+//! ```rust,ignore
+//! let mut window = Window::new(EgCanvas::new(FrameBuffer::<Rgb565, 240, 240>::new(Rgb565::BLACK)), gui);
+//!
+//! loop {
+//!     window.update();
+//!     window.measure();
+//!     window.arrange();
+//!     window.draw().unwrap();
+//!
+//!     window.canvas.target.flush(&mut display)?;
+//! }
+//! ```
+//!
+//! # Change tracking
+//!
+//! The framebuffer doesn't keep a copy of what was sent to the display. Instead, it records the
+//! span of columns that were drawn to in each row, and a hash of each row as it was last sent. A
+//! row is sent when it was drawn to and its hash changed, so redrawing a frame with the same
+//! contents sends nothing.
+//!
+//! # Memory use
+//!
+//! A framebuffer stores one [`PixelColor::Raw`] value per pixel, plus 14 bytes per row, e.g. about
+//! 113 KiB for a 240 × 240 pixel `Rgb565` display. Colors with less than 8 bits per pixel still
+//! take a byte each. [`FrameBuffer::new`] returns the framebuffer by value, which places it on the
+//! stack at least temporarily. Large framebuffers should be initialized in place instead, e.g. in
+//! memory provided by `cortex_m::singleton!`:
+//!
+//! ```rust,ignore
+//! let place = cortex_m::singleton!(
+//!     : MaybeUninit<FrameBuffer<Rgb565, 240, 240>> = MaybeUninit::uninit()
+//! )
+//! .unwrap();
+//!
+//! let framebuffer = FrameBuffer::init(place, Rgb565::BLACK);
+//! let mut window = Window::new(EgCanvas::new(framebuffer), gui);
+//! ```
+
+use core::{
+    hash::{Hash, Hasher},
+    mem::MaybeUninit,
+    ops::Range,
+    ptr,
+};
+
+use embedded_graphics::{
+    draw_target::DrawTarget,
+    geometry::{Dimensions, OriginDimensions, Point, Size},
+    pixelcolor::PixelColor,
+    primitives::Rectangle,
+    Pixel,
+};
+
+/// Colors that a [`FrameBuffer`] can store in their raw representation.
+///
+/// Implemented for every color that converts to and from its [`PixelColor::Raw`] type, which
+/// includes the colors provided by `embedded-graphics`.
+pub trait RawColor: PixelColor {
+    /// The stored representation.
+    type Stored: Copy + PartialEq + Hash;
+
+    /// Converts the color into its stored representation.
+    fn into_stored(self) -> Self::Stored;
+
+    /// Converts a stored value back into the color.
+    fn from_stored(stored: Self::Stored) -> Self;
+}
+
+impl<C> RawColor for C
+where
+    C: PixelColor + From<C::Raw> + Into<C::Raw>,
+    C::Raw: Copy + PartialEq + Hash,
+{
+    type Stored = C::Raw;
+
+    fn into_stored(self) -> Self::Stored {
+        self.into()
+    }
+
+    fn from_stored(stored: Self::Stored) -> Self {
+        stored.into()
+    }
+}
+
+/// A `DrawTarget` that keeps a `WIDTH` × `HEIGHT` pixel image in memory.
+///
+/// See the [module documentation](self) for how changes are tracked, and how to place large
+/// framebuffers.
+pub struct FrameBuffer<C, const WIDTH: usize, const HEIGHT: usize>
+where
+    C: RawColor,
+{
+    frame: [[C::Stored; WIDTH]; HEIGHT],
+    /// The first and last column of each row that changed since the row was last sent.
+    drawn: [Option<(u16, u16)>; HEIGHT],
+    /// The hash of each row, as it was last sent.
+    hashes: [u64; HEIGHT],
+    /// Whether `hashes` match the contents of the display.
+    valid: bool,
+}
+
+impl<C, const WIDTH: usize, const HEIGHT: usize> FrameBuffer<C, WIDTH, HEIGHT>
+where
+    C: RawColor,
+{
+    /// Creates a framebuffer filled with `color`.
+    pub fn new(color: C) -> Self {
+        Self::check_width();
+
+        Self {
+            frame: [[color.into_stored(); WIDTH]; HEIGHT],
+            drawn: [None; HEIGHT],
+            hashes: [0; HEIGHT],
+            valid: false,
+        }
+    }
+
+    /// Initializes a framebuffer filled with `color` in `place`, without creating it on the stack.
+    pub fn init(place: &mut MaybeUninit<Self>, color: C) -> &mut Self {
+        Self::check_width();
+
+        let this = place.as_mut_ptr();
+        let color = color.into_stored();
+
+        // SAFETY: `this` points to memory that is valid for writes, and every field is written
+        // before the reference is created. The rows of the arrays are laid out contiguously.
+        unsafe {
+            let frame = ptr::addr_of_mut!((*this).frame) as *mut C::Stored;
+            for i in 0..WIDTH * HEIGHT {
+                frame.add(i).write(color);
+            }
+
+            let drawn = ptr::addr_of_mut!((*this).drawn) as *mut Option<(u16, u16)>;
+            let hashes = ptr::addr_of_mut!((*this).hashes) as *mut u64;
+            for y in 0..HEIGHT {
+                drawn.add(y).write(None);
+                hashes.add(y).write(0);
+            }
+            ptr::addr_of_mut!((*this).valid).write(false);
+
+            &mut *this
+        }
+    }
+
+    fn check_width() {
+        assert!(
+            WIDTH <= u16::MAX as usize + 1,
+            "FrameBuffer rows are limited to 65536 pixels"
+        );
+    }
+
+    /// Returns the color of the pixel at `point`, if it is inside the framebuffer.
+    pub fn pixel(&self, point: Point) -> Option<C> {
+        let (x, y) = Self::index(point)?;
+
+        Some(C::from_stored(self.frame[y][x]))
+    }
+
+    /// Forces the next [`FrameBuffer::flush`] to send the whole image, e.g. after the display was
+    /// drawn to by something else.
+    pub fn invalidate(&mut self) {
+        self.valid = false;
+    }
+
+    /// Sends the areas that changed since the last flush to `display`.
+    ///
+    /// The changed pixels of each row form a span. Adjacent rows with the same span are sent
+    /// together, as a single `fill_contiguous` call. Rows are only marked as sent when the display
+    /// accepted them, so the areas that failed are sent again by the next flush.
+    pub fn flush<D>(&mut self, display: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        // (first row, columns) of the area that is being collected
+        let mut pending: Option<(usize, (usize, usize))> = None;
+
+        for y in 0..HEIGHT {
+            let span = self.dirty_span(y);
+            if span.is_none() {
+                // The row may have been drawn to without changing it.
+                self.drawn[y] = None;
+            }
+
+            if let Some((first_row, columns)) = pending {
+                if span != Some(columns) {
+                    self.send(display, first_row..y, columns)?;
+                    pending = None;
+                }
+            }
+
+            if pending.is_none() {
+                pending = span.map(|columns| (y, columns));
+            }
+        }
+
+        if let Some((first_row, columns)) = pending {
+            self.send(display, first_row..HEIGHT, columns)?;
+        }
+
+        self.valid = true;
+
+        Ok(())
+    }
+
+    /// Returns the smallest rectangle that contains every pixel that changed since the last flush.
+    pub fn dirty_area(&self) -> Option<Rectangle> {
+        let mut rows = (0..HEIGHT).filter_map(|y| self.dirty_span(y).map(|span| (y, span)));
+
+        let (first_row, (mut first, mut last)) = rows.next()?;
+        let mut last_row = first_row;
+        for (y, (start, end)) in rows {
+            first = first.min(start);
+            last = last.max(end);
+            last_row = y;
+        }
+
+        Some(Rectangle::with_corners(
+            Point::new(first as i32, first_row as i32),
+            Point::new(last as i32, last_row as i32),
+        ))
+    }
+
+    /// Returns the first and last column that changed in row `y`.
+    fn dirty_span(&self, y: usize) -> Option<(usize, usize)> {
+        if !self.valid {
+            return if WIDTH > 0 {
+                Some((0, WIDTH - 1))
+            } else {
+                None
+            };
+        }
+
+        let (first, last) = self.drawn[y]?;
+        if self.row_hash(y) == self.hashes[y] {
+            return None;
+        }
+
+        Some((first as usize, last as usize))
+    }
+
+    fn row_hash(&self, y: usize) -> u64 {
+        let mut hasher = Fnv1a::default();
+        self.frame[y].hash(&mut hasher);
+        hasher.finish()
+    }
+
+    fn send<D>(
+        &mut self,
+        display: &mut D,
+        rows: Range<usize>,
+        (first, last): (usize, usize),
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = C>,
+    {
+        let area = Rectangle::new(
+            Point::new(first as i32, rows.start as i32),
+            Size::new((last - first + 1) as u32, rows.len() as u32),
+        );
+
+        let colors = self.frame[rows.clone()]
+            .iter()
+            .flat_map(|row| row[first..=last].iter().copied().map(C::from_stored));
+
+        display.fill_contiguous(&area, colors)?;
+
+        for y in rows {
+            self.drawn[y] = None;
+            self.hashes[y] = self.row_hash(y);
+        }
+
+        Ok(())
+    }
+
+    fn set(&mut self, x: usize, y: usize, color: C::Stored) {
+        let pixel = &mut self.frame[y][x];
+        if *pixel == color {
+            return;
+        }
+        *pixel = color;
+
+        let x = x as u16;
+        self.drawn[y] = Some(match self.drawn[y] {
+            Some((first, last)) => (first.min(x), last.max(x)),
+            None => (x, x),
+        });
+    }
+
+    fn index(point: Point) -> Option<(usize, usize)> {
+        if point.x < 0 || point.y < 0 {
+            return None;
+        }
+
+        let (x, y) = (point.x as usize, point.y as usize);
+        if x < WIDTH && y < HEIGHT {
+            Some((x, y))
+        } else {
+            None
+        }
+    }
+}
+
+/// The 64 bit FNV-1a hash function.
+struct Fnv1a(u64);
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for Fnv1a {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 ^ byte as u64).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+impl<C, const WIDTH: usize, const HEIGHT: usize> OriginDimensions for FrameBuffer<C, WIDTH, HEIGHT>
+where
+    C: RawColor,
+{
+    fn size(&self) -> Size {
+        Size::new(WIDTH as u32, HEIGHT as u32)
+    }
+}
+
+impl<C, const WIDTH: usize, const HEIGHT: usize> DrawTarget for FrameBuffer<C, WIDTH, HEIGHT>
+where
+    C: RawColor,
+{
+    type Color = C;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            if let Some((x, y)) = Self::index(point) {
+                self.set(x, y, color.into_stored());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let area = area.intersection(&self.bounding_box());
+        let color = color.into_stored();
+
+        for y in area.rows() {
+            for x in area.columns() {
+                self.set(x as usize, y as usize, color);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        let color = color.into_stored();
+
+        for y in 0..HEIGHT {
+            for x in 0..WIDTH {
+                self.set(x, y, color);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Framebuffers initialized with [`FrameBuffer::init`] are drawn to by reference.
+impl<C, const WIDTH: usize, const HEIGHT: usize> OriginDimensions
+    for &mut FrameBuffer<C, WIDTH, HEIGHT>
+where
+    C: RawColor,
+{
+    fn size(&self) -> Size {
+        (**self).size()
+    }
+}
+
+impl<C, const WIDTH: usize, const HEIGHT: usize> DrawTarget for &mut FrameBuffer<C, WIDTH, HEIGHT>
+where
+    C: RawColor,
+{
+    type Color = C;
+    type Error = core::convert::Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        (**self).draw_iter(pixels)
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        (**self).fill_solid(area, color)
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        (**self).clear(color)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use core::mem::MaybeUninit;
+
+    use embedded_graphics::{
+        draw_target::DrawTarget,
+        geometry::{OriginDimensions, Point, Size},
+        pixelcolor::BinaryColor,
+        primitives::{Primitive, PrimitiveStyle, Rectangle},
+        Drawable, Pixel,
+    };
+    use heapless::Vec;
+
+    use crate::framebuffer::FrameBuffer;
+
+    /// Records the areas it receives.
+    #[derive(Default)]
+    struct Display {
+        areas: Vec<Rectangle, 8>,
+        /// The number of areas that are accepted before the display fails.
+        fail_after: Option<usize>,
+    }
+
+    impl OriginDimensions for Display {
+        fn size(&self) -> Size {
+            Size::new(8, 8)
+        }
+    }
+
+    impl DrawTarget for Display {
+        type Color = BinaryColor;
+        type Error = ();
+
+        fn draw_iter<I>(&mut self, _pixels: I) -> Result<(), Self::Error>
+        where
+            I: IntoIterator<Item = Pixel<Self::Color>>,
+        {
+            unimplemented!()
+        }
+
+        fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+        where
+            I: IntoIterator<Item = Self::Color>,
+        {
+            if self.fail_after == Some(self.areas.len()) {
+                return Err(());
+            }
+            assert_eq!(
+                colors.into_iter().count() as u32,
+                area.size.width * area.size.height
+            );
+            self.areas.push(*area).unwrap();
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn flush_sends_changed_areas() {
+        let mut framebuffer = FrameBuffer::<BinaryColor, 8, 8>::new(BinaryColor::Off);
+        let mut display = Display::default();
+
+        // The first flush sends everything
+        framebuffer.flush(&mut display).unwrap();
+        assert_eq!(
+            display.areas,
+            [Rectangle::new(Point::zero(), Size::new(8, 8))]
+        );
+
+        display.areas.clear();
+        framebuffer.flush(&mut display).unwrap();
+        assert!(display.areas.is_empty());
+
+        Rectangle::new(Point::new(1, 2), Size::new(3, 2))
+            .into_styled(PrimitiveStyle::with_fill(BinaryColor::On))
+            .draw(&mut framebuffer)
+            .unwrap();
+        Pixel(Point::new(6, 5), BinaryColor::On)
+            .draw(&mut framebuffer)
+            .unwrap();
+
+        framebuffer.flush(&mut display).unwrap();
+        assert_eq!(
+            display.areas,
+            [
+                Rectangle::new(Point::new(1, 2), Size::new(3, 2)),
+                Rectangle::new(Point::new(6, 5), Size::new(1, 1)),
+            ]
+        );
+        assert_eq!(framebuffer.pixel(Point::new(6, 5)), Some(BinaryColor::On));
+    }
+
+    #[test]
+    fn init_in_place() {
+        let mut place = MaybeUninit::uninit();
+        let mut framebuffer = FrameBuffer::<BinaryColor, 8, 8>::init(&mut place, BinaryColor::On);
+        let mut display = Display::default();
+
+        assert_eq!(framebuffer.pixel(Point::new(7, 7)), Some(BinaryColor::On));
+
+        framebuffer.flush(&mut display).unwrap();
+        display.areas.clear();
+
+        // Drawing through the reference
+        Pixel(Point::new(2, 3), BinaryColor::Off)
+            .draw(&mut framebuffer)
+            .unwrap();
+        framebuffer.flush(&mut display).unwrap();
+        assert_eq!(
+            display.areas,
+            [Rectangle::new(Point::new(2, 3), Size::new(1, 1))]
+        );
+
+        // Only the changed pixels are sent after clearing and redrawing
+        display.areas.clear();
+        framebuffer.clear(BinaryColor::On).unwrap();
+        Pixel(Point::new(2, 3), BinaryColor::Off)
+            .draw(&mut framebuffer)
+            .unwrap();
+        framebuffer.flush(&mut display).unwrap();
+        assert!(display.areas.is_empty());
+    }
+
+    #[test]
+    fn failed_areas_are_sent_again() {
+        let mut framebuffer = FrameBuffer::<BinaryColor, 8, 8>::new(BinaryColor::Off);
+        let mut display = Display::default();
+        framebuffer.flush(&mut display).unwrap();
+
+        Pixel(Point::new(1, 1), BinaryColor::On)
+            .draw(&mut framebuffer)
+            .unwrap();
+        Pixel(Point::new(5, 4), BinaryColor::On)
+            .draw(&mut framebuffer)
+            .unwrap();
+
+        // The first area is sent, the second one fails
+        display.areas.clear();
+        display.fail_after = Some(1);
+        assert_eq!(framebuffer.flush(&mut display), Err(()));
+        assert_eq!(
+            framebuffer.dirty_area(),
+            Some(Rectangle::new(Point::new(5, 4), Size::new(1, 1)))
+        );
+
+        display.areas.clear();
+        display.fail_after = None;
+        framebuffer.flush(&mut display).unwrap();
+        assert_eq!(
+            display.areas,
+            [Rectangle::new(Point::new(5, 4), Size::new(1, 1))]
+        );
+        assert_eq!(framebuffer.dirty_area(), None);
+    }
+}
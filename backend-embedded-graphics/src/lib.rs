@@ -21,6 +21,18 @@
 //! in these widgets. For example, a `Label` constructed by importing the
 //! [widgets::label::iso_8859_2::LabelConstructor] trait will support the `iso_8859_2` character set.
 //!
+//! Display orientation
+//! -------------------
+//!
+//! Panels that are mounted rotated or mirrored can be wrapped in an
+//! [`OrientedDrawTarget`](orientation::OrientedDrawTarget).
+//!
+//! Testing
+//! -------
+//!
+//! With the `std` feature enabled, the [`snapshot`] module provides a headless window for
+//! golden-image tests of widget rendering.
+//!
 //! Themes
 //! ------
 //!
@@ -36,18 +48,29 @@
 
 #![no_std]
 
+#[cfg(feature = "std")]
+extern crate std;
+
+pub mod epaper;
+pub mod framebuffer;
+pub mod orientation;
+pub mod shared;
+#[cfg(feature = "std")]
+pub mod snapshot;
 pub mod themes;
 pub mod widgets;
 
 use embedded_graphics::{
     draw_target::DrawTarget,
-    prelude::{Point, Size},
-    primitives::Rectangle,
+    prelude::{Dimensions, Point, Size},
+    primitives::{PointsIter, Rectangle},
+    Pixel,
 };
 use embedded_gui::{
     geometry::{BoundingBox, MeasuredSize, Position},
     Canvas,
 };
+use heapless::Vec;
 
 trait ToPoint {
     fn to_point(self) -> Point;
@@ -82,11 +105,21 @@ impl ToRectangle for BoundingBox {
     }
 }
 
+/// The maximum number of nested clip areas.
+const MAX_CLIP_DEPTH: usize = 16;
+
+/// A canvas that draws to an `embedded-graphics` `DrawTarget`.
+///
+/// `EgCanvas` is itself a `DrawTarget` that only draws inside the current clip area, so renderers
+/// should draw to the canvas instead of directly to `target`.
 pub struct EgCanvas<D>
 where
     D: DrawTarget,
 {
     pub target: D,
+    clip_stack: Vec<Rectangle, MAX_CLIP_DEPTH>,
+    /// Number of clip areas that didn't fit on the stack.
+    overflow: usize,
 }
 
 impl<D> EgCanvas<D>
@@ -94,7 +127,19 @@ where
     D: DrawTarget,
 {
     pub fn new(target: D) -> Self {
-        Self { target }
+        Self {
+            target,
+            clip_stack: Vec::new(),
+            overflow: 0,
+        }
+    }
+
+    /// Returns the area drawing is currently restricted to.
+    pub fn clip_area(&self) -> Rectangle {
+        match self.clip_stack.last() {
+            Some(area) => *area,
+            None => self.target.bounding_box(),
+        }
     }
 }
 
@@ -109,4 +154,170 @@ impl<D: DrawTarget> Canvas for EgCanvas<D> {
             height: size.height,
         }
     }
+
+    fn push_clip(&mut self, bounds: BoundingBox) {
+        let area = self.clip_area().intersection(&bounds.to_rectangle());
+
+        if self.clip_stack.push(area).is_err() {
+            // Too deeply nested, keep drawing with the innermost clip area we could store
+            self.overflow += 1;
+        }
+    }
+
+    fn pop_clip(&mut self) {
+        if self.overflow > 0 {
+            self.overflow -= 1;
+        } else {
+            self.clip_stack.pop();
+        }
+    }
+}
+
+impl<D> Dimensions for EgCanvas<D>
+where
+    D: DrawTarget,
+{
+    fn bounding_box(&self) -> Rectangle {
+        self.target.bounding_box()
+    }
+}
+
+impl<D> DrawTarget for EgCanvas<D>
+where
+    D: DrawTarget,
+{
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let area = self.clip_area();
+
+        self.target.draw_iter(
+            pixels
+                .into_iter()
+                .filter(|Pixel(point, _)| area.contains(*point)),
+        )
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        let clip_area = self.clip_area();
+        let visible = area.intersection(&clip_area);
+
+        if visible == *area {
+            self.target.fill_contiguous(area, colors)
+        } else {
+            self.target.draw_iter(
+                area.points()
+                    .zip(colors)
+                    .filter(|(point, _)| clip_area.contains(*point))
+                    .map(|(point, color)| Pixel(point, color)),
+            )
+        }
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        let visible = area.intersection(&self.clip_area());
+
+        self.target.fill_solid(&visible, color)
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        let area = self.clip_area();
+
+        self.target.fill_solid(&area, color)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use embedded_graphics::{
+        draw_target::DrawTarget,
+        geometry::{Point, Size},
+        mock_display::MockDisplay,
+        pixelcolor::BinaryColor,
+        primitives::Rectangle,
+    };
+    use embedded_gui::{
+        geometry::{BoundingBox, MeasuredSize, Position},
+        Canvas,
+    };
+
+    use crate::{EgCanvas, MAX_CLIP_DEPTH};
+
+    fn bounds(x: i32, y: i32, width: u32, height: u32) -> BoundingBox {
+        BoundingBox {
+            position: Position { x, y },
+            size: MeasuredSize { width, height },
+        }
+    }
+
+    #[test]
+    fn nested_clip_areas_intersect() {
+        let mut canvas = EgCanvas::new(MockDisplay::<BinaryColor>::new());
+        let full = Rectangle::new(Point::zero(), Size::new(64, 64));
+        assert_eq!(canvas.clip_area(), full);
+
+        canvas.push_clip(bounds(0, 0, 8, 8));
+        canvas.push_clip(bounds(4, 2, 8, 8));
+        assert_eq!(
+            canvas.clip_area(),
+            Rectangle::new(Point::new(4, 2), Size::new(4, 6))
+        );
+
+        canvas.fill_solid(&full, BinaryColor::On).unwrap();
+
+        let mut expected = MockDisplay::new();
+        expected
+            .fill_solid(
+                &Rectangle::new(Point::new(4, 2), Size::new(4, 6)),
+                BinaryColor::On,
+            )
+            .unwrap();
+        canvas.target.assert_eq(&expected);
+
+        canvas.pop_clip();
+        assert_eq!(
+            canvas.clip_area(),
+            Rectangle::new(Point::zero(), Size::new(8, 8))
+        );
+        canvas.pop_clip();
+        assert_eq!(canvas.clip_area(), full);
+    }
+
+    #[test]
+    fn clip_stack_overflow() {
+        let mut canvas = EgCanvas::new(MockDisplay::<BinaryColor>::new());
+        let depth = MAX_CLIP_DEPTH as i32 + 4;
+
+        for i in 0..depth {
+            canvas.push_clip(bounds(i, 0, 64, 64));
+        }
+
+        // The innermost clip areas that didn't fit are ignored
+        let innermost = Rectangle::new(
+            Point::new(MAX_CLIP_DEPTH as i32 - 1, 0),
+            Size::new(64 - MAX_CLIP_DEPTH as u32 + 1, 64),
+        );
+        assert_eq!(canvas.clip_area(), innermost);
+
+        // Popping them leaves the stored ones in place
+        for _ in 0..4 {
+            canvas.pop_clip();
+        }
+        assert_eq!(canvas.clip_area(), innermost);
+
+        for _ in 4..depth {
+            canvas.pop_clip();
+        }
+        assert_eq!(
+            canvas.clip_area(),
+            Rectangle::new(Point::zero(), Size::new(64, 64))
+        );
+    }
 }
@@ -0,0 +1,269 @@
+//! Rotated and mirrored displays.
+//!
+//! Panels are often mounted in a different orientation than their native one. Wrapping the
+//! `DrawTarget` of such a panel into an [`OrientedDrawTarget`] makes the widget tree lay itself out
+//! for the rotated size and transforms everything it draws.
+//!
+//! Pointer positions reported by a touch panel that is attached to the display need the same
+//! transformation. Configure the input controller using [`OrientedDrawTarget::orientation`] and
+//! [`OrientedDrawTarget::native_size`]:
+//!
+//! ```rust,ignore
+//! let target = OrientedDrawTarget::new(display, Orientation::new(Rotation::Rotate90));
+//! let input = InputTransform::new(DefaultInputController::new())
+//!     .orientation(target.orientation(), target.native_size());
+//!
+//! let mut window = Window::new(EgCanvas::new(target), gui).with_input_controller(input);
+//! ```
+
+use embedded_graphics::{
+    draw_target::DrawTarget,
+    geometry::{Dimensions, Point},
+    primitives::{PointsIter, Rectangle},
+    Pixel,
+};
+use embedded_gui::geometry::{
+    orientation::{Orientation, Rotation},
+    MeasuredSize, Position,
+};
+
+use crate::ToSize;
+
+/// A `DrawTarget` adapter that rotates and mirrors everything drawn to the wrapped target.
+pub struct OrientedDrawTarget<D>
+where
+    D: DrawTarget,
+{
+    target: D,
+    orientation: Orientation,
+}
+
+impl<D> OrientedDrawTarget<D>
+where
+    D: DrawTarget,
+{
+    pub fn new(target: D, orientation: Orientation) -> Self {
+        Self {
+            target,
+            orientation,
+        }
+    }
+
+    pub fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    pub fn set_orientation(&mut self, orientation: Orientation) {
+        self.orientation = orientation;
+    }
+
+    /// Returns the size of the wrapped target, in its native orientation.
+    pub fn native_size(&self) -> MeasuredSize {
+        let size = self.target.bounding_box().size;
+
+        MeasuredSize {
+            width: size.width,
+            height: size.height,
+        }
+    }
+
+    /// Maps a position reported in native display coordinates (e.g. by a touch panel) to the
+    /// coordinates the widgets are laid out in.
+    pub fn map_position(&self, position: Position) -> Position {
+        self.orientation.transform(position, self.native_size())
+    }
+
+    pub fn inner(&self) -> &D {
+        &self.target
+    }
+
+    pub fn inner_mut(&mut self) -> &mut D {
+        &mut self.target
+    }
+
+    pub fn into_inner(self) -> D {
+        self.target
+    }
+
+    fn to_native(&self, point: Point) -> Point {
+        let origin = self.target.bounding_box().top_left;
+        let native = self.orientation.inverse_transform(
+            Position {
+                x: point.x,
+                y: point.y,
+            },
+            self.native_size(),
+        );
+
+        origin + Point::new(native.x, native.y)
+    }
+
+    fn to_native_rectangle(&self, area: &Rectangle) -> Option<Rectangle> {
+        let bottom_right = area.bottom_right()?;
+
+        Some(Rectangle::with_corners(
+            self.to_native(area.top_left),
+            self.to_native(bottom_right),
+        ))
+    }
+}
+
+impl<D> Dimensions for OrientedDrawTarget<D>
+where
+    D: DrawTarget,
+{
+    fn bounding_box(&self) -> Rectangle {
+        let size = self.orientation.transform_size(self.native_size());
+
+        Rectangle::new(Point::zero(), size.to_size())
+    }
+}
+
+impl<D> DrawTarget for OrientedDrawTarget<D>
+where
+    D: DrawTarget,
+{
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let origin = self.target.bounding_box().top_left;
+        let native_size = self.native_size();
+        let orientation = self.orientation;
+
+        self.target
+            .draw_iter(pixels.into_iter().map(|Pixel(point, color)| {
+                let native = orientation.inverse_transform(
+                    Position {
+                        x: point.x,
+                        y: point.y,
+                    },
+                    native_size,
+                );
+
+                Pixel(origin + Point::new(native.x, native.y), color)
+            }))
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        // The order of the pixels only matches the native order without rotation and mirroring.
+        if self.orientation == Orientation::new(Rotation::Rotate0) {
+            let origin = self.target.bounding_box().top_left;
+            let native = Rectangle::new(area.top_left + origin, area.size);
+
+            return self.target.fill_contiguous(&native, colors);
+        }
+
+        self.draw_iter(
+            area.points()
+                .zip(colors)
+                .map(|(point, color)| Pixel(point, color)),
+        )
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        // Rectangles stay rectangles.
+        match self.to_native_rectangle(area) {
+            Some(native) => self.target.fill_solid(&native, color),
+            None => Ok(()),
+        }
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.target.clear(color)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use embedded_graphics::{
+        draw_target::DrawTarget,
+        geometry::{Dimensions, Point, Size},
+        mock_display::MockDisplay,
+        pixelcolor::BinaryColor,
+        primitives::{PointsIter, Rectangle},
+        Drawable, Pixel,
+    };
+    use embedded_gui::geometry::orientation::{Orientation, Rotation};
+
+    use crate::orientation::OrientedDrawTarget;
+
+    /// Draws a pixel at (1, 0) and returns where it ends up on the 64 × 64 pixel mock display.
+    fn draw_pixel(orientation: Orientation) -> Point {
+        let mut target = OrientedDrawTarget::new(MockDisplay::new(), orientation);
+        Pixel(Point::new(1, 0), BinaryColor::On)
+            .draw(&mut target)
+            .unwrap();
+
+        let display = target.into_inner();
+        let on = display
+            .bounding_box()
+            .points()
+            .filter(|&point| display.get_pixel(point) == Some(BinaryColor::On))
+            .collect::<heapless::Vec<_, 2>>();
+        assert_eq!(on.len(), 1, "{:?}", orientation);
+
+        on[0]
+    }
+
+    #[test]
+    fn pixels_are_transformed() {
+        let orientation = Orientation::new;
+
+        assert_eq!(draw_pixel(orientation(Rotation::Rotate0)), Point::new(1, 0));
+        assert_eq!(
+            draw_pixel(orientation(Rotation::Rotate90)),
+            Point::new(0, 62)
+        );
+        assert_eq!(
+            draw_pixel(orientation(Rotation::Rotate180)),
+            Point::new(62, 63)
+        );
+        assert_eq!(
+            draw_pixel(orientation(Rotation::Rotate270)),
+            Point::new(63, 1)
+        );
+
+        assert_eq!(
+            draw_pixel(orientation(Rotation::Rotate0).mirror_x(true)),
+            Point::new(62, 0)
+        );
+        assert_eq!(
+            draw_pixel(orientation(Rotation::Rotate0).mirror_y(true)),
+            Point::new(1, 63)
+        );
+        // Mirroring happens in native coordinates
+        assert_eq!(
+            draw_pixel(orientation(Rotation::Rotate90).mirror_x(true)),
+            Point::new(63, 62)
+        );
+    }
+
+    #[test]
+    fn rectangles_are_transformed() {
+        let mut target =
+            OrientedDrawTarget::new(MockDisplay::new(), Orientation::new(Rotation::Rotate90));
+        target
+            .fill_solid(
+                &Rectangle::new(Point::new(0, 0), Size::new(3, 1)),
+                BinaryColor::On,
+            )
+            .unwrap();
+
+        let mut expected = MockDisplay::new();
+        expected
+            .fill_solid(
+                &Rectangle::new(Point::new(0, 61), Size::new(1, 3)),
+                BinaryColor::On,
+            )
+            .unwrap();
+
+        target.into_inner().assert_eq(&expected);
+    }
+}
@@ -0,0 +1,86 @@
+//! Sharing a display between multiple canvases.
+//!
+//! A [`Window`] owns its canvas. To draw multiple windows (or a window and other, non-GUI content)
+//! to the same display, put the display into a `RefCell` and give each canvas a
+//! [`SharedDrawTarget`]:
+//!
+//! ```rust,ignore
+//! let display = RefCell::new(display);
+//!
+//! let mut status_bar = Window::new(EgCanvas::new(SharedDrawTarget::new(&display)), status)
+//!     .with_viewport(BoundingBox {
+//!         position: Position { x: 0, y: 0 },
+//!         size: MeasuredSize { width: 128, height: 16 },
+//!     });
+//! let mut main = Window::new(EgCanvas::new(SharedDrawTarget::new(&display)), gui)
+//!     .with_viewport(BoundingBox {
+//!         position: Position { x: 0, y: 16 },
+//!         size: MeasuredSize { width: 128, height: 48 },
+//!     });
+//! ```
+//!
+//! [`Window`]: embedded_gui::Window
+
+use core::cell::RefCell;
+
+use embedded_graphics::{
+    draw_target::DrawTarget, geometry::Dimensions, primitives::Rectangle, Pixel,
+};
+
+/// A `DrawTarget` that draws to a display that is shared using a `RefCell`.
+///
+/// The display is only borrowed while drawing.
+pub struct SharedDrawTarget<'a, D>
+where
+    D: DrawTarget,
+{
+    target: &'a RefCell<D>,
+}
+
+impl<'a, D> SharedDrawTarget<'a, D>
+where
+    D: DrawTarget,
+{
+    pub fn new(target: &'a RefCell<D>) -> Self {
+        Self { target }
+    }
+}
+
+impl<D> Dimensions for SharedDrawTarget<'_, D>
+where
+    D: DrawTarget,
+{
+    fn bounding_box(&self) -> Rectangle {
+        self.target.borrow().bounding_box()
+    }
+}
+
+impl<D> DrawTarget for SharedDrawTarget<'_, D>
+where
+    D: DrawTarget,
+{
+    type Color = D::Color;
+    type Error = D::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        self.target.borrow_mut().draw_iter(pixels)
+    }
+
+    fn fill_contiguous<I>(&mut self, area: &Rectangle, colors: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Self::Color>,
+    {
+        self.target.borrow_mut().fill_contiguous(area, colors)
+    }
+
+    fn fill_solid(&mut self, area: &Rectangle, color: Self::Color) -> Result<(), Self::Error> {
+        self.target.borrow_mut().fill_solid(area, color)
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.target.borrow_mut().clear(color)
+    }
+}
@@ -0,0 +1,324 @@
+//! Headless rendering and golden-image snapshot testing.
+//!
+//! Requires the `std` feature.
+//!
+//! A [`TestWindow`] renders a widget tree into an in-memory [`FrameBuffer`]. The rendered image can
+//! be converted to text, and compared to a snapshot file that is checked into the repository:
+//!
+//! ```rust,ignore
+//! let mut window = TestWindow::<_, BinaryColor, 64, 16>::new(BinaryColor::primary_button("Click"));
+//!
+//! window.render();
+//! window.assert_snapshot("tests/snapshots/button.txt");
+//!
+//! window.click(Position { x: 8, y: 8 });
+//! window.render();
+//! window.assert_snapshot("tests/snapshots/button_clicked.txt");
+//! ```
+//!
+//! # Driving the UI
+//!
+//! Instead of pixel positions, input can also target widgets, by index or by the text they display.
+//! This allows testing application logic through the UI, without rendering:
+//!
+//! ```rust,ignore
+//! let counter = BoundData::new(0, |_| ());
+//! let mut window = TestWindow::<_, BinaryColor, 64, 16>::new(
+//!     BinaryColor::primary_button("Add").bind(&counter).on_clicked(|data| *data += 1),
+//! );
+//!
+//! window.click_text("Add");
+//! assert_eq!(counter.with_data(|count| *count), 1);
+//! ```
+//!
+//! # Snapshot format
+//!
+//! Snapshots begin with a legend that assigns a character to each color of the image, followed by
+//! one line of characters for each row of pixels:
+//!
+//! ```text
+//! . = #000000
+//! # = #FFFFFF
+//! .....
+//! .###.
+//! .....
+//! ```
+//!
+//! When the `UPDATE_SNAPSHOTS` environment variable is set, [`TestWindow::assert_snapshot`]
+//! (re)creates the snapshot file instead of comparing against it. Otherwise, missing snapshot files
+//! and mismatches fail the test, and the rendered image is saved next to the snapshot file with a
+//! `.new` extension.
+
+use std::{env, fmt::Write as _, fs, path::Path, string::String, vec::Vec};
+
+use embedded_graphics::{
+    draw_target::DrawTarget,
+    geometry::Point,
+    pixelcolor::{Rgb888, RgbColor},
+};
+use embedded_gui::{
+    geometry::Position,
+    input::{
+        controller::DefaultInputController,
+        event::{InputEvent, PointerEvent},
+    },
+    widgets::Widget,
+    WidgetRenderer, Window,
+};
+
+use crate::{
+    framebuffer::{FrameBuffer, RawColor},
+    themes::Theme,
+    EgCanvas,
+};
+
+/// Characters used to represent colors, in the order of their first appearance.
+const SYMBOLS: &str = ".#+*oxO=%@abcdefghijklmnopqrstuvwyzABCDEFGHIJKLMNPQRSTUVWXYZ0123456789";
+
+/// The canvas type of a [`TestWindow`].
+pub type TestCanvas<C, const WIDTH: usize, const HEIGHT: usize> =
+    EgCanvas<FrameBuffer<C, WIDTH, HEIGHT>>;
+
+/// A `WIDTH` × `HEIGHT` pixel window that renders into memory.
+pub struct TestWindow<W, C, const WIDTH: usize, const HEIGHT: usize>
+where
+    W: Widget + WidgetRenderer<TestCanvas<C, WIDTH, HEIGHT>>,
+    C: RawColor + Into<Rgb888>,
+{
+    pub window: Window<TestCanvas<C, WIDTH, HEIGHT>, W, DefaultInputController>,
+    background: C,
+}
+
+impl<W, C, const WIDTH: usize, const HEIGHT: usize> TestWindow<W, C, WIDTH, HEIGHT>
+where
+    W: Widget + WidgetRenderer<TestCanvas<C, WIDTH, HEIGHT>>,
+    C: Theme + RawColor + Into<Rgb888>,
+{
+    /// Creates a window with the theme's background color.
+    pub fn new(root: W) -> Self {
+        Self::with_background(root, C::BACKGROUND_COLOR)
+    }
+}
+
+impl<W, C, const WIDTH: usize, const HEIGHT: usize> TestWindow<W, C, WIDTH, HEIGHT>
+where
+    W: Widget + WidgetRenderer<TestCanvas<C, WIDTH, HEIGHT>>,
+    C: RawColor + Into<Rgb888>,
+{
+    pub fn with_background(root: W, background: C) -> Self {
+        Self {
+            window: Window::new(EgCanvas::new(FrameBuffer::new(background)), root),
+            background,
+        }
+    }
+
+    /// Updates and lays out the widget tree.
+    pub fn layout(&mut self) {
+        self.window.update();
+        self.window.measure();
+        self.window.arrange();
+    }
+
+    /// Updates, lays out and draws the widget tree on a clear background.
+    pub fn render(&mut self) {
+        let background = self.background;
+        self.window.canvas.target.clear(background).unwrap();
+
+        self.layout();
+        self.window.draw().unwrap();
+    }
+
+    pub fn input_event(&mut self, event: InputEvent) {
+        self.window.input_event(event);
+    }
+
+    /// Presses the pointer at `position`, without releasing it.
+    pub fn press(&mut self, position: Position) {
+        self.input_event(InputEvent::PointerEvent(position, PointerEvent::Hover));
+        self.input_event(InputEvent::PointerEvent(position, PointerEvent::Down));
+    }
+
+    /// Presses and releases the pointer at `position`.
+    pub fn click(&mut self, position: Position) {
+        self.press(position);
+        self.input_event(InputEvent::PointerEvent(position, PointerEvent::Up));
+    }
+
+    /// Returns the center of the widget at `index`, using the current layout.
+    ///
+    /// See [`Window::widget`] for how widgets are indexed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no widget at `index`.
+    pub fn widget_center(&self, index: usize) -> Position {
+        let bounds = self
+            .window
+            .widget(index)
+            .unwrap_or_else(|| panic!("No widget at index {}", index))
+            .bounding_box();
+
+        Position {
+            x: bounds.position.x + bounds.size.width as i32 / 2,
+            y: bounds.position.y + bounds.size.height as i32 / 2,
+        }
+    }
+
+    /// Lays out the widget tree, clicks the center of the widget at `index`, and processes the
+    /// consequences of the click.
+    pub fn click_widget(&mut self, index: usize) {
+        self.layout();
+        self.click(self.widget_center(index));
+        self.layout();
+    }
+
+    /// Lays out the widget tree, clicks the first widget that displays `text` (e.g. the label of a
+    /// button), and processes the consequences of the click.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no widget displays `text`.
+    pub fn click_text(&mut self, text: &str) {
+        self.layout();
+        let index = self
+            .window
+            .find_text(text)
+            .unwrap_or_else(|| panic!("No widget displays {:?}", text));
+
+        self.click_widget(index);
+    }
+
+    /// Returns the color of the pixel at `position`.
+    pub fn pixel(&self, position: Position) -> Option<C> {
+        self.window
+            .canvas
+            .target
+            .pixel(Point::new(position.x, position.y))
+    }
+
+    /// Returns the rendered image in the snapshot format.
+    pub fn snapshot(&self) -> String {
+        let mut colors = Vec::<Rgb888>::new();
+        let mut rows = String::new();
+
+        for y in 0..HEIGHT {
+            for x in 0..WIDTH {
+                let color: Rgb888 = self
+                    .window
+                    .canvas
+                    .target
+                    .pixel(Point::new(x as i32, y as i32))
+                    .unwrap()
+                    .into();
+
+                let index = match colors.iter().position(|c| *c == color) {
+                    Some(index) => index,
+                    None => {
+                        colors.push(color);
+                        colors.len() - 1
+                    }
+                };
+
+                rows.push(symbol(index));
+            }
+            rows.push('\n');
+        }
+
+        let mut snapshot = String::new();
+        for (index, color) in colors.iter().enumerate() {
+            writeln!(
+                snapshot,
+                "{} = #{:02X}{:02X}{:02X}",
+                symbol(index),
+                color.r(),
+                color.g(),
+                color.b()
+            )
+            .unwrap();
+        }
+        snapshot.push_str(&rows);
+
+        snapshot
+    }
+
+    /// Compares the rendered image to the snapshot file at `path`.
+    ///
+    /// # Panics
+    ///
+    /// Panics with a description of the differences if the image doesn't match the snapshot, or if
+    /// the snapshot file doesn't exist.
+    pub fn assert_snapshot(&self, path: impl AsRef<Path>) {
+        let path = path.as_ref();
+        let actual = self.snapshot();
+
+        if env::var_os("UPDATE_SNAPSHOTS").is_some() {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir).unwrap();
+            }
+            fs::write(path, &actual).unwrap();
+            return;
+        }
+
+        let new_path = path.with_extension("new");
+        let expected = match fs::read_to_string(path) {
+            Ok(expected) => expected,
+            Err(error) => {
+                fs::write(&new_path, &actual).unwrap();
+                panic!(
+                    "Failed to read snapshot {}: {}\nThe rendered image was saved to {}. \
+                     Set UPDATE_SNAPSHOTS=1 to create the snapshot.",
+                    path.display(),
+                    error,
+                    new_path.display()
+                );
+            }
+        };
+
+        // Don't trip over line endings changed by version control
+        let expected = expected.replace("\r\n", "\n");
+        if expected == actual {
+            return;
+        }
+
+        fs::write(&new_path, &actual).unwrap();
+
+        panic!(
+            "Rendered image doesn't match snapshot {}\n{}\nThe rendered image was saved to {}. \
+             Set UPDATE_SNAPSHOTS=1 to accept it.",
+            path.display(),
+            diff(&expected, &actual),
+            new_path.display()
+        );
+    }
+}
+
+fn symbol(index: usize) -> char {
+    SYMBOLS.chars().nth(index).expect("Too many colors")
+}
+
+/// Lists the differing lines of two snapshots.
+fn diff(expected: &str, actual: &str) -> String {
+    let mut expected_lines = expected.lines();
+    let mut actual_lines = actual.lines();
+
+    let mut result = String::new();
+    let mut line = 1;
+    loop {
+        match (expected_lines.next(), actual_lines.next()) {
+            (None, None) => break,
+            (e, a) if e != a => {
+                writeln!(result, "line {}:", line).unwrap();
+                if let Some(e) = e {
+                    writeln!(result, "- {}", e).unwrap();
+                }
+                if let Some(a) = a {
+                    writeln!(result, "+ {}", a).unwrap();
+                }
+            }
+            _ => {}
+        }
+        line += 1;
+    }
+
+    result
+}
@@ -77,14 +77,14 @@ where
             .bounds
             .to_rectangle()
             .into_styled(bg_style)
-            .draw(&mut canvas.target)?;
+            .draw(canvas)?;
 
         // Foreground
         slider
             .slider_bounds()
             .to_rectangle()
             .into_styled(slider_style)
-            .draw(&mut canvas.target)
+            .draw(canvas)
     }
 }
 
@@ -83,14 +83,14 @@ where
 
         Line::new(Point::new(x0, y), Point::new(x1, y))
             .into_styled(bg_style)
-            .draw(&mut canvas.target)?;
+            .draw(canvas)?;
 
         // Foreground
         slider
             .slider_bounds()
             .to_rectangle()
             .into_styled(slider_style)
-            .draw(&mut canvas.target)
+            .draw(canvas)
     }
 }
 
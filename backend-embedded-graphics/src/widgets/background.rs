@@ -10,7 +10,7 @@ use embedded_gui::{
         background::{Background, BackgroundProperties},
         Widget,
     },
-    WidgetRenderer,
+    Canvas, WidgetRenderer,
 };
 
 use crate::{themes::Theme, EgCanvas, ToRectangle};
@@ -67,8 +67,8 @@ where
         self.bounding_box()
             .to_rectangle()
             .into_styled(style)
-            .draw(&mut canvas.target)?;
+            .draw(canvas)?;
 
-        self.inner.draw(canvas)
+        canvas.draw_clipped(self.bounding_box(), &mut self.inner)
     }
 }
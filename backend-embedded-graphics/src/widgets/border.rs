@@ -6,11 +6,12 @@ use embedded_graphics::{
     Drawable,
 };
 use embedded_gui::{
+    geometry::{BoundingBox, MeasuredSize, PositionDelta},
     widgets::{
         border::{Border, BorderProperties},
         Widget,
     },
-    WidgetRenderer,
+    Canvas, WidgetRenderer,
 };
 
 use crate::{themes::Theme, EgCanvas, ToRectangle};
@@ -77,8 +78,23 @@ where
         self.bounding_box()
             .to_rectangle()
             .into_styled(style)
-            .draw(&mut canvas.target)?;
+            .draw(canvas)?;
 
-        self.inner.draw(canvas)
+        // Don't let the inner widget draw over the border
+        let width = self.border_properties.get_border_width();
+        let bounds = self.bounding_box();
+        let inside = BoundingBox {
+            position: bounds.position
+                + PositionDelta {
+                    x: width as i32,
+                    y: width as i32,
+                },
+            size: MeasuredSize {
+                width: bounds.size.width.saturating_sub(2 * width),
+                height: bounds.size.height.saturating_sub(2 * width),
+            },
+        };
+
+        canvas.draw_clipped(inside, &mut self.inner)
     }
 }
@@ -0,0 +1,99 @@
+use embedded_graphics::{
+    draw_target::DrawTarget,
+    pixelcolor::PixelColor,
+    prelude::{Point, Primitive},
+    primitives::{Line, PrimitiveStyle, PrimitiveStyleBuilder},
+    Drawable,
+};
+use embedded_gui::{
+    data::WidgetData,
+    widgets::{
+        calibration::{CalibrationProperties, CalibrationScreen},
+        Widget,
+    },
+    WidgetRenderer,
+};
+
+use crate::{themes::Theme, EgCanvas, ToPoint, ToRectangle};
+
+pub struct CalibrationStyle<C>
+where
+    C: PixelColor,
+{
+    pub background_color: C,
+    pub target_color: C,
+    /// The length of the lines of the crosshair.
+    pub target_size: u32,
+}
+
+impl<C> CalibrationStyle<C>
+where
+    C: PixelColor,
+{
+    fn build_background_style(&self) -> PrimitiveStyle<C> {
+        PrimitiveStyleBuilder::new()
+            .fill_color(self.background_color)
+            .build()
+    }
+
+    fn build_target_style(&self) -> PrimitiveStyle<C> {
+        PrimitiveStyle::with_stroke(self.target_color, 1)
+    }
+}
+
+impl<C> Default for CalibrationStyle<C>
+where
+    C: Theme,
+{
+    fn default() -> Self {
+        Self {
+            background_color: C::BACKGROUND_COLOR,
+            target_color: C::TEXT_COLOR,
+            target_size: 15,
+        }
+    }
+}
+
+impl<C> CalibrationProperties for CalibrationStyle<C>
+where
+    C: PixelColor,
+{
+    type Color = C;
+
+    fn set_background_color(&mut self, color: Self::Color) {
+        self.background_color = color;
+    }
+
+    fn set_target_color(&mut self, color: Self::Color) {
+        self.target_color = color;
+    }
+}
+
+impl<C, D, DT> WidgetRenderer<EgCanvas<DT>> for CalibrationScreen<CalibrationStyle<C>, D>
+where
+    C: PixelColor,
+    D: WidgetData,
+    DT: DrawTarget<Color = C>,
+{
+    fn draw(&mut self, canvas: &mut EgCanvas<DT>) -> Result<(), DT::Error> {
+        self.bounding_box()
+            .to_rectangle()
+            .into_styled(self.calibration_properties.build_background_style())
+            .draw(canvas)?;
+
+        if let Some(target) = self.target() {
+            let center = target.to_point();
+            let half = self.calibration_properties.target_size as i32 / 2;
+            let style = self.calibration_properties.build_target_style();
+
+            Line::new(center - Point::new(half, 0), center + Point::new(half, 0))
+                .into_styled(style)
+                .draw(canvas)?;
+            Line::new(center - Point::new(0, half), center + Point::new(0, half))
+                .into_styled(style)
+                .draw(canvas)?;
+        }
+
+        Ok(())
+    }
+}
@@ -106,7 +106,7 @@ where
         self.bounding_box()
             .to_rectangle()
             .into_styled(self.checkbox_properties.build_box_style())
-            .draw(&mut canvas.target)?;
+            .draw(canvas)?;
 
         if self.checkbox_properties.is_checked {
             let BoundingBox { position, size } = self.bounding_box();
@@ -124,7 +124,7 @@ where
             check_bounds
                 .to_rectangle()
                 .into_styled(self.checkbox_properties.build_check_style())
-                .draw(&mut canvas.target)?;
+                .draw(canvas)?;
         }
 
         Ok(())
@@ -107,7 +107,7 @@ where
             self.bounding_box().size.width,
         )
         .into_styled(self.radio_properties.build_box_style())
-        .draw(&mut canvas.target)?;
+        .draw(canvas)?;
 
         if self.radio_properties.is_selected {
             let BoundingBox { position, size } = self.bounding_box();
@@ -118,7 +118,7 @@ where
             );
             check_bounds
                 .into_styled(self.radio_properties.build_check_style())
-                .draw(&mut canvas.target)?;
+                .draw(canvas)?;
         }
 
         Ok(())
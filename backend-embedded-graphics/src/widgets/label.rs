@@ -65,6 +65,15 @@ where
             height: metrics.bounding_box.size.height,
         }
     }
+
+    fn baseline(&self, text: &str) -> Option<u32> {
+        let metrics = self
+            .renderer
+            .measure_string(text, Point::zero(), Baseline::Alphabetic);
+
+        // The text extends above the baseline, which is at y = 0
+        Some((-metrics.bounding_box.top_left.y) as u32)
+    }
 }
 
 pub trait LabelStyling<S>: Sized {
@@ -150,7 +159,7 @@ where
                 self.text.as_ref(),
                 Point::new(self.bounds.position.x, self.bounds.position.y),
                 Baseline::Top,
-                &mut canvas.target,
+                canvas,
             )
             .map(|_| ())
     }
@@ -1,7 +1,7 @@
 pub mod background;
 pub mod border;
+pub mod calibration;
 pub mod graphical;
 pub mod label;
-pub mod scroll;
 pub mod text_block;
 pub mod text_box;
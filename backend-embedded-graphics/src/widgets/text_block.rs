@@ -229,7 +229,7 @@ where
                 .build(),
         )
         .add_plugin(Ansi::new())
-        .draw(&mut canvas.target)
+        .draw(canvas)
         .map(|_| ())
     }
 }
@@ -214,6 +214,7 @@ where
         TextBox {
             fields: TextBoxFields {
                 state: self.fields.state,
+                active: self.fields.active,
                 parent_index: self.fields.parent_index,
                 text: self.fields.text,
                 bounds: self.fields.bounds,
@@ -349,7 +350,7 @@ where
                     .plugin(cursor_color.unwrap()),
             );
 
-            let result = textbox.draw(&mut canvas.target).map(|_| ());
+            let result = textbox.draw(canvas).map(|_| ());
 
             let plugins = textbox.take_plugins();
             let (plugin, _plugins) = plugins.pop();
@@ -357,7 +358,7 @@ where
 
             result
         } else {
-            textbox.draw(&mut canvas.target).map(|_| ())
+            textbox.draw(canvas).map(|_| ())
         }
     }
 }
@@ -406,6 +407,7 @@ macro_rules! textbox_for_charset {
                     TextBox {
                         fields: TextBoxFields {
                             state: WidgetState::default(),
+                            active: true,
                             parent_index: 0,
                             text,
                             label_properties: TextBoxStyle {
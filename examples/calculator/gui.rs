@@ -0,0 +1,304 @@
+//! The calculator's state and widget tree, separate from the simulator so that tests can drive it.
+
+use std::fmt::Write;
+
+use backend_embedded_graphics::{
+    themes::default::DefaultTheme,
+    widgets::label::{ascii::LabelConstructor, MonoFontLabelStyling},
+    EgCanvas,
+};
+use embedded_graphics::{
+    draw_target::DrawTarget, mono_font::ascii::FONT_10X20, pixelcolor::BinaryColor,
+};
+use embedded_gui::{
+    data::BoundData,
+    prelude::*,
+    widgets::{
+        fill::{FillParent, Right},
+        label::Label,
+        layouts::linear::{Column, Row},
+        spacing::Spacing,
+    },
+    WidgetRenderer,
+};
+use heapless::String;
+
+#[derive(Copy, Clone)]
+pub enum Op {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+}
+
+impl Op {
+    fn calc(self, current: i32, previous: i32) -> i32 {
+        match self {
+            Op::Add => previous.saturating_add(current),
+            Op::Subtract => previous.saturating_sub(current),
+            Op::Multiply => previous.saturating_mul(current),
+            Op::Divide => {
+                // I'm too lazy to add error handling.
+                let div = if current == 0 { 1 } else { current };
+                previous / div
+            }
+        }
+    }
+
+    fn bind(self, n: i32) -> PrevOp {
+        match self {
+            Op::Add => PrevOp::Add(n),
+            Op::Subtract => PrevOp::Subtract(n),
+            Op::Multiply => PrevOp::Multiply(n),
+            Op::Divide => PrevOp::Divide(n),
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+pub enum PrevOp {
+    Add(i32),
+    Subtract(i32),
+    Multiply(i32),
+    Divide(i32),
+}
+
+impl PrevOp {
+    fn apply(self, n: i32) -> i32 {
+        match self {
+            PrevOp::Add(prev) => Op::Add.calc(prev, n),
+            PrevOp::Subtract(prev) => Op::Subtract.calc(prev, n),
+            PrevOp::Multiply(prev) => Op::Multiply.calc(prev, n),
+            PrevOp::Divide(prev) => Op::Divide.calc(prev, n),
+        }
+    }
+}
+
+pub struct Calculator {
+    pub previous: i32,
+    pub current: i32,
+    current_op: Option<Op>,
+    prev_op: Option<PrevOp>,
+    next_digit_clears: bool,
+}
+
+impl Default for Calculator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Calculator {
+    pub fn new() -> Self {
+        Calculator {
+            previous: 0,
+            current: 0,
+            current_op: None,
+            prev_op: None,
+            next_digit_clears: false,
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.previous = 0;
+        self.current = 0;
+        self.current_op = None;
+        self.prev_op = None;
+        self.next_digit_clears = false;
+    }
+
+    pub fn add_digit(&mut self, d: i32) {
+        if self.next_digit_clears {
+            self.next_digit_clears = false;
+            self.current = 0;
+        }
+        if let Some(c) = self.current.checked_mul(10).and_then(|c| c.checked_add(d)) {
+            self.current = c;
+        }
+    }
+
+    pub fn delete_digit(&mut self) {
+        self.current /= 10;
+    }
+
+    pub fn set_op(&mut self, op: Op) {
+        if self.current_op.is_some() {
+            self.calc();
+        }
+        self.previous = self.current;
+        self.current_op = Some(op);
+        self.next_digit_clears = true;
+    }
+
+    pub fn update(&mut self) {
+        if self.current_op.is_some() {
+            self.calc();
+        } else if let Some(prev_op) = self.prev_op {
+            self.current = prev_op.apply(self.current);
+        }
+        self.current_op = None;
+        self.next_digit_clears = true;
+    }
+
+    fn calc(&mut self) {
+        if let Some(op) = self.current_op {
+            self.prev_op = Some(op.bind(self.current));
+            let prev = std::mem::replace(&mut self.previous, self.current);
+            self.current = op.calc(self.current, prev);
+        }
+    }
+}
+
+/// Builds the widget tree of a calculator operating on `calculator`.
+pub fn widget_tree<D, F>(
+    calculator: &BoundData<Calculator, F>,
+) -> impl Widget + WidgetRenderer<EgCanvas<D>> + '_
+where
+    D: DrawTarget<Color = BinaryColor>,
+    F: FnMut(&Calculator),
+{
+    Column::new()
+        .spacing(1)
+        .add(
+            Spacing::new(
+                FillParent::horizontal(
+                    Label::new(String::<11>::new())
+                        .font(&FONT_10X20)
+                        .bind(calculator)
+                        .on_data_changed(|label, calc| {
+                            label.text.clear();
+                            write!(label.text, "{}", calc.current).unwrap();
+                        }),
+                )
+                .align_horizontal(Right),
+            )
+            .all(4),
+        )
+        .add(
+            Row::new()
+                .spacing(1)
+                .add(
+                    DefaultTheme::primary_button_stretched("CE")
+                        .bind(calculator)
+                        .on_clicked(|calculator| calculator.clear()),
+                )
+                .weight(2)
+                .add(
+                    DefaultTheme::secondary_button_stretched("<")
+                        .bind(calculator)
+                        .on_clicked(|calculator| calculator.delete_digit()),
+                )
+                .weight(1)
+                .add(
+                    DefaultTheme::primary_button_stretched("/")
+                        .bind(calculator)
+                        .on_clicked(|calculator| calculator.set_op(Op::Divide)),
+                )
+                .weight(1),
+        )
+        .weight(1)
+        .add(
+            Row::new()
+                .spacing(1)
+                .add(
+                    DefaultTheme::secondary_button_stretched("7")
+                        .bind(calculator)
+                        .on_clicked(|calculator| calculator.add_digit(7)),
+                )
+                .weight(1)
+                .add(
+                    DefaultTheme::secondary_button_stretched("8")
+                        .bind(calculator)
+                        .on_clicked(|calculator| calculator.add_digit(8)),
+                )
+                .weight(1)
+                .add(
+                    DefaultTheme::secondary_button_stretched("9")
+                        .bind(calculator)
+                        .on_clicked(|calculator| calculator.add_digit(9)),
+                )
+                .weight(1)
+                .add(
+                    DefaultTheme::primary_button_stretched("x")
+                        .bind(calculator)
+                        .on_clicked(|calculator| calculator.set_op(Op::Multiply)),
+                )
+                .weight(1),
+        )
+        .weight(1)
+        .add(
+            Row::new()
+                .spacing(1)
+                .add(
+                    DefaultTheme::secondary_button_stretched("4")
+                        .bind(calculator)
+                        .on_clicked(|calculator| calculator.add_digit(4)),
+                )
+                .weight(1)
+                .add(
+                    DefaultTheme::secondary_button_stretched("5")
+                        .bind(calculator)
+                        .on_clicked(|calculator| calculator.add_digit(5)),
+                )
+                .weight(1)
+                .add(
+                    DefaultTheme::secondary_button_stretched("6")
+                        .bind(calculator)
+                        .on_clicked(|calculator| calculator.add_digit(6)),
+                )
+                .weight(1)
+                .add(
+                    DefaultTheme::primary_button_stretched("-")
+                        .bind(calculator)
+                        .on_clicked(|calculator| calculator.set_op(Op::Subtract)),
+                )
+                .weight(1),
+        )
+        .weight(1)
+        .add(
+            Row::new()
+                .spacing(1)
+                .add(
+                    DefaultTheme::secondary_button_stretched("1")
+                        .bind(calculator)
+                        .on_clicked(|calculator| calculator.add_digit(1)),
+                )
+                .weight(1)
+                .add(
+                    DefaultTheme::secondary_button_stretched("2")
+                        .bind(calculator)
+                        .on_clicked(|calculator| calculator.add_digit(2)),
+                )
+                .weight(1)
+                .add(
+                    DefaultTheme::secondary_button_stretched("3")
+                        .bind(calculator)
+                        .on_clicked(|calculator| calculator.add_digit(3)),
+                )
+                .weight(1)
+                .add(
+                    DefaultTheme::primary_button_stretched("+")
+                        .bind(calculator)
+                        .on_clicked(|calculator| calculator.set_op(Op::Add)),
+                )
+                .weight(1),
+        )
+        .weight(1)
+        .add(
+            Row::new()
+                .spacing(1)
+                .add(
+                    DefaultTheme::secondary_button_stretched("0")
+                        .bind(calculator)
+                        .on_clicked(|calculator| calculator.add_digit(0)),
+                )
+                .weight(3)
+                .add(
+                    DefaultTheme::primary_button_stretched("=")
+                        .bind(calculator)
+                        .on_clicked(|calculator| calculator.update()),
+                )
+                .weight(1),
+        )
+        .weight(1)
+}
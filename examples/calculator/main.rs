@@ -0,0 +1,32 @@
+use backend_embedded_graphics::EgCanvas;
+use embedded_graphics::{pixelcolor::BinaryColor, prelude::Size as EgSize};
+use embedded_graphics_simulator::{BinaryColorTheme, OutputSettingsBuilder, SimulatorDisplay};
+use embedded_gui::{data::BoundData, Window};
+use embedded_gui_simulator::Simulator;
+
+use crate::gui::{widget_tree, Calculator};
+
+mod gui;
+
+fn main() {
+    let display = SimulatorDisplay::<BinaryColor>::new(EgSize::new(96, 96));
+
+    let calculator = BoundData::new(Calculator::new(), |_data| {});
+
+    let mut gui = Window::new(EgCanvas::new(display), widget_tree(&calculator));
+
+    fn print_type_of<T>(_: &T) {
+        println!("Type of tree: {}", std::any::type_name::<T>());
+        println!("Length of type: {}", std::any::type_name::<T>().len());
+        println!("Size of struct: {}", std::mem::size_of::<T>());
+    }
+
+    print_type_of(&gui.root);
+
+    let output_settings = OutputSettingsBuilder::new()
+        .theme(BinaryColorTheme::OledBlue)
+        .build();
+    Simulator::new("GUI demonstration")
+        .output_settings(output_settings)
+        .run(&mut gui);
+}
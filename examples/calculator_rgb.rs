@@ -1,23 +1,18 @@
-use std::{fmt::Write, thread, time::Duration};
+use std::fmt::Write;
 
 use backend_embedded_graphics::{
-    themes::{default::DefaultTheme, Theme},
+    themes::default::DefaultTheme,
     widgets::label::{ascii::LabelConstructor, MonoFontLabelStyling},
     EgCanvas,
 };
 use embedded_graphics::{
-    draw_target::DrawTarget,
     mono_font::ascii::FONT_10X20,
     pixelcolor::{Rgb888, WebColors},
     prelude::Size as EgSize,
 };
-use embedded_graphics_simulator::{
-    sdl2::MouseButton, OutputSettingsBuilder, SimulatorDisplay, SimulatorEvent, Window as SimWindow,
-};
+use embedded_graphics_simulator::{OutputSettingsBuilder, SimulatorDisplay};
 use embedded_gui::{
     data::BoundData,
-    geometry::Position,
-    input::event::{InputEvent, PointerEvent},
     prelude::*,
     widgets::{
         background::Background,
@@ -27,56 +22,9 @@ use embedded_gui::{
         spacing::Spacing,
     },
 };
+use embedded_gui_simulator::Simulator;
 use heapless::String;
 
-fn convert_input(event: SimulatorEvent) -> Result<InputEvent, bool> {
-    unsafe {
-        // This is fine for a demo
-        static mut MOUSE_DOWN: bool = false;
-        match event {
-            SimulatorEvent::MouseButtonUp {
-                mouse_btn: MouseButton::Left,
-                point,
-            } => {
-                MOUSE_DOWN = false;
-                Ok(InputEvent::PointerEvent(
-                    Position {
-                        x: point.x,
-                        y: point.y,
-                    },
-                    PointerEvent::Up,
-                ))
-            }
-            SimulatorEvent::MouseButtonDown {
-                mouse_btn: MouseButton::Left,
-                point,
-            } => {
-                MOUSE_DOWN = true;
-                Ok(InputEvent::PointerEvent(
-                    Position {
-                        x: point.x,
-                        y: point.y,
-                    },
-                    PointerEvent::Down,
-                ))
-            }
-            SimulatorEvent::MouseMove { point } => Ok(InputEvent::PointerEvent(
-                Position {
-                    x: point.x,
-                    y: point.y,
-                },
-                if MOUSE_DOWN {
-                    PointerEvent::Drag
-                } else {
-                    PointerEvent::Hover
-                },
-            )),
-            SimulatorEvent::Quit => Err(true),
-            _ => Err(false),
-        }
-    }
-}
-
 #[derive(Copy, Clone, Debug)]
 pub enum Op {
     Add,
@@ -219,7 +167,7 @@ impl Calculator {
 }
 
 fn main() {
-    let display = SimulatorDisplay::new(EgSize::new(128, 160));
+    let display = SimulatorDisplay::<Rgb888>::new(EgSize::new(128, 160));
 
     let calculator = BoundData::new(Calculator::new(), |_data| {});
 
@@ -386,31 +334,7 @@ fn main() {
     print_type_of(&gui.root);
 
     let output_settings = OutputSettingsBuilder::new().scale(2).build();
-    let mut window = SimWindow::new("GUI demonstration", &output_settings);
-
-    loop {
-        gui.canvas.target.clear(Rgb888::BACKGROUND_COLOR).unwrap();
-
-        gui.update();
-        gui.measure();
-        gui.arrange();
-        gui.draw().unwrap();
-
-        // Update the window.
-        window.update(&gui.canvas.target);
-
-        // Handle key and mouse events.
-        for event in window.events() {
-            match convert_input(event) {
-                Ok(input) => {
-                    gui.input_event(input);
-                }
-                Err(true) => return,
-                _ => {}
-            }
-        }
-
-        // Wait for a little while.
-        thread::sleep(Duration::from_millis(10));
-    }
+    Simulator::new("GUI demonstration")
+        .output_settings(output_settings)
+        .run(&mut gui);
 }
@@ -1,21 +1,11 @@
-use std::{thread, time::Duration};
-
 use backend_embedded_graphics::{
-    themes::Theme,
     widgets::{background::BackgroundStyle, border::BorderStyle, label::ascii::LabelConstructor},
     EgCanvas,
 };
-use embedded_graphics::{
-    draw_target::DrawTarget, pixelcolor::BinaryColor, prelude::Size as EgSize,
-};
-use embedded_graphics_simulator::{
-    sdl2::MouseButton, BinaryColorTheme, OutputSettingsBuilder, SimulatorDisplay, SimulatorEvent,
-    Window as SimWindow,
-};
+use embedded_graphics::{pixelcolor::BinaryColor, prelude::Size as EgSize};
+use embedded_graphics_simulator::{BinaryColorTheme, OutputSettingsBuilder, SimulatorDisplay};
 use embedded_gui::{
     data::BoundData,
-    geometry::Position,
-    input::event::{InputEvent, PointerEvent},
     prelude::*,
     state::WidgetState,
     widgets::{
@@ -28,54 +18,7 @@ use embedded_gui::{
         spacing::Spacing,
     },
 };
-
-fn convert_input(event: SimulatorEvent) -> Result<InputEvent, bool> {
-    unsafe {
-        // This is fine for a demo
-        static mut MOUSE_DOWN: bool = false;
-        match event {
-            SimulatorEvent::MouseButtonUp {
-                mouse_btn: MouseButton::Left,
-                point,
-            } => {
-                MOUSE_DOWN = false;
-                Ok(InputEvent::PointerEvent(
-                    Position {
-                        x: point.x,
-                        y: point.y,
-                    },
-                    PointerEvent::Up,
-                ))
-            }
-            SimulatorEvent::MouseButtonDown {
-                mouse_btn: MouseButton::Left,
-                point,
-            } => {
-                MOUSE_DOWN = true;
-                Ok(InputEvent::PointerEvent(
-                    Position {
-                        x: point.x,
-                        y: point.y,
-                    },
-                    PointerEvent::Down,
-                ))
-            }
-            SimulatorEvent::MouseMove { point } => Ok(InputEvent::PointerEvent(
-                Position {
-                    x: point.x,
-                    y: point.y,
-                },
-                if MOUSE_DOWN {
-                    PointerEvent::Drag
-                } else {
-                    PointerEvent::Hover
-                },
-            )),
-            SimulatorEvent::Quit => Err(true),
-            _ => Err(false),
-        }
-    }
-}
+use embedded_gui_simulator::Simulator;
 
 fn update_button_background<W: Widget>(
     widget: &mut Background<W, BackgroundStyle<BinaryColor>>,
@@ -129,7 +72,7 @@ fn button_with_style<W: Widget>(
 }
 
 fn main() {
-    let display = SimulatorDisplay::new(EgSize::new(128, 64));
+    let display = SimulatorDisplay::<BinaryColor>::new(EgSize::new(128, 64));
 
     let flag = BoundData::new(true, |data| println!("Data changed to {:?}", data));
 
@@ -161,34 +104,7 @@ fn main() {
     let output_settings = OutputSettingsBuilder::new()
         .theme(BinaryColorTheme::OledBlue)
         .build();
-    let mut window = SimWindow::new("GUI demonstration", &output_settings);
-
-    loop {
-        gui.canvas
-            .target
-            .clear(BinaryColor::BACKGROUND_COLOR)
-            .unwrap();
-
-        gui.update();
-        gui.measure();
-        gui.arrange();
-        gui.draw().unwrap();
-
-        // Update the window.
-        window.update(&gui.canvas.target);
-
-        // Handle key and mouse events.
-        for event in window.events() {
-            match convert_input(event) {
-                Ok(input) => {
-                    gui.input_event(input);
-                }
-                Err(true) => return,
-                _ => {}
-            }
-        }
-
-        // Wait for a little while.
-        thread::sleep(Duration::from_millis(10));
-    }
+    Simulator::new("GUI demonstration")
+        .output_settings(output_settings)
+        .run(&mut gui);
 }
@@ -1,7 +1,7 @@
-use std::{fmt::Write, thread, time::Duration};
+use std::fmt::Write;
 
 use backend_embedded_graphics::{
-    themes::{default::DefaultTheme, Theme},
+    themes::default::DefaultTheme,
     widgets::{
         label::ascii::LabelConstructor,
         text_block::{
@@ -12,26 +12,19 @@ use backend_embedded_graphics::{
     EgCanvas,
 };
 use embedded_graphics::{
-    draw_target::DrawTarget,
     pixelcolor::Rgb888,
     prelude::{Size as EgSize, WebColors},
 };
-use embedded_graphics_simulator::{
-    sdl2::{Keycode, Mod, MouseButton},
-    OutputSettingsBuilder, SimulatorDisplay, SimulatorEvent, Window as SimWindow,
-};
+use embedded_graphics_simulator::{OutputSettingsBuilder, SimulatorDisplay};
 use embedded_gui::{
     data::BoundData,
-    geometry::Position,
-    input::event::{InputEvent, Key, KeyEvent, Modifier, PointerEvent, ScrollEvent},
     prelude::*,
     widgets::{
         border::Border,
-        fill::FillParent,
         label::Label,
         layouts::{
             frame::Frame,
-            linear::{Column, Row},
+            linear::{Column, CrossAxisAlignment, Row},
         },
         scroll::Scroll,
         slider::ScrollbarConnector,
@@ -41,159 +34,9 @@ use embedded_gui::{
         visibility::Visibility,
     },
 };
+use embedded_gui_simulator::Simulator;
 use heapless::String;
 
-trait Convert {
-    type Output;
-
-    fn convert(self) -> Self::Output;
-}
-
-impl Convert for Keycode {
-    type Output = Option<Key>;
-
-    fn convert(self) -> Self::Output {
-        match self {
-            Keycode::Backspace => Some(Key::Backspace),
-            Keycode::Tab => Some(Key::Tab),
-            Keycode::Return => Some(Key::Enter),
-            Keycode::Space => Some(Key::Space),
-            Keycode::KpComma | Keycode::Comma => Some(Key::Comma),
-            Keycode::KpMinus | Keycode::Minus => Some(Key::Minus),
-            Keycode::KpPeriod | Keycode::Period => Some(Key::Period),
-            Keycode::Kp1 | Keycode::Num0 => Some(Key::N0),
-            Keycode::Kp2 | Keycode::Num1 => Some(Key::N1),
-            Keycode::Kp3 | Keycode::Num2 => Some(Key::N2),
-            Keycode::Kp4 | Keycode::Num3 => Some(Key::N3),
-            Keycode::Kp5 | Keycode::Num4 => Some(Key::N4),
-            Keycode::Kp6 | Keycode::Num5 => Some(Key::N5),
-            Keycode::Kp7 | Keycode::Num6 => Some(Key::N6),
-            Keycode::Kp8 | Keycode::Num7 => Some(Key::N7),
-            Keycode::Kp9 | Keycode::Num8 => Some(Key::N8),
-            Keycode::Kp0 | Keycode::Num9 => Some(Key::N9),
-            Keycode::A => Some(Key::A),
-            Keycode::B => Some(Key::B),
-            Keycode::C => Some(Key::C),
-            Keycode::D => Some(Key::D),
-            Keycode::E => Some(Key::E),
-            Keycode::F => Some(Key::F),
-            Keycode::G => Some(Key::G),
-            Keycode::H => Some(Key::H),
-            Keycode::I => Some(Key::I),
-            Keycode::J => Some(Key::J),
-            Keycode::K => Some(Key::K),
-            Keycode::L => Some(Key::L),
-            Keycode::M => Some(Key::M),
-            Keycode::N => Some(Key::N),
-            Keycode::O => Some(Key::O),
-            Keycode::P => Some(Key::P),
-            Keycode::Q => Some(Key::Q),
-            Keycode::R => Some(Key::R),
-            Keycode::S => Some(Key::S),
-            Keycode::T => Some(Key::T),
-            Keycode::U => Some(Key::U),
-            Keycode::V => Some(Key::V),
-            Keycode::W => Some(Key::W),
-            Keycode::X => Some(Key::X),
-            Keycode::Y => Some(Key::Y),
-            Keycode::Z => Some(Key::Z),
-            Keycode::Delete => Some(Key::Del),
-            Keycode::Right => Some(Key::ArrowRight),
-            Keycode::Left => Some(Key::ArrowLeft),
-            Keycode::Down => Some(Key::ArrowDown),
-            Keycode::Up => Some(Key::ArrowUp),
-            _ => None,
-        }
-    }
-}
-
-impl Convert for Mod {
-    type Output = Modifier;
-
-    fn convert(self) -> Self::Output {
-        if self.contains(Mod::RALTMOD) {
-            Modifier::Alt
-        } else if self.intersects(Mod::LSHIFTMOD | Mod::RSHIFTMOD) {
-            Modifier::Shift
-        } else if self.contains(Mod::CAPSMOD) {
-            Modifier::Shift
-        } else {
-            Modifier::None
-        }
-    }
-}
-
-fn convert_input(event: SimulatorEvent) -> Result<InputEvent, bool> {
-    unsafe {
-        // This is fine for a demo
-        static mut MOUSE_DOWN: bool = false;
-        match event {
-            SimulatorEvent::MouseButtonUp {
-                mouse_btn: MouseButton::Left,
-                point,
-            } => {
-                MOUSE_DOWN = false;
-                Ok(InputEvent::PointerEvent(
-                    Position {
-                        x: point.x,
-                        y: point.y,
-                    },
-                    PointerEvent::Up,
-                ))
-            }
-            SimulatorEvent::MouseButtonDown {
-                mouse_btn: MouseButton::Left,
-                point,
-            } => {
-                MOUSE_DOWN = true;
-                Ok(InputEvent::PointerEvent(
-                    Position {
-                        x: point.x,
-                        y: point.y,
-                    },
-                    PointerEvent::Down,
-                ))
-            }
-            SimulatorEvent::MouseMove { point } => Ok(InputEvent::PointerEvent(
-                Position {
-                    x: point.x,
-                    y: point.y,
-                },
-                if MOUSE_DOWN {
-                    PointerEvent::Drag
-                } else {
-                    PointerEvent::Hover
-                },
-            )),
-            SimulatorEvent::MouseWheel { scroll_delta, .. } => {
-                // TODO: We could break this down into multiple scroll events
-                Ok(InputEvent::ScrollEvent(if scroll_delta.y != 0 {
-                    ScrollEvent::VerticalScroll(scroll_delta.y * 4)
-                } else {
-                    ScrollEvent::HorizontalScroll(scroll_delta.x * 4)
-                }))
-            }
-            SimulatorEvent::KeyDown {
-                keycode, keymod, ..
-            } => Ok(InputEvent::KeyEvent(KeyEvent::KeyDown(
-                keycode.convert().ok_or(false)?,
-                keymod.convert(),
-                0,
-            ))),
-
-            SimulatorEvent::KeyUp {
-                keycode, keymod, ..
-            } => Ok(InputEvent::KeyEvent(KeyEvent::KeyUp(
-                keycode.convert().ok_or(false)?,
-                keymod.convert(),
-            ))),
-
-            SimulatorEvent::Quit => Err(true),
-            _ => Err(false),
-        }
-    }
-}
-
 #[derive(PartialEq)]
 enum Page {
     TextBlock,
@@ -203,7 +46,7 @@ enum Page {
 }
 
 fn main() {
-    let display = SimulatorDisplay::new(EgSize::new(300, 180));
+    let display = SimulatorDisplay::<Rgb888>::new(EgSize::new(300, 180));
 
     let page = BoundData::new(Page::TextBlock, |_| ());
 
@@ -365,14 +208,14 @@ fn main() {
         .add(Label::new("Numeric sliders"))
         .add(
             Row::new()
-                .add(FillParent::horizontal(
+                .add(
                     Label::new(String::<11>::from("0"))
                         .bind(&slider1_data)
                         .on_data_changed(|label, data| {
                             label.text.clear();
                             write!(label.text, "{}", data).unwrap();
                         }),
-                ))
+                )
                 .weight(1)
                 .add(
                     Spacing::new(
@@ -387,14 +230,14 @@ fn main() {
         )
         .add(
             Row::new()
-                .add(FillParent::horizontal(
+                .add(
                     Label::new(String::<11>::from("0"))
                         .bind(&slider2_data)
                         .on_data_changed(|label, data| {
                             label.text.clear();
                             write!(label.text, "{}", data).unwrap();
                         }),
-                ))
+                )
                 .weight(1)
                 .add(
                     Spacing::new(
@@ -429,7 +272,7 @@ fn main() {
         );
 
     let scrolling_page = Column::new()
-        .add(FillParent::horizontal(
+        .add(
             Label::new("Scroll down")
                 .bind(&scroll_data)
                 .on_data_changed(|label, data| {
@@ -441,7 +284,8 @@ fn main() {
                         "Scroll more"
                     };
                 }),
-        ))
+        )
+        .align_cell(CrossAxisAlignment::Center)
         .add(
             Row::new()
                 .add(Border::new(
@@ -553,31 +397,7 @@ fn main() {
     print_type_of(&gui.root);
 
     let output_settings = OutputSettingsBuilder::new().scale(2).build();
-    let mut window = SimWindow::new("Everything but the kitchen sink", &output_settings);
-
-    loop {
-        gui.canvas.target.clear(Rgb888::BACKGROUND_COLOR).unwrap();
-
-        gui.update();
-        gui.measure();
-        gui.arrange();
-        gui.draw().unwrap();
-
-        // Update the window.
-        window.update(&gui.canvas.target);
-
-        // Handle key and mouse events.
-        for event in window.events() {
-            match convert_input(event) {
-                Ok(input) => {
-                    gui.input_event(input);
-                }
-                Err(true) => return,
-                _ => {}
-            }
-        }
-
-        // Wait for a little while.
-        thread::sleep(Duration::from_millis(10));
-    }
+    Simulator::new("Everything but the kitchen sink")
+        .output_settings(output_settings)
+        .run(&mut gui);
 }
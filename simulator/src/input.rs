@@ -0,0 +1,310 @@
+//! Converting simulator events to `embedded-gui` input events.
+
+use embedded_graphics::geometry::Point;
+use embedded_graphics_simulator::{
+    sdl2::{Keycode, Mod, MouseButton, MouseWheelDirection},
+    SimulatorEvent,
+};
+use embedded_gui::{
+    geometry::Position,
+    input::event::{InputEvent, Key, KeyEvent, Modifier, PointerEvent, ScrollEvent},
+};
+
+/// Returns the key that corresponds to `keycode`.
+///
+/// Keypad keys act as digits when `num_lock` is on, and as navigation keys otherwise.
+pub fn convert_key(keycode: Keycode, num_lock: bool) -> Option<Key> {
+    let key = match keycode {
+        Keycode::Kp0 if num_lock => Key::N0,
+        Keycode::Kp1 if num_lock => Key::N1,
+        Keycode::Kp2 if num_lock => Key::N2,
+        Keycode::Kp3 if num_lock => Key::N3,
+        Keycode::Kp4 if num_lock => Key::N4,
+        Keycode::Kp5 if num_lock => Key::N5,
+        Keycode::Kp6 if num_lock => Key::N6,
+        Keycode::Kp7 if num_lock => Key::N7,
+        Keycode::Kp8 if num_lock => Key::N8,
+        Keycode::Kp9 if num_lock => Key::N9,
+        Keycode::KpPeriod if num_lock => Key::Period,
+        Keycode::Kp2 => Key::ArrowDown,
+        Keycode::Kp4 => Key::ArrowLeft,
+        Keycode::Kp6 => Key::ArrowRight,
+        Keycode::Kp8 => Key::ArrowUp,
+        Keycode::KpPeriod => Key::Del,
+
+        Keycode::Backspace => Key::Backspace,
+        Keycode::Tab => Key::Tab,
+        Keycode::Return | Keycode::KpEnter => Key::Enter,
+        Keycode::Space => Key::Space,
+        Keycode::Comma | Keycode::KpComma => Key::Comma,
+        Keycode::Minus | Keycode::KpMinus => Key::Minus,
+        Keycode::Period => Key::Period,
+        Keycode::Delete => Key::Del,
+        Keycode::Right => Key::ArrowRight,
+        Keycode::Left => Key::ArrowLeft,
+        Keycode::Down => Key::ArrowDown,
+        Keycode::Up => Key::ArrowUp,
+
+        Keycode::Num0 => Key::N0,
+        Keycode::Num1 => Key::N1,
+        Keycode::Num2 => Key::N2,
+        Keycode::Num3 => Key::N3,
+        Keycode::Num4 => Key::N4,
+        Keycode::Num5 => Key::N5,
+        Keycode::Num6 => Key::N6,
+        Keycode::Num7 => Key::N7,
+        Keycode::Num8 => Key::N8,
+        Keycode::Num9 => Key::N9,
+
+        Keycode::A => Key::A,
+        Keycode::B => Key::B,
+        Keycode::C => Key::C,
+        Keycode::D => Key::D,
+        Keycode::E => Key::E,
+        Keycode::F => Key::F,
+        Keycode::G => Key::G,
+        Keycode::H => Key::H,
+        Keycode::I => Key::I,
+        Keycode::J => Key::J,
+        Keycode::K => Key::K,
+        Keycode::L => Key::L,
+        Keycode::M => Key::M,
+        Keycode::N => Key::N,
+        Keycode::O => Key::O,
+        Keycode::P => Key::P,
+        Keycode::Q => Key::Q,
+        Keycode::R => Key::R,
+        Keycode::S => Key::S,
+        Keycode::T => Key::T,
+        Keycode::U => Key::U,
+        Keycode::V => Key::V,
+        Keycode::W => Key::W,
+        Keycode::X => Key::X,
+        Keycode::Y => Key::Y,
+        Keycode::Z => Key::Z,
+
+        _ => return None,
+    };
+
+    Some(key)
+}
+
+/// Returns the modifier `key` is pressed with.
+///
+/// Caps lock only affects letters. Keypad keys are never shifted, so that shift doesn't turn keypad
+/// digits into symbols.
+pub fn convert_modifier(keymod: Mod, keycode: Keycode, key: Key) -> Modifier {
+    let ctrl = keymod.intersects(Mod::LCTRLMOD | Mod::RCTRLMOD);
+    let alt = keymod.intersects(Mod::LALTMOD | Mod::RALTMOD);
+    let shift = if is_keypad(keycode) {
+        false
+    } else if is_letter(key) {
+        keymod.intersects(Mod::LSHIFTMOD | Mod::RSHIFTMOD) != keymod.contains(Mod::CAPSMOD)
+    } else {
+        keymod.intersects(Mod::LSHIFTMOD | Mod::RSHIFTMOD)
+    };
+
+    match (ctrl, alt, shift) {
+        (false, false, false) => Modifier::None,
+        (false, false, true) => Modifier::Shift,
+        (false, true, false) => Modifier::Alt,
+        (false, true, true) => Modifier::AltShift,
+        (true, false, false) => Modifier::Ctrl,
+        (true, true, _) => Modifier::CtrlAlt,
+        (true, false, true) => Modifier::CtrlShift,
+    }
+}
+
+fn is_keypad(keycode: Keycode) -> bool {
+    matches!(
+        keycode,
+        Keycode::Kp0
+            | Keycode::Kp1
+            | Keycode::Kp2
+            | Keycode::Kp3
+            | Keycode::Kp4
+            | Keycode::Kp5
+            | Keycode::Kp6
+            | Keycode::Kp7
+            | Keycode::Kp8
+            | Keycode::Kp9
+            | Keycode::KpPeriod
+            | Keycode::KpComma
+            | Keycode::KpMinus
+            | Keycode::KpEnter
+    )
+}
+
+fn is_letter(key: Key) -> bool {
+    // Letters are the first variants of `Key`
+    (key as u8) <= (Key::Z as u8)
+}
+
+fn position(point: Point) -> Position {
+    Position {
+        x: point.x,
+        y: point.y,
+    }
+}
+
+/// Converts simulator events to input events.
+///
+/// The left mouse button acts as the pointer. Mouse movement is reported as `Drag` while the button
+/// is held, and as `Hover` otherwise.
+pub struct EventConverter {
+    pointer_down: bool,
+    scroll_step: i32,
+    repetitions: u32,
+}
+
+impl EventConverter {
+    pub fn new() -> Self {
+        Self {
+            pointer_down: false,
+            scroll_step: 4,
+            repetitions: 0,
+        }
+    }
+
+    /// Sets the distance, in pixels, a single step of the mouse wheel scrolls.
+    pub fn scroll_step(mut self, pixels: i32) -> Self {
+        self.scroll_step = pixels;
+        self
+    }
+
+    /// Converts `event`, and calls `handler` with the resulting input events.
+    pub fn convert(&mut self, event: SimulatorEvent, mut handler: impl FnMut(InputEvent)) {
+        match event {
+            SimulatorEvent::MouseButtonDown {
+                mouse_btn: MouseButton::Left,
+                point,
+            } => {
+                self.pointer_down = true;
+                handler(InputEvent::PointerEvent(
+                    position(point),
+                    PointerEvent::Down,
+                ));
+            }
+
+            SimulatorEvent::MouseButtonUp {
+                mouse_btn: MouseButton::Left,
+                point,
+            } => {
+                self.pointer_down = false;
+                handler(InputEvent::PointerEvent(position(point), PointerEvent::Up));
+            }
+
+            SimulatorEvent::MouseMove { point } => {
+                let event = if self.pointer_down {
+                    PointerEvent::Drag
+                } else {
+                    PointerEvent::Hover
+                };
+                handler(InputEvent::PointerEvent(position(point), event));
+            }
+
+            SimulatorEvent::MouseWheel {
+                scroll_delta,
+                direction,
+            } => {
+                let step = match direction {
+                    MouseWheelDirection::Flipped => -self.scroll_step,
+                    _ => self.scroll_step,
+                };
+
+                if scroll_delta.x != 0 {
+                    handler(InputEvent::ScrollEvent(ScrollEvent::HorizontalScroll(
+                        scroll_delta.x * step,
+                    )));
+                }
+                if scroll_delta.y != 0 {
+                    handler(InputEvent::ScrollEvent(ScrollEvent::VerticalScroll(
+                        scroll_delta.y * step,
+                    )));
+                }
+            }
+
+            SimulatorEvent::KeyDown {
+                keycode,
+                keymod,
+                repeat,
+            } => {
+                self.repetitions = if repeat { self.repetitions + 1 } else { 0 };
+
+                if let Some(key) = convert_key(keycode, keymod.contains(Mod::NUMMOD)) {
+                    let modifier = convert_modifier(keymod, keycode, key);
+                    handler(InputEvent::KeyEvent(KeyEvent::KeyDown(
+                        key,
+                        modifier,
+                        self.repetitions,
+                    )));
+                }
+            }
+
+            SimulatorEvent::KeyUp {
+                keycode, keymod, ..
+            } => {
+                if let Some(key) = convert_key(keycode, keymod.contains(Mod::NUMMOD)) {
+                    let modifier = convert_modifier(keymod, keycode, key);
+                    handler(InputEvent::KeyEvent(KeyEvent::KeyUp(key, modifier)));
+                }
+            }
+
+            _ => {}
+        }
+    }
+}
+
+impl Default for EventConverter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use embedded_graphics_simulator::sdl2::{Keycode, Mod};
+    use embedded_gui::input::event::{Key, Modifier};
+
+    use crate::input::{convert_key, convert_modifier};
+
+    #[test]
+    fn keypad_digits() {
+        assert!(matches!(convert_key(Keycode::Kp1, true), Some(Key::N1)));
+        assert!(matches!(convert_key(Keycode::Kp0, true), Some(Key::N0)));
+        assert!(matches!(
+            convert_key(Keycode::Kp8, false),
+            Some(Key::ArrowUp)
+        ));
+
+        let shift = Mod::LSHIFTMOD | Mod::NUMMOD;
+        assert!(matches!(
+            convert_modifier(shift, Keycode::Kp5, Key::N5),
+            Modifier::None
+        ));
+        assert!(matches!(
+            convert_modifier(shift, Keycode::Num5, Key::N5),
+            Modifier::Shift
+        ));
+    }
+
+    #[test]
+    fn modifiers() {
+        assert!(matches!(
+            convert_modifier(Mod::CAPSMOD, Keycode::A, Key::A),
+            Modifier::Shift
+        ));
+        assert!(matches!(
+            convert_modifier(Mod::CAPSMOD | Mod::RSHIFTMOD, Keycode::A, Key::A),
+            Modifier::None
+        ));
+        assert!(matches!(
+            convert_modifier(Mod::CAPSMOD, Keycode::Num1, Key::N1),
+            Modifier::None
+        ));
+        assert!(matches!(
+            convert_modifier(Mod::LCTRLMOD | Mod::LSHIFTMOD, Keycode::Z, Key::Z),
+            Modifier::CtrlShift
+        ));
+    }
+}
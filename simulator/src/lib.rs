@@ -0,0 +1,199 @@
+//! embedded-gui-simulator
+//! ======================
+//!
+//! Runs `embedded-gui` windows in the [`embedded-graphics-simulator`], for examples and desktop
+//! prototyping.
+//!
+//! ```rust,ignore
+//! let mut gui = Window::new(EgCanvas::new(SimulatorDisplay::new(Size::new(128, 64))), root);
+//!
+//! Simulator::new("GUI demonstration")
+//!     .output_settings(OutputSettingsBuilder::new().scale(2).build())
+//!     .run(&mut gui);
+//! ```
+//!
+//! [`Simulator::run`] clears the display, updates, lays out and draws the window, then forwards
+//! mouse and keyboard events to it, until the simulator window is closed. Frames are paced to the
+//! rate set by [`Simulator::frame_time`].
+//!
+//! Input
+//! -----
+//!
+//! The left mouse button acts as the pointer, the mouse wheel scrolls. Keyboard modifiers are
+//! reported with key events, and keypad keys respect num lock. See [`input`] for the details.
+//!
+//! Pressing the screenshot key (F12 by default) saves the display to a numbered PNG file in the
+//! working directory.
+//!
+//! [`embedded-graphics-simulator`]: https://github.com/embedded-graphics/simulator
+
+use std::{
+    thread,
+    time::{Duration, Instant as StdInstant},
+};
+
+use backend_embedded_graphics::{themes::Theme, EgCanvas};
+use embedded_graphics::{
+    draw_target::DrawTarget,
+    pixelcolor::{PixelColor, Rgb888},
+};
+use embedded_graphics_simulator::{
+    sdl2::Keycode, OutputSettings, SimulatorDisplay, SimulatorEvent, Window as SimWindow,
+};
+use embedded_gui::{
+    input::controller::InputController,
+    time::{Instant, TimeSource},
+    widgets::Widget,
+    WidgetRenderer, Window,
+};
+
+pub mod input;
+
+use crate::input::EventConverter;
+
+/// The canvas type of windows run by the [`Simulator`].
+pub type SimulatorCanvas<C> = EgCanvas<SimulatorDisplay<C>>;
+
+/// A [`TimeSource`] that measures the time since it was created.
+#[derive(Copy, Clone)]
+pub struct Clock {
+    start: StdInstant,
+}
+
+impl Clock {
+    pub fn new() -> Self {
+        Self {
+            start: StdInstant::now(),
+        }
+    }
+}
+
+impl Default for Clock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TimeSource for Clock {
+    fn now(&self) -> Instant {
+        Instant::from_millis(self.start.elapsed().as_millis() as u32)
+    }
+}
+
+/// Runs a window in a simulator window.
+pub struct Simulator {
+    title: String,
+    output_settings: OutputSettings,
+    frame_time: Duration,
+    scroll_step: i32,
+    screenshot_key: Option<Keycode>,
+}
+
+impl Simulator {
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            output_settings: OutputSettings::default(),
+            frame_time: Duration::from_millis(16),
+            scroll_step: 4,
+            screenshot_key: Some(Keycode::F12),
+        }
+    }
+
+    pub fn output_settings(mut self, output_settings: OutputSettings) -> Self {
+        self.output_settings = output_settings;
+        self
+    }
+
+    /// Sets the minimum duration of a frame. Defaults to 16ms, about 60 frames per second.
+    pub fn frame_time(mut self, frame_time: Duration) -> Self {
+        self.frame_time = frame_time;
+        self
+    }
+
+    /// Sets the distance, in pixels, a single step of the mouse wheel scrolls. Defaults to 4.
+    pub fn scroll_step(mut self, pixels: i32) -> Self {
+        self.scroll_step = pixels;
+        self
+    }
+
+    /// Sets the key that saves a screenshot, or disables screenshots when `None`.
+    pub fn screenshot_key(mut self, key: Option<Keycode>) -> Self {
+        self.screenshot_key = key;
+        self
+    }
+
+    /// Runs `gui` until the simulator window is closed.
+    pub fn run<C, W, I>(self, gui: &mut Window<SimulatorCanvas<C>, W, I>)
+    where
+        C: Theme + Into<Rgb888> + From<Rgb888>,
+        W: Widget + WidgetRenderer<SimulatorCanvas<C>>,
+        I: InputController,
+    {
+        self.run_with(gui, |_| {})
+    }
+
+    /// Runs `gui` until the simulator window is closed, calling `on_frame` before each frame is
+    /// laid out and drawn.
+    pub fn run_with<C, W, I>(
+        self,
+        gui: &mut Window<SimulatorCanvas<C>, W, I>,
+        mut on_frame: impl FnMut(&mut Window<SimulatorCanvas<C>, W, I>),
+    ) where
+        C: Theme + Into<Rgb888> + From<Rgb888>,
+        W: Widget + WidgetRenderer<SimulatorCanvas<C>>,
+        I: InputController,
+    {
+        let mut window = SimWindow::new(&self.title, &self.output_settings);
+        let mut converter = EventConverter::new().scroll_step(self.scroll_step);
+        let mut screenshots = 0;
+
+        loop {
+            let frame_start = StdInstant::now();
+
+            on_frame(gui);
+
+            gui.canvas.target.clear(C::BACKGROUND_COLOR).unwrap();
+            gui.update();
+            gui.measure();
+            gui.arrange();
+            gui.draw().unwrap();
+
+            window.update(&gui.canvas.target);
+
+            for event in window.events() {
+                match event {
+                    SimulatorEvent::Quit => return,
+                    SimulatorEvent::KeyDown {
+                        keycode,
+                        repeat: false,
+                        ..
+                    } if Some(keycode) == self.screenshot_key => {
+                        screenshots += 1;
+                        self.save_screenshot(&gui.canvas.target, screenshots);
+                    }
+                    event => converter.convert(event, |event| gui.input_event(event)),
+                }
+            }
+
+            if let Some(remaining) = self.frame_time.checked_sub(frame_start.elapsed()) {
+                thread::sleep(remaining);
+            }
+        }
+    }
+
+    fn save_screenshot<C>(&self, display: &SimulatorDisplay<C>, index: u32)
+    where
+        C: PixelColor + Into<Rgb888>,
+    {
+        let path = format!("screenshot-{}.png", index);
+
+        match display
+            .to_rgb_output_image(&self.output_settings)
+            .save_png(&path)
+        {
+            Ok(()) => println!("Screenshot saved to {}", path),
+            Err(error) => eprintln!("Failed to save screenshot to {}: {}", path, error),
+        }
+    }
+}
@@ -2,6 +2,7 @@ use core::ops::{Add, AddAssign, Neg, Sub};
 
 pub mod axis_order;
 pub mod measurement;
+pub mod orientation;
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Position {
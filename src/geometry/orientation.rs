@@ -0,0 +1,193 @@
+//! Display orientation.
+
+use crate::geometry::{MeasuredSize, Position};
+
+/// Clockwise rotation of the display, relative to its native orientation.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Rotation {
+    Rotate0,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+}
+
+/// Rotation and mirroring of the display, relative to its native orientation.
+///
+/// Mirroring is applied in native coordinates, before rotation.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Orientation {
+    pub rotation: Rotation,
+    pub mirror_x: bool,
+    pub mirror_y: bool,
+}
+
+impl Orientation {
+    pub const fn new(rotation: Rotation) -> Self {
+        Self {
+            rotation,
+            mirror_x: false,
+            mirror_y: false,
+        }
+    }
+
+    /// Mirrors the native horizontal axis.
+    pub fn mirror_x(mut self, mirror: bool) -> Self {
+        self.mirror_x = mirror;
+        self
+    }
+
+    /// Mirrors the native vertical axis.
+    pub fn mirror_y(mut self, mirror: bool) -> Self {
+        self.mirror_y = mirror;
+        self
+    }
+
+    /// Returns whether the horizontal and vertical axes are swapped.
+    pub fn swaps_axes(&self) -> bool {
+        matches!(self.rotation, Rotation::Rotate90 | Rotation::Rotate270)
+    }
+
+    /// Returns the size of a display with the native size `native_size` in this orientation.
+    pub fn transform_size(&self, native_size: MeasuredSize) -> MeasuredSize {
+        if self.swaps_axes() {
+            MeasuredSize {
+                width: native_size.height,
+                height: native_size.width,
+            }
+        } else {
+            native_size
+        }
+    }
+
+    /// Maps a position given in native coordinates to coordinates in this orientation.
+    pub fn transform(&self, position: Position, native_size: MeasuredSize) -> Position {
+        let w = native_size.width as i32;
+        let h = native_size.height as i32;
+
+        let Position { mut x, mut y } = position;
+        if self.mirror_x {
+            x = w - 1 - x;
+        }
+        if self.mirror_y {
+            y = h - 1 - y;
+        }
+
+        match self.rotation {
+            Rotation::Rotate0 => Position { x, y },
+            Rotation::Rotate90 => Position { x: h - 1 - y, y: x },
+            Rotation::Rotate180 => Position {
+                x: w - 1 - x,
+                y: h - 1 - y,
+            },
+            Rotation::Rotate270 => Position { x: y, y: w - 1 - x },
+        }
+    }
+
+    /// Maps a position given in coordinates of this orientation to native coordinates.
+    pub fn inverse_transform(&self, position: Position, native_size: MeasuredSize) -> Position {
+        let w = native_size.width as i32;
+        let h = native_size.height as i32;
+
+        let Position { x, y } = position;
+        let Position { mut x, mut y } = match self.rotation {
+            Rotation::Rotate0 => Position { x, y },
+            Rotation::Rotate90 => Position { x: y, y: h - 1 - x },
+            Rotation::Rotate180 => Position {
+                x: w - 1 - x,
+                y: h - 1 - y,
+            },
+            Rotation::Rotate270 => Position { x: w - 1 - y, y: x },
+        };
+
+        if self.mirror_x {
+            x = w - 1 - x;
+        }
+        if self.mirror_y {
+            y = h - 1 - y;
+        }
+
+        Position { x, y }
+    }
+}
+
+impl Default for Orientation {
+    fn default() -> Self {
+        Self::new(Rotation::Rotate0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::geometry::{
+        orientation::{Orientation, Rotation},
+        MeasuredSize, Position,
+    };
+
+    const ROTATIONS: [Rotation; 4] = [
+        Rotation::Rotate0,
+        Rotation::Rotate90,
+        Rotation::Rotate180,
+        Rotation::Rotate270,
+    ];
+
+    fn orientations() -> impl Iterator<Item = Orientation> {
+        ROTATIONS.iter().flat_map(|&rotation| {
+            [(false, false), (true, false), (false, true), (true, true)]
+                .iter()
+                .map(move |&(mirror_x, mirror_y)| {
+                    Orientation::new(rotation)
+                        .mirror_x(mirror_x)
+                        .mirror_y(mirror_y)
+                })
+        })
+    }
+
+    #[test]
+    fn inverse_transform_undoes_transform() {
+        let native_size = MeasuredSize {
+            width: 5,
+            height: 3,
+        };
+
+        for orientation in orientations() {
+            let size = orientation.transform_size(native_size);
+
+            for y in 0..native_size.height as i32 {
+                for x in 0..native_size.width as i32 {
+                    let native = Position { x, y };
+                    let oriented = orientation.transform(native, native_size);
+
+                    assert!(
+                        (0..size.width as i32).contains(&oriented.x)
+                            && (0..size.height as i32).contains(&oriented.y),
+                        "{:?} maps {:?} outside of the display",
+                        orientation,
+                        native
+                    );
+                    assert_eq!(
+                        orientation.inverse_transform(oriented, native_size),
+                        native,
+                        "{:?}",
+                        orientation
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn rotations_are_clockwise() {
+        let native_size = MeasuredSize {
+            width: 5,
+            height: 3,
+        };
+        // The top left corner of the native display
+        let corner =
+            |rotation| Orientation::new(rotation).transform(Position { x: 0, y: 0 }, native_size);
+
+        assert_eq!(corner(Rotation::Rotate0), Position { x: 0, y: 0 });
+        assert_eq!(corner(Rotation::Rotate90), Position { x: 2, y: 0 });
+        assert_eq!(corner(Rotation::Rotate180), Position { x: 4, y: 2 });
+        assert_eq!(corner(Rotation::Rotate270), Position { x: 0, y: 4 });
+    }
+}
@@ -0,0 +1,242 @@
+/// Builds a widget tree from nested blocks.
+///
+/// Layouts are written as their name, followed by their settings in parentheses and their
+/// children in braces. Any other widget is an expression, optionally followed by `=>` and a block
+/// of builder calls. Settings of a child's cell, like its weight in a linear layout, are written
+/// as an attribute on the child.
+///
+/// ```rust,ignore
+/// gui! {
+///     Column(spacing(2)) {
+///         Label::new("Counter"),
+///         Row {
+///             #[weight(1)]
+///             BinaryColor::primary_button("Add") => {
+///                 bind(&counter),
+///                 on_clicked(|count| *count += 1),
+///             },
+///             #[weight(1)]
+///             BinaryColor::secondary_button("Sub") => {
+///                 bind(&counter),
+///                 on_clicked(|count| *count -= 1),
+///             },
+///         },
+///     }
+/// }
+/// ```
+///
+/// expands to the builder calls:
+///
+/// ```rust,ignore
+/// Column::new()
+///     .spacing(2)
+///     .add(Label::new("Counter"))
+///     .add(
+///         Row::new()
+///             .add(BinaryColor::primary_button("Add").bind(&counter).on_clicked(|count| *count += 1))
+///             .weight(1)
+///             .add(BinaryColor::secondary_button("Sub").bind(&counter).on_clicked(|count| *count -= 1))
+///             .weight(1),
+///     )
+/// ```
+///
+/// `Row`, `Column`, `Flow`, `Frame` and `Absolute` don't need to be imported. Other layouts are
+/// created by calling `new()`, and children are added by calling `add()`. A child written as a
+/// struct literal must be wrapped in parentheses.
+///
+/// Type aliases
+/// ------------
+///
+/// The same tree, with the widgets replaced by their types, declares a type alias for the result.
+/// Only the settings that change the type of the layout need to be listed.
+///
+/// ```rust,ignore
+/// gui! {
+///     pub type Toolbar<C> = Row(spacing) {
+///         #[weight]
+///         StyledButton<'static, C>,
+///         #[weight]
+///         StyledButton<'static, C>,
+///     }
+/// }
+///
+/// fn toolbar<C: DefaultTheme>() -> Toolbar<C> {
+///     gui! {
+///         Row(spacing(1)) {
+///             #[weight(1)]
+///             C::primary_button("OK"),
+///             #[weight(1)]
+///             C::secondary_button("Cancel"),
+///         }
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! gui {
+    // Layouts
+    (@node Row $(($($settings:tt)*))? { $($children:tt)* }) => {
+        $crate::gui!(@layout add [$crate::widgets::layouts::linear::Row] $(($($settings)*))? { $($children)* })
+    };
+    (@node Column $(($($settings:tt)*))? { $($children:tt)* }) => {
+        $crate::gui!(@layout add [$crate::widgets::layouts::linear::Column] $(($($settings)*))? { $($children)* })
+    };
+    (@node Flow $(($($settings:tt)*))? { $($children:tt)* }) => {
+        $crate::gui!(@layout add [$crate::widgets::layouts::flow::Flow] $(($($settings)*))? { $($children)* })
+    };
+    (@node Frame $(($($settings:tt)*))? { $($children:tt)* }) => {
+        $crate::gui!(@layout add_layer [$crate::widgets::layouts::frame::Frame] $(($($settings)*))? { $($children)* })
+    };
+    (@node Absolute $(($($settings:tt)*))? { $($children:tt)* }) => {
+        $crate::gui!(@layout add [$crate::widgets::layouts::absolute::Absolute] $(($($settings)*))? { $($children)* })
+    };
+    (@node $layout:ident $(($($settings:tt)*))? { $($children:tt)* }) => {
+        $crate::gui!(@layout add [$layout] $(($($settings)*))? { $($children)* })
+    };
+
+    // Other widgets
+    (@node $widget:expr $(=> { $($method:ident($($args:tt)*)),* $(,)? })?) => {
+        $widget $($(.$method($($args)*))*)?
+    };
+
+    (@layout $add:ident [$($layout:tt)*] $((
+        $($setting:ident $(($($setting_args:tt)*))?),* $(,)?
+    ))? { $($children:tt)* }) => {
+        $crate::gui!(@children $add [
+            $($layout)*::new() $($(.$setting($($($setting_args)*)?))*)?
+        ] $($children)*)
+    };
+
+    // Children are added one by one, followed by the settings of their cells
+    (@children $add:ident [$($layout:tt)*]) => {
+        $($layout)*
+    };
+    (@children $add:ident [$($layout:tt)*]
+        #[$($cell:ident $(($($cell_args:tt)*))?),* $(,)?]
+        $child:ident $(($($settings:tt)*))? { $($children:tt)* } $(, $($rest:tt)*)?
+    ) => {
+        $crate::gui!(@children $add [
+            $($layout)*
+                .$add($crate::gui!(@node $child $(($($settings)*))? { $($children)* }))
+                $(.$cell($($($cell_args)*)?))*
+        ] $($($rest)*)?)
+    };
+    (@children $add:ident [$($layout:tt)*]
+        $child:ident $(($($settings:tt)*))? { $($children:tt)* } $(, $($rest:tt)*)?
+    ) => {
+        $crate::gui!(@children $add [
+            $($layout)*
+                .$add($crate::gui!(@node $child $(($($settings)*))? { $($children)* }))
+        ] $($($rest)*)?)
+    };
+    (@children $add:ident [$($layout:tt)*]
+        #[$($cell:ident $(($($cell_args:tt)*))?),* $(,)?]
+        $widget:expr $(=> { $($methods:tt)* })? $(, $($rest:tt)*)?
+    ) => {
+        $crate::gui!(@children $add [
+            $($layout)*
+                .$add($crate::gui!(@node $widget $(=> { $($methods)* })?))
+                $(.$cell($($($cell_args)*)?))*
+        ] $($($rest)*)?)
+    };
+    (@children $add:ident [$($layout:tt)*]
+        $widget:expr $(=> { $($methods:tt)* })? $(, $($rest:tt)*)?
+    ) => {
+        $crate::gui!(@children $add [
+            $($layout)*
+                .$add($crate::gui!(@node $widget $(=> { $($methods)* })?))
+        ] $($($rest)*)?)
+    };
+
+    // Types
+    (@type Row $(($($settings:tt)*))? { $($children:tt)* }) => {
+        $crate::widgets::layouts::linear::LinearLayout<
+            $crate::gui!(@chain linear [] $($children)*),
+            $crate::widgets::layouts::linear::Row<$crate::gui!(@spacing $($($settings)*)?)>,
+        >
+    };
+    (@type Column $(($($settings:tt)*))? { $($children:tt)* }) => {
+        $crate::widgets::layouts::linear::LinearLayout<
+            $crate::gui!(@chain linear [] $($children)*),
+            $crate::widgets::layouts::linear::Column<$crate::gui!(@spacing $($($settings)*)?)>,
+        >
+    };
+    (@type Flow $(($($settings:tt)*))? { $($children:tt)* }) => {
+        $crate::widgets::layouts::flow::FlowLayout<$crate::gui!(@chain flow [] $($children)*)>
+    };
+    (@type Frame $(($($settings:tt)*))? { $($children:tt)* }) => {
+        $crate::widgets::layouts::frame::FrameLayout<$crate::gui!(@chain frame [] $($children)*)>
+    };
+    (@type Absolute $(($($settings:tt)*))? { $($children:tt)* }) => {
+        $crate::widgets::layouts::absolute::AbsoluteLayout<
+            $crate::gui!(@chain absolute [] $($children)*)
+        >
+    };
+
+    (@chain $kind:ident [$($chain:tt)*]) => {
+        $($chain)*
+    };
+    (@chain $kind:ident [$($chain:tt)*]
+        $(#[$($cell:tt)*])? $layout:ident $(($($settings:tt)*))? { $($children:tt)* }
+        $(, $($rest:tt)*)?
+    ) => {
+        $crate::gui!(@chain $kind [
+            $crate::gui!(@link $kind [$($($cell)*)?]
+                ($crate::gui!(@type $layout $(($($settings)*))? { $($children)* }))
+                $($chain)*)
+        ] $($($rest)*)?)
+    };
+    (@chain $kind:ident [$($chain:tt)*] #[$($cell:tt)*] $widget:ty $(, $($rest:tt)*)?) => {
+        $crate::gui!(@chain $kind [
+            $crate::gui!(@link $kind [$($cell)*] ($widget) $($chain)*)
+        ] $($($rest)*)?)
+    };
+    (@chain $kind:ident [$($chain:tt)*] $widget:ty $(, $($rest:tt)*)?) => {
+        $crate::gui!(@chain $kind [
+            $crate::gui!(@link $kind [] ($widget) $($chain)*)
+        ] $($($rest)*)?)
+    };
+
+    (@link $kind:ident [$($cell:tt)*] ($widget:ty)) => {
+        $crate::widgets::layouts::linear::object_chain::Chain<
+            $crate::gui!(@cell $kind [$($cell)*] $widget)
+        >
+    };
+    (@link $kind:ident [$($cell:tt)*] ($widget:ty) $($parent:tt)+) => {
+        $crate::widgets::layouts::linear::object_chain::Link<
+            $crate::gui!(@cell $kind [$($cell)*] $widget),
+            $($parent)+
+        >
+    };
+
+    (@cell linear [$($cell:tt)*] $widget:ty) => {
+        $crate::widgets::layouts::linear::Cell<$widget, $crate::gui!(@weight $($cell)*)>
+    };
+    (@cell flow [$($cell:tt)*] $widget:ty) => {
+        $widget
+    };
+    (@cell frame [$($cell:tt)*] $widget:ty) => {
+        $crate::widgets::layouts::frame::Layer<$widget>
+    };
+    (@cell absolute [$($cell:tt)*] $widget:ty) => {
+        $crate::widgets::layouts::absolute::AbsoluteCell<$widget>
+    };
+
+    (@weight) => { $crate::widgets::layouts::linear::NoWeight };
+    (@weight weight $($rest:tt)*) => { $crate::widgets::layouts::linear::Weight };
+    (@weight $other:tt $($rest:tt)*) => { $crate::gui!(@weight $($rest)*) };
+
+    (@spacing) => { $crate::widgets::layouts::linear::NoSpacing };
+    (@spacing spacing $($rest:tt)*) => { $crate::widgets::layouts::linear::WithSpacing };
+    (@spacing $other:tt $($rest:tt)*) => { $crate::gui!(@spacing $($rest)*) };
+
+    // Entry points
+    ($vis:vis type $name:ident $(<$($lifetime:lifetime),* $(,)? $($param:ident),* $(,)?>)? =
+        $layout:ident $(($($settings:tt)*))? { $($children:tt)* } $(;)?
+    ) => {
+        $vis type $name $(<$($lifetime,)* $($param),*>)? =
+            $crate::gui!(@type $layout $(($($settings)*))? { $($children)* });
+    };
+    ($($tree:tt)+) => {
+        $crate::gui!(@node $($tree)+)
+    };
+}
@@ -1,27 +1,215 @@
-use crate::{input::event::InputEvent, widgets::Widget};
+//! Input event routing.
+//!
+//! The [`DefaultInputController`] first selects a target widget for each event, using
+//! [`Widget::test_input`], or the widget that currently captures the pointer. The event is then
+//! delivered in three phases:
+//!
+//!  * [`EventPhase::Capture`]: the event travels from the root down to the parent of the target.
+//!    This allows parents to intercept events before their children react, like a `Scroll` taking
+//!    over a drag that started on a button.
+//!  * [`EventPhase::Target`]: the target receives the event.
+//!  * [`EventPhase::Bubble`]: the event travels from the parent of the target back up to the root.
+//!
+//! Propagation ends when a widget handles the event (i.e. `handle_input` returns `true`) or calls
+//! [`InputContext::stop_propagation`]. If a parent handles the event in the capture or bubble phase,
+//! the target receives an [`InputEvent::Cancel`] event.
+//!
+//! # Touch input
+//!
+//! Touch points are tracked separately, each with its own capture. Touch events are sent to the
+//! widget that captures the touch point, or to the widget under it. The first touch point that is
+//! put down while no other touch point is down is the primary touch point. Unless a widget handles
+//! its touch events, the primary touch point is also delivered as a pointer event, so widgets that
+//! only handle pointer events work with touch panels. When a widget starts handling the primary
+//! touch point (e.g. because a pinch gesture started), the ongoing pointer interaction is cancelled.
+//!
+//! Only the parents that are part of the widget tree's parent chain (see
+//! [`Widget::parent_index`]) take part in the capture and bubble phases. Layouts and simple
+//! decorators are transparent and don't receive events on their own. At most 16 parents take
+//! part; the outermost parents of deeper chains don't receive the event.
+//!
+//! # Filtering
+//!
+//! Touch panels report noisy positions and may briefly lose contact while pressed. A
+//! [`PointerFilter`] in front of the input controller cleans up the stream of pointer events before
+//! widgets see it.
+
+use core::cell::Cell;
+
+use heapless::Vec;
+
+use crate::{
+    geometry::Position,
+    input::event::{InputEvent, PointerEvent, Touch},
+    time::{Instant, TimeSource},
+    widgets::Widget,
+};
+
+/// The maximum supported depth of the parent chain. Deeper chains are cut off at the outermost
+/// parents.
+const MAX_DEPTH: usize = 16;
+
+/// The maximum number of simultaneously tracked touch points.
+const MAX_TOUCH_POINTS: usize = 5;
 
 pub trait InputController {
     fn input_event(&mut self, root: &mut impl Widget, event: InputEvent);
+
+    /// Called periodically (by [`Window::update`]) so that controllers can act on elapsed time,
+    /// e.g. deliver events they held back.
+    ///
+    /// [`Window::update`]: crate::Window::update
+    fn update(&mut self, _root: &mut impl Widget) {}
+}
+
+/// The phase of the event propagation.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum EventPhase {
+    /// The event travels from the root towards the target.
+    Capture,
+    /// The event is delivered to its target.
+    Target,
+    /// The event travels from the target towards the root.
+    Bubble,
+}
+
+#[derive(Copy, Clone, PartialEq)]
+enum CaptureRequest {
+    None,
+    Capture,
+    Release,
+}
+
+struct Requests {
+    capture: Cell<CaptureRequest>,
+    stop_propagation: Cell<bool>,
+}
+
+impl Requests {
+    fn new() -> Self {
+        Self {
+            capture: Cell::new(CaptureRequest::None),
+            stop_propagation: Cell::new(false),
+        }
+    }
+}
+
+enum Propagation {
+    Continue,
+    Stopped,
+    Handled,
 }
 
-pub struct InputContext {
-    bubbled: bool,
+pub struct InputContext<'a> {
+    phase: EventPhase,
+    requests: &'a Requests,
 }
 
-impl InputContext {
-    /// Returns whether the input event is bubbled.
+impl InputContext<'_> {
+    /// Returns whether the input event is delivered in the [`EventPhase::Bubble`] phase.
+    ///
+    /// Note that this is `false` in the [`EventPhase::Capture`] phase, even though the widget is
+    /// not the target of the event. Before event phases were introduced, this returned `true` for
+    /// every widget other than the target. Use `phase() != EventPhase::Target` for that meaning.
     pub fn is_bubbled(&self) -> bool {
-        self.bubbled
+        self.phase == EventPhase::Bubble
+    }
+
+    /// Returns whether the widget is the target of the input event.
+    pub fn is_target(&self) -> bool {
+        self.phase == EventPhase::Target
+    }
+
+    /// Returns the current phase of the event propagation.
+    pub fn phase(&self) -> EventPhase {
+        self.phase
+    }
+
+    /// Prevents other widgets from receiving the current event, without handling it.
+    pub fn stop_propagation(&self) {
+        self.requests.stop_propagation.set(true);
+    }
+
+    /// Routes every event of the pointer (or touch point) that caused the current event to the
+    /// current widget until the pointer is released.
+    ///
+    /// A widget that handles `PointerEvent::Down` captures the pointer automatically.
+    pub fn capture_pointer(&self) {
+        self.requests.capture.set(CaptureRequest::Capture);
+    }
+
+    /// Releases the capture of the pointer (or touch point) that caused the current event, if the
+    /// current widget holds it.
+    pub fn release_pointer(&self) {
+        self.requests.capture.set(CaptureRequest::Release);
     }
 }
 
+struct TouchPoint {
+    id: u8,
+    capture: Option<usize>,
+}
+
+#[derive(Copy, Clone)]
+struct PrimaryTouch {
+    id: u8,
+    /// Whether the touch point is still delivered as pointer events.
+    emulated: bool,
+}
+
 pub struct DefaultInputController {
     last_handler: Option<usize>,
+    pointer_capture: Option<usize>,
+    hovered: Vec<usize, MAX_DEPTH>,
+    touch_points: Vec<TouchPoint, MAX_TOUCH_POINTS>,
+    primary_touch: Option<PrimaryTouch>,
 }
 
 impl DefaultInputController {
     pub fn new() -> Self {
-        Self { last_handler: None }
+        Self {
+            last_handler: None,
+            pointer_capture: None,
+            hovered: Vec::new(),
+            touch_points: Vec::new(),
+            primary_touch: None,
+        }
+    }
+
+    /// Returns the index of the widget that currently captures the pointer.
+    pub fn pointer_capture(&self) -> Option<usize> {
+        self.pointer_capture
+    }
+
+    /// Routes every pointer event to the widget at `index` until the pointer is released.
+    pub fn set_pointer_capture(&mut self, index: usize) {
+        self.pointer_capture = Some(index);
+    }
+
+    /// Releases the pointer capture.
+    pub fn release_pointer_capture(&mut self) {
+        self.pointer_capture = None;
+    }
+
+    /// Returns the index of the widget that captures the touch point `id`.
+    pub fn touch_capture(&self, id: u8) -> Option<usize> {
+        self.touch_points
+            .iter()
+            .find(|touch| touch.id == id)
+            .and_then(|touch| touch.capture)
+    }
+
+    /// Returns the capture that belongs to the pointer or touch point of `event`.
+    fn capture_of(&mut self, event: InputEvent) -> Option<&mut Option<usize>> {
+        match event {
+            InputEvent::PointerEvent(..) => Some(&mut self.pointer_capture),
+            InputEvent::TouchEvent(touch, _) => self
+                .touch_points
+                .iter_mut()
+                .find(|point| point.id == touch.id)
+                .map(|point| &mut point.capture),
+            _ => None,
+        }
     }
 
     fn get_mut_widget<'a>(
@@ -35,10 +223,238 @@ impl DefaultInputController {
             root.get_mut_child(idx - 1)
         }
     }
+
+    /// Sends `event` to a single widget and applies the pointer capture it requested.
+    fn send_event(
+        &mut self,
+        root: &mut impl Widget,
+        idx: usize,
+        phase: EventPhase,
+        event: InputEvent,
+    ) -> Propagation {
+        let requests = Requests::new();
+        let context = InputContext {
+            phase,
+            requests: &requests,
+        };
+
+        let handled = self.get_mut_widget(root, idx).handle_input(context, event);
+
+        let is_down = matches!(
+            event,
+            InputEvent::PointerEvent(_, PointerEvent::Down)
+                | InputEvent::TouchEvent(_, PointerEvent::Down)
+        );
+        if let Some(capture) = self.capture_of(event) {
+            match requests.capture.get() {
+                CaptureRequest::Capture => *capture = Some(idx),
+                CaptureRequest::Release => {
+                    if *capture == Some(idx) {
+                        *capture = None;
+                    }
+                }
+                CaptureRequest::None => {
+                    if handled {
+                        if is_down {
+                            *capture = Some(idx);
+                        } else if capture.is_some() && phase != EventPhase::Target {
+                            // a parent took over a captured pointer
+                            *capture = Some(idx);
+                        }
+                    }
+                }
+            }
+        }
+
+        if handled {
+            Propagation::Handled
+        } else if requests.stop_propagation.get() {
+            Propagation::Stopped
+        } else {
+            Propagation::Continue
+        }
+    }
+
+    /// Delivers `event` to `target` and its parents. Returns whether the event was handled.
+    fn dispatch(&mut self, root: &mut impl Widget, target: usize, event: InputEvent) -> bool {
+        // Parents of the target, innermost first
+        let mut parents = Vec::<usize, MAX_DEPTH>::new();
+        let mut idx = target;
+        while idx != 0 {
+            let parent = self.get_mut_widget(root, idx).parent_index();
+            debug_assert!(parent != idx);
+            if parents.push(parent).is_err() {
+                // The outermost parents of deeper chains are left out
+                break;
+            }
+            idx = parent;
+        }
+
+        for &parent in parents.iter().rev() {
+            match self.send_event(root, parent, EventPhase::Capture, event) {
+                Propagation::Continue => {}
+                Propagation::Stopped => return false,
+                Propagation::Handled => {
+                    self.cancel(root, target);
+                    return true;
+                }
+            }
+        }
+
+        match self.send_event(root, target, EventPhase::Target, event) {
+            Propagation::Continue => {}
+            Propagation::Stopped => return false,
+            Propagation::Handled => return true,
+        }
+
+        for &parent in parents.iter() {
+            match self.send_event(root, parent, EventPhase::Bubble, event) {
+                Propagation::Continue => {}
+                Propagation::Stopped => return false,
+                Propagation::Handled => {
+                    self.cancel(root, target);
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
+    /// Notifies `target` that one of its parents handled the event meant for it.
+    fn cancel(&mut self, root: &mut impl Widget, target: usize) {
+        let requests = Requests::new();
+        let context = InputContext {
+            phase: EventPhase::Target,
+            requests: &requests,
+        };
+        self.get_mut_widget(root, target)
+            .handle_input(context, InputEvent::Cancel);
+    }
+
+    fn touch_event(&mut self, root: &mut impl Widget, touch: Touch, pe: PointerEvent) {
+        if let PointerEvent::Down = pe {
+            if !self.touch_points.iter().any(|point| point.id == touch.id) {
+                let point = TouchPoint {
+                    id: touch.id,
+                    capture: None,
+                };
+                if self.touch_points.push(point).is_err() {
+                    // Too many touch points, ignore this one
+                    return;
+                }
+
+                if self.primary_touch.is_none() {
+                    self.primary_touch = Some(PrimaryTouch {
+                        id: touch.id,
+                        emulated: true,
+                    });
+                }
+            }
+        }
+
+        let capture = match self.touch_points.iter().find(|point| point.id == touch.id) {
+            Some(point) => point.capture,
+            // We don't track this touch point
+            None => return,
+        };
+
+        let event = InputEvent::TouchEvent(touch, pe);
+        let handled = match capture.or_else(|| root.hit_test(touch.position)) {
+            Some(target) => self.dispatch(root, target, event),
+            None => false,
+        };
+
+        match self.primary_touch {
+            Some(primary) if primary.id == touch.id => {
+                if handled {
+                    if primary.emulated {
+                        self.primary_touch = Some(PrimaryTouch {
+                            emulated: false,
+                            ..primary
+                        });
+                        if let Some(captured) = self.pointer_capture.take() {
+                            self.cancel(root, captured);
+                        }
+                    }
+                } else if primary.emulated {
+                    self.input_event(root, InputEvent::PointerEvent(touch.position, pe));
+                }
+
+                if let PointerEvent::Up = pe {
+                    self.primary_touch = None;
+                }
+            }
+            _ => {}
+        }
+
+        if let PointerEvent::Up = pe {
+            self.touch_points.retain(|point| point.id != touch.id);
+        }
+    }
+
+    /// Sends `Leave` and `Enter` events to the widgets the pointer left or entered.
+    fn update_hovered(&mut self, root: &mut impl Widget, position: Position) {
+        let mut hovered = Vec::<usize, MAX_DEPTH>::new();
+        if let Some(mut idx) = root.hit_test(position) {
+            while hovered.push(idx).is_ok() && idx != 0 {
+                let parent = self.get_mut_widget(root, idx).parent_index();
+                debug_assert!(parent != idx);
+                idx = parent;
+            }
+        }
+
+        let old = core::mem::replace(&mut self.hovered, hovered);
+
+        // innermost widget leaves first, outermost widget enters first
+        for &idx in old.iter() {
+            if self.hovered.contains(&idx) {
+                continue;
+            }
+            self.send_event(
+                root,
+                idx,
+                EventPhase::Target,
+                InputEvent::PointerEvent(position, PointerEvent::Leave),
+            );
+        }
+
+        for i in (0..self.hovered.len()).rev() {
+            let idx = self.hovered[i];
+            if !old.contains(&idx) {
+                self.send_event(
+                    root,
+                    idx,
+                    EventPhase::Target,
+                    InputEvent::PointerEvent(position, PointerEvent::Enter),
+                );
+            }
+        }
+    }
 }
 
 impl InputController for DefaultInputController {
     fn input_event(&mut self, root: &mut impl Widget, event: InputEvent) {
+        if let InputEvent::TouchEvent(touch, pe) = event {
+            self.touch_event(root, touch, pe);
+            return;
+        }
+
+        if let InputEvent::PointerEvent(position, pe) = event {
+            if let Some(captured) = self.pointer_capture {
+                self.last_handler = Some(captured);
+                self.dispatch(root, captured, event);
+
+                if let PointerEvent::Up = pe {
+                    self.pointer_capture = None;
+                    self.update_hovered(root, position);
+                }
+                return;
+            }
+
+            self.update_hovered(root, position);
+        }
+
         self.last_handler = if let Some(last) = self.last_handler {
             if let Some(handler) = self.get_mut_widget(root, last).test_input(event) {
                 // it's possible the widget wants to pass the event to it's child
@@ -52,29 +468,1262 @@ impl InputController for DefaultInputController {
         };
 
         if let Some(orig_handler) = self.last_handler {
-            let mut handler = orig_handler;
-            loop {
-                let widget = self.get_mut_widget(root, handler);
-                let context = InputContext { bubbled: false };
-                if widget.handle_input(context, event) {
-                    if handler != orig_handler {
-                        // parent handled a bubbled event, should notify child somehow
-                        let widget = self.get_mut_widget(root, orig_handler);
-                        let context = InputContext { bubbled: true };
-                        widget.handle_input(context, InputEvent::Cancel);
-                    }
-                    break;
+            self.dispatch(root, orig_handler, event);
+        }
+
+        if let InputEvent::PointerEvent(_, PointerEvent::Up) = event {
+            self.pointer_capture = None;
+        }
+    }
+}
+
+/// Smoothing of the pointer position while the pointer is pressed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Smoothing {
+    /// Positions are passed on unchanged.
+    None,
+
+    /// Median of the last three positions. Removes single-sample spikes without lagging behind.
+    Median,
+
+    /// Exponential moving average. The value is the weight of the newest position, in percent.
+    /// Lower values result in smoother, but slower following movement.
+    LowPass(u8),
+}
+
+/// The number of fractional bits of the low-pass filter state.
+const LOW_PASS_FRACTION_BITS: u32 = 8;
+
+/// The position history of the current press.
+struct PositionHistory {
+    samples: [Position; 3],
+    count: usize,
+    /// The low-pass filtered position, in fixed point. Keeping the fraction lets the filter reach
+    /// the pointer even when a step rounds down to less than a pixel.
+    filtered: (i64, i64),
+}
+
+impl PositionHistory {
+    fn new(position: Position) -> Self {
+        Self {
+            samples: [position; 3],
+            count: 1,
+            filtered: (to_fixed(position.x), to_fixed(position.y)),
+        }
+    }
+
+    fn add(&mut self, position: Position, smoothing: Smoothing) -> Position {
+        self.samples.rotate_right(1);
+        self.samples[0] = position;
+        self.count = (self.count + 1).min(3);
+
+        let filtered = match smoothing {
+            Smoothing::None => position,
+            Smoothing::Median if self.count < 3 => position,
+            Smoothing::Median => {
+                let [a, b, c] = self.samples;
+                Position {
+                    x: median(a.x, b.x, c.x),
+                    y: median(a.y, b.y, c.y),
+                }
+            }
+            Smoothing::LowPass(weight) => {
+                let weight = weight.clamp(1, 100) as i64;
+                let (x, y) = self.filtered;
+                self.filtered = (
+                    x + (to_fixed(position.x) - x) * weight / 100,
+                    y + (to_fixed(position.y) - y) * weight / 100,
+                );
+
+                return Position {
+                    x: from_fixed(self.filtered.0),
+                    y: from_fixed(self.filtered.1),
+                };
+            }
+        };
+
+        self.filtered = (to_fixed(filtered.x), to_fixed(filtered.y));
+        filtered
+    }
+}
+
+fn to_fixed(coordinate: i32) -> i64 {
+    (coordinate as i64) << LOW_PASS_FRACTION_BITS
+}
+
+/// Rounds a fixed point coordinate to the nearest integer.
+fn from_fixed(coordinate: i64) -> i32 {
+    ((coordinate + (1 << (LOW_PASS_FRACTION_BITS - 1))) >> LOW_PASS_FRACTION_BITS) as i32
+}
+
+fn median(a: i32, b: i32, c: i32) -> i32 {
+    a.max(b).min(a.min(b).max(c))
+}
+
+#[derive(Copy, Clone)]
+enum FilterState {
+    Released,
+
+    /// The pointer is down, but not for long enough to be delivered.
+    Touching {
+        since: Instant,
+        position: Position,
+    },
+
+    Pressed {
+        origin: Position,
+        position: Position,
+        /// Whether the pointer has left the dead zone.
+        dragging: bool,
+    },
+
+    /// The pointer was released, but may come back down within the debounce window.
+    Releasing {
+        since: Instant,
+        origin: Position,
+        position: Position,
+        dragging: bool,
+    },
+}
+
+/// An input controller adapter that filters pointer events before passing them to the wrapped
+/// controller.
+///
+/// Every filter is disabled by default:
+///
+///  * [`dead_zone`]: `Drag` events are held back until the pointer moves far enough from the
+///    position where it was pressed. Until then, the `Up` event is reported at the initial position.
+///  * [`smoothing`]: `Drag` positions are smoothed.
+///  * [`min_press_duration`]: the `Down` event is held back until the pointer is pressed long enough.
+///    Shorter presses are ignored.
+///  * [`debounce`]: the `Up` event is held back for a while. If the pointer is pressed again in the
+///    meantime, the release and the new press are ignored.
+///
+/// Held back events are delivered by later events, or by [`InputController::update`]. Other
+/// events, including touch events, are passed on unchanged.
+///
+/// ```rust,ignore
+/// let controller = PointerFilter::new(DefaultInputController::new(), clock)
+///     .dead_zone(4)
+///     .smoothing(Smoothing::Median)
+///     .min_press_duration(20)
+///     .debounce(50);
+///
+/// let window = Window::new(canvas, gui).with_input_controller(controller);
+/// ```
+///
+/// [`dead_zone`]: PointerFilter::dead_zone
+/// [`smoothing`]: PointerFilter::smoothing
+/// [`min_press_duration`]: PointerFilter::min_press_duration
+/// [`debounce`]: PointerFilter::debounce
+pub struct PointerFilter<I, T> {
+    inner: I,
+    time_source: T,
+    dead_zone: u32,
+    smoothing: Smoothing,
+    min_press_duration: u32,
+    debounce: u32,
+    state: FilterState,
+    history: PositionHistory,
+}
+
+impl<I, T> PointerFilter<I, T>
+where
+    I: InputController,
+    T: TimeSource,
+{
+    pub fn new(inner: I, time_source: T) -> Self {
+        Self {
+            inner,
+            time_source,
+            dead_zone: 0,
+            smoothing: Smoothing::None,
+            min_press_duration: 0,
+            debounce: 0,
+            state: FilterState::Released,
+            history: PositionHistory::new(Position { x: 0, y: 0 }),
+        }
+    }
+
+    /// Sets the distance, in pixels, the pointer needs to move before a drag starts.
+    pub fn dead_zone(mut self, distance: u32) -> Self {
+        self.dead_zone = distance;
+        self
+    }
+
+    pub fn smoothing(mut self, smoothing: Smoothing) -> Self {
+        self.smoothing = smoothing;
+        self
+    }
+
+    /// Sets the time, in milliseconds, the pointer needs to be pressed to register a press.
+    pub fn min_press_duration(mut self, millis: u32) -> Self {
+        self.min_press_duration = millis;
+        self
+    }
+
+    /// Sets the time, in milliseconds, a release is held back to detect contact bounces.
+    pub fn debounce(mut self, millis: u32) -> Self {
+        self.debounce = millis;
+        self
+    }
+
+    pub fn inner(&self) -> &I {
+        &self.inner
+    }
+
+    pub fn inner_mut(&mut self) -> &mut I {
+        &mut self.inner
+    }
+
+    fn send(&mut self, root: &mut impl Widget, position: Position, pe: PointerEvent) {
+        self.inner
+            .input_event(root, InputEvent::PointerEvent(position, pe));
+    }
+
+    /// Delivers the held back events whose time has come.
+    fn flush(&mut self, root: &mut impl Widget, now: Instant) {
+        match self.state {
+            FilterState::Touching { since, position }
+                if now.duration_since(since) >= self.min_press_duration =>
+            {
+                self.press(root, position);
+            }
+            FilterState::Releasing {
+                since, position, ..
+            } if now.duration_since(since) >= self.debounce => {
+                self.state = FilterState::Released;
+                self.send(root, position, PointerEvent::Up);
+            }
+            _ => {}
+        }
+    }
+
+    fn press(&mut self, root: &mut impl Widget, position: Position) {
+        self.state = FilterState::Pressed {
+            origin: position,
+            position,
+            dragging: false,
+        };
+        self.send(root, position, PointerEvent::Down);
+    }
+
+    fn drag(&mut self, root: &mut impl Widget, origin: Position, raw: Position, dragging: bool) {
+        let position = self.history.add(raw, self.smoothing);
+
+        let delta = position - origin;
+        let distance_sq = delta.x as i64 * delta.x as i64 + delta.y as i64 * delta.y as i64;
+        let dragging = dragging || distance_sq > self.dead_zone as i64 * self.dead_zone as i64;
+
+        self.state = FilterState::Pressed {
+            origin,
+            position,
+            dragging,
+        };
+
+        if dragging {
+            self.send(root, position, PointerEvent::Drag);
+        }
+    }
+
+    fn pointer_event(&mut self, root: &mut impl Widget, raw: Position, pe: PointerEvent) {
+        match (pe, self.state) {
+            (PointerEvent::Down, FilterState::Released) => {
+                self.history = PositionHistory::new(raw);
+                if self.min_press_duration == 0 {
+                    self.press(root, raw);
                 } else {
-                    let parent = widget.parent_index();
-                    if parent == 0 && handler == 0 {
-                        // I am Root
-                        break;
-                    } else {
-                        debug_assert!(parent != handler);
-                        handler = parent;
-                    }
+                    self.state = FilterState::Touching {
+                        since: self.time_source.now(),
+                        position: raw,
+                    };
+                }
+            }
+
+            // The contact bounced, continue the previous press
+            (
+                PointerEvent::Down,
+                FilterState::Releasing {
+                    origin, dragging, ..
+                },
+            ) => self.drag(root, origin, raw, dragging),
+
+            (PointerEvent::Down, FilterState::Touching { .. })
+            | (PointerEvent::Drag, FilterState::Touching { .. }) => {
+                self.history.add(raw, self.smoothing);
+            }
+
+            (
+                PointerEvent::Down,
+                FilterState::Pressed {
+                    origin, dragging, ..
+                },
+            )
+            | (
+                PointerEvent::Drag,
+                FilterState::Pressed {
+                    origin, dragging, ..
+                },
+            ) => self.drag(root, origin, raw, dragging),
+
+            // The press was too short
+            (PointerEvent::Up, FilterState::Touching { .. }) => {
+                self.state = FilterState::Released;
+            }
+
+            (
+                PointerEvent::Up,
+                FilterState::Pressed {
+                    origin,
+                    position,
+                    dragging,
+                },
+            ) => {
+                // Without dragging, the pointer is still where it was pressed
+                let position = if dragging { position } else { origin };
+
+                if self.debounce == 0 {
+                    self.state = FilterState::Released;
+                    self.send(root, position, PointerEvent::Up);
+                } else {
+                    self.state = FilterState::Releasing {
+                        since: self.time_source.now(),
+                        origin,
+                        position,
+                        dragging,
+                    };
+                }
+            }
+
+            (PointerEvent::Drag, FilterState::Releasing { .. })
+            | (PointerEvent::Up, FilterState::Releasing { .. })
+            | (PointerEvent::Up, FilterState::Released) => {}
+
+            _ => self.send(root, raw, pe),
+        }
+    }
+}
+
+impl<I, T> InputController for PointerFilter<I, T>
+where
+    I: InputController,
+    T: TimeSource,
+{
+    fn input_event(&mut self, root: &mut impl Widget, event: InputEvent) {
+        let now = self.time_source.now();
+        self.flush(root, now);
+
+        match event {
+            InputEvent::PointerEvent(position, pe) => self.pointer_event(root, position, pe),
+            _ => self.inner.input_event(root, event),
+        }
+    }
+
+    fn update(&mut self, root: &mut impl Widget) {
+        let now = self.time_source.now();
+        self.flush(root, now);
+
+        self.inner.update(root);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use core::cell::{Cell, RefCell};
+
+    use heapless::Vec;
+
+    use crate::{
+        geometry::{measurement::MeasureSpec, BoundingBox, MeasuredSize, Position},
+        input::{
+            controller::{
+                DefaultInputController, EventPhase, InputContext, InputController, PointerFilter,
+                Smoothing,
+            },
+            event::{InputEvent, PointerEvent, Touch},
+        },
+        state::WidgetState,
+        time::{Instant, TimeSource},
+        widgets::Widget,
+    };
+
+    struct Clock(Cell<u32>);
+
+    impl Clock {
+        fn advance(&self, millis: u32) {
+            self.0.set(self.0.get() + millis);
+        }
+    }
+
+    impl TimeSource for Clock {
+        fn now(&self) -> Instant {
+            Instant::from_millis(self.0.get())
+        }
+    }
+
+    struct Root;
+
+    impl Widget for Root {
+        fn bounding_box(&self) -> BoundingBox {
+            BoundingBox::default()
+        }
+
+        fn measure(&mut self, _measure_spec: MeasureSpec) {}
+
+        fn parent_index(&self) -> usize {
+            0
+        }
+
+        fn on_state_changed(&mut self, _state: WidgetState) {}
+    }
+
+    /// Records the filtered events.
+    #[derive(Default)]
+    struct Recorder {
+        events: Vec<(Position, &'static str), 16>,
+    }
+
+    impl InputController for Recorder {
+        fn input_event(&mut self, _root: &mut impl Widget, event: InputEvent) {
+            if let InputEvent::PointerEvent(position, pe) = event {
+                let name = match pe {
+                    PointerEvent::Down => "down",
+                    PointerEvent::Drag => "drag",
+                    PointerEvent::Up => "up",
+                    _ => "other",
+                };
+                self.events.push((position, name)).unwrap();
+            }
+        }
+    }
+
+    fn pos(x: i32, y: i32) -> Position {
+        Position { x, y }
+    }
+
+    fn send(filter: &mut PointerFilter<Recorder, &Clock>, x: i32, y: i32, pe: PointerEvent) {
+        filter.input_event(&mut Root, InputEvent::PointerEvent(pos(x, y), pe));
+    }
+
+    fn events<'a>(filter: &'a PointerFilter<Recorder, &Clock>) -> &'a [(Position, &'static str)] {
+        &filter.inner().events
+    }
+
+    #[test]
+    fn dead_zone_suppresses_jitter() {
+        let clock = Clock(Cell::new(0));
+        let mut filter = PointerFilter::new(Recorder::default(), &clock).dead_zone(3);
+
+        send(&mut filter, 10, 10, PointerEvent::Down);
+        send(&mut filter, 12, 11, PointerEvent::Drag);
+        send(&mut filter, 9, 8, PointerEvent::Drag);
+        send(&mut filter, 11, 10, PointerEvent::Up);
+
+        assert_eq!(
+            events(&filter),
+            &[(pos(10, 10), "down"), (pos(10, 10), "up")]
+        );
+    }
+
+    #[test]
+    fn drag_continues_after_leaving_dead_zone() {
+        let clock = Clock(Cell::new(0));
+        let mut filter = PointerFilter::new(Recorder::default(), &clock).dead_zone(3);
+
+        send(&mut filter, 10, 10, PointerEvent::Down);
+        send(&mut filter, 15, 10, PointerEvent::Drag);
+        send(&mut filter, 16, 10, PointerEvent::Drag);
+        send(&mut filter, 16, 11, PointerEvent::Up);
+
+        assert_eq!(
+            events(&filter),
+            &[
+                (pos(10, 10), "down"),
+                (pos(15, 10), "drag"),
+                (pos(16, 10), "drag"),
+                (pos(16, 10), "up"),
+            ]
+        );
+    }
+
+    #[test]
+    fn median_removes_spikes() {
+        let clock = Clock(Cell::new(0));
+        let mut filter =
+            PointerFilter::new(Recorder::default(), &clock).smoothing(Smoothing::Median);
+
+        send(&mut filter, 10, 10, PointerEvent::Down);
+        send(&mut filter, 20, 10, PointerEvent::Drag);
+        send(&mut filter, 22, 10, PointerEvent::Drag);
+        send(&mut filter, 90, 50, PointerEvent::Drag);
+        send(&mut filter, 24, 10, PointerEvent::Drag);
+
+        assert_eq!(
+            events(&filter),
+            &[
+                (pos(10, 10), "down"),
+                (pos(20, 10), "drag"),
+                (pos(20, 10), "drag"),
+                (pos(22, 10), "drag"),
+                (pos(24, 10), "drag"),
+            ]
+        );
+    }
+
+    #[test]
+    fn low_pass_follows_slowly() {
+        let clock = Clock(Cell::new(0));
+        let mut filter =
+            PointerFilter::new(Recorder::default(), &clock).smoothing(Smoothing::LowPass(50));
+
+        send(&mut filter, 0, 0, PointerEvent::Down);
+        send(&mut filter, 40, 0, PointerEvent::Drag);
+        send(&mut filter, 40, 0, PointerEvent::Drag);
+
+        assert_eq!(
+            events(&filter),
+            &[
+                (pos(0, 0), "down"),
+                (pos(20, 0), "drag"),
+                (pos(30, 0), "drag")
+            ]
+        );
+    }
+
+    #[test]
+    fn low_pass_reaches_stationary_pointer() {
+        let clock = Clock(Cell::new(0));
+        let mut filter =
+            PointerFilter::new(Recorder::default(), &clock).smoothing(Smoothing::LowPass(10));
+
+        send(&mut filter, 0, 0, PointerEvent::Down);
+        for _ in 0..30 {
+            send(&mut filter, 7, -7, PointerEvent::Drag);
+            filter.inner_mut().events.clear();
+        }
+        send(&mut filter, 7, -7, PointerEvent::Drag);
+
+        assert_eq!(events(&filter), &[(pos(7, -7), "drag")]);
+    }
+
+    #[test]
+    fn short_presses_are_ignored() {
+        let clock = Clock(Cell::new(0));
+        let mut filter = PointerFilter::new(Recorder::default(), &clock).min_press_duration(20);
+
+        send(&mut filter, 10, 10, PointerEvent::Down);
+        clock.advance(10);
+        send(&mut filter, 10, 10, PointerEvent::Up);
+        assert_eq!(events(&filter), &[]);
+
+        send(&mut filter, 10, 10, PointerEvent::Down);
+        clock.advance(20);
+        filter.update(&mut Root);
+        assert_eq!(events(&filter), &[(pos(10, 10), "down")]);
+
+        send(&mut filter, 10, 10, PointerEvent::Up);
+        assert_eq!(
+            events(&filter),
+            &[(pos(10, 10), "down"), (pos(10, 10), "up")]
+        );
+    }
+
+    #[test]
+    fn bounces_are_debounced() {
+        let clock = Clock(Cell::new(0));
+        let mut filter = PointerFilter::new(Recorder::default(), &clock).debounce(30);
+
+        send(&mut filter, 10, 10, PointerEvent::Down);
+        clock.advance(100);
+        send(&mut filter, 10, 10, PointerEvent::Up);
+        clock.advance(5);
+        send(&mut filter, 10, 10, PointerEvent::Down);
+        clock.advance(100);
+        send(&mut filter, 10, 10, PointerEvent::Up);
+        assert_eq!(events(&filter), &[(pos(10, 10), "down")]);
+
+        clock.advance(29);
+        filter.update(&mut Root);
+        assert_eq!(events(&filter), &[(pos(10, 10), "down")]);
+
+        clock.advance(1);
+        filter.update(&mut Root);
+        assert_eq!(
+            events(&filter),
+            &[(pos(10, 10), "down"), (pos(10, 10), "up")]
+        );
+    }
+
+    type Log = RefCell<Vec<(usize, &'static str, EventPhase), 64>>;
+
+    #[derive(Copy, Clone, PartialEq)]
+    enum Response {
+        Ignore,
+        Handle,
+        Stop,
+    }
+
+    fn ignore(_phase: EventPhase, _event: InputEvent) -> Response {
+        Response::Ignore
+    }
+
+    fn handle(_phase: EventPhase, _event: InputEvent) -> Response {
+        Response::Handle
+    }
+
+    fn event_name(event: InputEvent) -> &'static str {
+        match event {
+            InputEvent::Cancel => "cancel",
+            InputEvent::PointerEvent(_, PointerEvent::Hover) => "hover",
+            InputEvent::PointerEvent(_, PointerEvent::Down) => "down",
+            InputEvent::PointerEvent(_, PointerEvent::Drag) => "drag",
+            InputEvent::PointerEvent(_, PointerEvent::Up) => "up",
+            InputEvent::PointerEvent(_, PointerEvent::Enter) => "enter",
+            InputEvent::PointerEvent(_, PointerEvent::Leave) => "leave",
+            InputEvent::TouchEvent(_, PointerEvent::Down) => "touch down",
+            InputEvent::TouchEvent(_, PointerEvent::Drag) => "touch drag",
+            InputEvent::TouchEvent(_, PointerEvent::Up) => "touch up",
+            _ => "other",
+        }
+    }
+
+    /// A widget of a synthetic widget tree. Records the events it receives.
+    struct Node<'a> {
+        index: usize,
+        parent: usize,
+        bounds: BoundingBox,
+        respond: fn(EventPhase, InputEvent) -> Response,
+        log: &'a Log,
+    }
+
+    impl Node<'_> {
+        fn contains(&self, event: InputEvent) -> bool {
+            matches!(event.position(), Some(position) if self.bounds.contains(position))
+        }
+    }
+
+    impl Widget for Node<'_> {
+        fn bounding_box(&self) -> BoundingBox {
+            self.bounds
+        }
+
+        fn measure(&mut self, _measure_spec: MeasureSpec) {}
+
+        fn parent_index(&self) -> usize {
+            self.parent
+        }
+
+        fn test_input(&mut self, event: InputEvent) -> Option<usize> {
+            if self.contains(event) {
+                Some(0)
+            } else {
+                None
+            }
+        }
+
+        fn handle_input(&mut self, ctxt: InputContext, event: InputEvent) -> bool {
+            self.log
+                .borrow_mut()
+                .push((self.index, event_name(event), ctxt.phase()))
+                .unwrap();
+
+            match (self.respond)(ctxt.phase(), event) {
+                Response::Ignore => false,
+                Response::Handle => true,
+                Response::Stop => {
+                    ctxt.stop_propagation();
+                    false
                 }
             }
         }
+
+        fn on_state_changed(&mut self, _state: WidgetState) {}
+    }
+
+    /// A flat widget tree. The first node is the root, every other node lists the index of its
+    /// parent. Nodes must come after their parents, so the last node under the pointer is the
+    /// innermost one.
+    struct Tree<'a> {
+        nodes: Vec<Node<'a>, 24>,
+    }
+
+    impl<'a> Tree<'a> {
+        #[allow(clippy::type_complexity)]
+        fn new(
+            log: &'a Log,
+            nodes: &[(
+                usize,
+                (i32, i32, u32, u32),
+                fn(EventPhase, InputEvent) -> Response,
+            )],
+        ) -> Self {
+            let nodes = nodes
+                .iter()
+                .enumerate()
+                .map(|(index, &(parent, (x, y, width, height), respond))| Node {
+                    index,
+                    parent,
+                    bounds: BoundingBox {
+                        position: pos(x, y),
+                        size: MeasuredSize { width, height },
+                    },
+                    respond,
+                    log,
+                })
+                .collect();
+
+            Self { nodes }
+        }
+    }
+
+    impl Widget for Tree<'_> {
+        fn bounding_box(&self) -> BoundingBox {
+            self.nodes[0].bounds
+        }
+
+        fn children(&self) -> usize {
+            self.nodes.len() - 1
+        }
+
+        fn get_child(&self, idx: usize) -> &dyn Widget {
+            &self.nodes[idx + 1]
+        }
+
+        fn get_mut_child(&mut self, idx: usize) -> &mut dyn Widget {
+            &mut self.nodes[idx + 1]
+        }
+
+        fn measure(&mut self, _measure_spec: MeasureSpec) {}
+
+        fn parent_index(&self) -> usize {
+            0
+        }
+
+        fn test_input(&mut self, event: InputEvent) -> Option<usize> {
+            event
+                .position()
+                .and_then(|position| self.hit_test(position))
+        }
+
+        fn hit_test(&self, position: Position) -> Option<usize> {
+            self.nodes
+                .iter()
+                .rposition(|node| node.bounds.contains(position))
+        }
+
+        fn handle_input(&mut self, ctxt: InputContext, event: InputEvent) -> bool {
+            self.nodes[0].handle_input(ctxt, event)
+        }
+
+        fn on_state_changed(&mut self, _state: WidgetState) {}
+    }
+
+    fn pointer(
+        controller: &mut DefaultInputController,
+        tree: &mut Tree,
+        x: i32,
+        y: i32,
+        pe: PointerEvent,
+    ) {
+        controller.input_event(tree, InputEvent::PointerEvent(pos(x, y), pe));
+    }
+
+    /// Returns and clears the recorded events.
+    fn take(log: &Log) -> Vec<(usize, &'static str, EventPhase), 64> {
+        core::mem::take(&mut *log.borrow_mut())
+    }
+
+    /// Returns and clears the recorded `Enter` and `Leave` events.
+    fn take_hover(log: &Log) -> Vec<(usize, &'static str), 64> {
+        take(log)
+            .iter()
+            .filter(|(_, name, _)| *name == "enter" || *name == "leave")
+            .map(|&(index, name, _)| (index, name))
+            .collect()
+    }
+
+    #[test]
+    fn captured_pointer_is_delivered_outside_of_the_widget() {
+        let log = Log::default();
+        let mut tree = Tree::new(
+            &log,
+            &[
+                (0, (0, 0, 100, 100), ignore),
+                (0, (0, 0, 50, 50), handle),
+                (0, (50, 0, 50, 50), handle),
+            ],
+        );
+        let mut controller = DefaultInputController::new();
+
+        pointer(&mut controller, &mut tree, 10, 10, PointerEvent::Down);
+        assert_eq!(controller.pointer_capture(), Some(1));
+        take(&log);
+
+        pointer(&mut controller, &mut tree, 70, 10, PointerEvent::Drag);
+        pointer(&mut controller, &mut tree, 70, 10, PointerEvent::Up);
+
+        assert_eq!(
+            take(&log),
+            &[
+                (0, "drag", EventPhase::Capture),
+                (1, "drag", EventPhase::Target),
+                (0, "up", EventPhase::Capture),
+                (1, "up", EventPhase::Target),
+                // the hovered widget is only updated after the capture ends
+                (1, "leave", EventPhase::Target),
+                (2, "enter", EventPhase::Target),
+            ]
+        );
+        assert_eq!(controller.pointer_capture(), None);
+    }
+
+    #[test]
+    fn enter_and_leave_between_siblings() {
+        let log = Log::default();
+        let mut tree = Tree::new(
+            &log,
+            &[
+                (0, (0, 0, 100, 100), ignore),
+                (0, (0, 0, 50, 50), handle),
+                (0, (50, 0, 50, 50), handle),
+            ],
+        );
+        let mut controller = DefaultInputController::new();
+
+        pointer(&mut controller, &mut tree, 10, 10, PointerEvent::Hover);
+        assert_eq!(take_hover(&log), &[(0, "enter"), (1, "enter")]);
+
+        pointer(&mut controller, &mut tree, 20, 10, PointerEvent::Hover);
+        assert_eq!(take_hover(&log), &[]);
+
+        pointer(&mut controller, &mut tree, 60, 10, PointerEvent::Hover);
+        assert_eq!(take_hover(&log), &[(1, "leave"), (2, "enter")]);
+
+        pointer(&mut controller, &mut tree, 200, 10, PointerEvent::Hover);
+        assert_eq!(take_hover(&log), &[(2, "leave"), (0, "leave")]);
+    }
+
+    #[test]
+    fn enter_and_leave_between_parent_and_child() {
+        let log = Log::default();
+        let mut tree = Tree::new(
+            &log,
+            &[
+                (0, (0, 0, 100, 100), ignore),
+                (0, (0, 0, 60, 60), handle),
+                (1, (10, 10, 20, 20), handle),
+            ],
+        );
+        let mut controller = DefaultInputController::new();
+
+        pointer(&mut controller, &mut tree, 80, 80, PointerEvent::Hover);
+        assert_eq!(take_hover(&log), &[(0, "enter")]);
+
+        // outer widgets enter first
+        pointer(&mut controller, &mut tree, 15, 15, PointerEvent::Hover);
+        assert_eq!(take_hover(&log), &[(1, "enter"), (2, "enter")]);
+
+        // the parent is still hovered while the pointer is over its child
+        pointer(&mut controller, &mut tree, 40, 40, PointerEvent::Hover);
+        assert_eq!(take_hover(&log), &[(2, "leave")]);
+
+        pointer(&mut controller, &mut tree, 15, 15, PointerEvent::Hover);
+        assert_eq!(take_hover(&log), &[(2, "enter")]);
+
+        // inner widgets leave first
+        pointer(&mut controller, &mut tree, 200, 200, PointerEvent::Hover);
+        assert_eq!(
+            take_hover(&log),
+            &[(2, "leave"), (1, "leave"), (0, "leave")]
+        );
+    }
+
+    /// A chain of nested widgets: 0 contains 1, 1 contains 2, 2 contains 3.
+    fn nested<'a>(log: &'a Log, respond: [fn(EventPhase, InputEvent) -> Response; 4]) -> Tree<'a> {
+        Tree::new(
+            log,
+            &[
+                (0, (0, 0, 100, 100), respond[0]),
+                (0, (10, 10, 80, 80), respond[1]),
+                (1, (20, 20, 60, 60), respond[2]),
+                (2, (30, 30, 40, 40), respond[3]),
+            ],
+        )
+    }
+
+    fn down(controller: &mut DefaultInputController, tree: &mut Tree) {
+        pointer(controller, tree, 50, 50, PointerEvent::Down);
+    }
+
+    /// Returns and clears the recorded events, except for `Enter` and `Leave`.
+    fn take_dispatched(log: &Log) -> Vec<(usize, &'static str, EventPhase), 64> {
+        take(log)
+            .iter()
+            .filter(|(_, name, _)| *name != "enter" && *name != "leave")
+            .cloned()
+            .collect()
+    }
+
+    #[test]
+    fn events_are_captured_then_targeted_then_bubbled() {
+        let log = Log::default();
+        let mut tree = nested(&log, [ignore; 4]);
+        let mut controller = DefaultInputController::new();
+
+        down(&mut controller, &mut tree);
+
+        assert_eq!(
+            take_dispatched(&log),
+            &[
+                (0, "down", EventPhase::Capture),
+                (1, "down", EventPhase::Capture),
+                (2, "down", EventPhase::Capture),
+                (3, "down", EventPhase::Target),
+                (2, "down", EventPhase::Bubble),
+                (1, "down", EventPhase::Bubble),
+                (0, "down", EventPhase::Bubble),
+            ]
+        );
+        assert_eq!(controller.pointer_capture(), None);
+    }
+
+    #[test]
+    fn deep_parent_chains_are_cut_off() {
+        let log = Log::default();
+        let mut nodes = Vec::<_, 20>::new();
+        for index in 0..20usize {
+            let node = (
+                index.saturating_sub(1),
+                (0, 0, 100, 100),
+                ignore as fn(_, _) -> _,
+            );
+            nodes.push(node).unwrap();
+        }
+        let mut tree = Tree::new(&log, &nodes);
+        let mut controller = DefaultInputController::new();
+
+        down(&mut controller, &mut tree);
+
+        // Only the innermost 16 parents take part
+        let dispatched = take_dispatched(&log);
+        assert_eq!(dispatched.len(), 33);
+        assert_eq!(dispatched[0], (3, "down", EventPhase::Capture));
+        assert_eq!(dispatched[16], (19, "down", EventPhase::Target));
+        assert_eq!(dispatched[32], (3, "down", EventPhase::Bubble));
+    }
+
+    #[test]
+    fn stop_propagation_in_capture_phase() {
+        fn stop_capture(phase: EventPhase, _event: InputEvent) -> Response {
+            if phase == EventPhase::Capture {
+                Response::Stop
+            } else {
+                Response::Ignore
+            }
+        }
+
+        let log = Log::default();
+        let mut tree = nested(&log, [ignore, stop_capture, ignore, ignore]);
+        let mut controller = DefaultInputController::new();
+
+        down(&mut controller, &mut tree);
+
+        assert_eq!(
+            take_dispatched(&log),
+            &[
+                (0, "down", EventPhase::Capture),
+                (1, "down", EventPhase::Capture),
+            ]
+        );
+        assert_eq!(controller.pointer_capture(), None);
+    }
+
+    #[test]
+    fn stop_propagation_in_target_phase() {
+        fn stop(_phase: EventPhase, _event: InputEvent) -> Response {
+            Response::Stop
+        }
+
+        let log = Log::default();
+        let mut tree = nested(&log, [ignore, ignore, ignore, stop]);
+        let mut controller = DefaultInputController::new();
+
+        down(&mut controller, &mut tree);
+
+        assert_eq!(
+            take_dispatched(&log),
+            &[
+                (0, "down", EventPhase::Capture),
+                (1, "down", EventPhase::Capture),
+                (2, "down", EventPhase::Capture),
+                (3, "down", EventPhase::Target),
+            ]
+        );
+    }
+
+    #[test]
+    fn stop_propagation_in_bubble_phase() {
+        fn stop_bubble(phase: EventPhase, _event: InputEvent) -> Response {
+            if phase == EventPhase::Bubble {
+                Response::Stop
+            } else {
+                Response::Ignore
+            }
+        }
+
+        let log = Log::default();
+        let mut tree = nested(&log, [ignore, stop_bubble, ignore, ignore]);
+        let mut controller = DefaultInputController::new();
+
+        down(&mut controller, &mut tree);
+
+        // stopping the propagation doesn't cancel the target
+        assert_eq!(
+            take_dispatched(&log),
+            &[
+                (0, "down", EventPhase::Capture),
+                (1, "down", EventPhase::Capture),
+                (2, "down", EventPhase::Capture),
+                (3, "down", EventPhase::Target),
+                (2, "down", EventPhase::Bubble),
+                (1, "down", EventPhase::Bubble),
+            ]
+        );
+    }
+
+    #[test]
+    fn parent_handling_in_capture_phase_cancels_target() {
+        fn steal_drag(phase: EventPhase, event: InputEvent) -> Response {
+            match (phase, event) {
+                (EventPhase::Capture, InputEvent::PointerEvent(_, PointerEvent::Drag)) => {
+                    Response::Handle
+                }
+                _ => Response::Ignore,
+            }
+        }
+
+        let log = Log::default();
+        let mut tree = nested(&log, [ignore, steal_drag, ignore, handle]);
+        let mut controller = DefaultInputController::new();
+
+        down(&mut controller, &mut tree);
+        assert_eq!(controller.pointer_capture(), Some(3));
+        take(&log);
+
+        pointer(&mut controller, &mut tree, 60, 50, PointerEvent::Drag);
+        assert_eq!(
+            take_dispatched(&log),
+            &[
+                (0, "drag", EventPhase::Capture),
+                (1, "drag", EventPhase::Capture),
+                (3, "cancel", EventPhase::Target),
+            ]
+        );
+
+        // the parent took over the pointer
+        assert_eq!(controller.pointer_capture(), Some(1));
+        pointer(&mut controller, &mut tree, 60, 50, PointerEvent::Up);
+        assert_eq!(
+            take_dispatched(&log),
+            &[
+                (0, "up", EventPhase::Capture),
+                (1, "up", EventPhase::Target),
+                (0, "up", EventPhase::Bubble),
+            ]
+        );
+    }
+
+    #[test]
+    fn parent_handling_in_bubble_phase_cancels_target() {
+        fn handle_bubble(phase: EventPhase, _event: InputEvent) -> Response {
+            if phase == EventPhase::Bubble {
+                Response::Handle
+            } else {
+                Response::Ignore
+            }
+        }
+
+        let log = Log::default();
+        let mut tree = nested(&log, [ignore, ignore, handle_bubble, ignore]);
+        let mut controller = DefaultInputController::new();
+
+        down(&mut controller, &mut tree);
+
+        assert_eq!(
+            take_dispatched(&log),
+            &[
+                (0, "down", EventPhase::Capture),
+                (1, "down", EventPhase::Capture),
+                (2, "down", EventPhase::Capture),
+                (3, "down", EventPhase::Target),
+                (2, "down", EventPhase::Bubble),
+                (3, "cancel", EventPhase::Target),
+            ]
+        );
+        assert_eq!(controller.pointer_capture(), Some(2));
+    }
+
+    fn handle_touch(_phase: EventPhase, event: InputEvent) -> Response {
+        if let InputEvent::TouchEvent(..) = event {
+            Response::Handle
+        } else {
+            Response::Ignore
+        }
+    }
+
+    fn touch(
+        controller: &mut DefaultInputController,
+        tree: &mut Tree,
+        id: u8,
+        x: i32,
+        y: i32,
+        pe: PointerEvent,
+    ) {
+        controller.input_event(tree, InputEvent::TouchEvent(Touch::new(id, pos(x, y)), pe));
+    }
+
+    /// Returns the events the widget at `index` received as their target, except for `Enter` and
+    /// `Leave`.
+    fn received(log: &Log, index: usize) -> Vec<&'static str, 64> {
+        log.borrow()
+            .iter()
+            .filter(|(idx, name, phase)| {
+                *idx == index
+                    && *phase == EventPhase::Target
+                    && *name != "enter"
+                    && *name != "leave"
+            })
+            .map(|&(_, name, _)| name)
+            .collect()
+    }
+
+    #[test]
+    fn touch_points_are_captured_separately() {
+        let log = Log::default();
+        let mut tree = Tree::new(
+            &log,
+            &[
+                (0, (0, 0, 100, 100), ignore),
+                (0, (0, 0, 50, 50), handle_touch),
+                (0, (50, 0, 50, 50), handle_touch),
+            ],
+        );
+        let mut controller = DefaultInputController::new();
+
+        touch(&mut controller, &mut tree, 0, 10, 10, PointerEvent::Down);
+        touch(&mut controller, &mut tree, 1, 60, 10, PointerEvent::Down);
+        assert_eq!(controller.touch_capture(0), Some(1));
+        assert_eq!(controller.touch_capture(1), Some(2));
+
+        // the touch points swap places
+        touch(&mut controller, &mut tree, 0, 60, 10, PointerEvent::Drag);
+        touch(&mut controller, &mut tree, 1, 10, 10, PointerEvent::Drag);
+        assert_eq!(received(&log, 1), &["touch down", "touch drag"]);
+        assert_eq!(received(&log, 2), &["touch down", "touch drag"]);
+        take(&log);
+
+        touch(&mut controller, &mut tree, 0, 60, 10, PointerEvent::Up);
+        assert_eq!(received(&log, 1), &["touch up"]);
+        assert!(received(&log, 2).is_empty());
+        assert_eq!(controller.touch_capture(0), None);
+        assert_eq!(controller.touch_capture(1), Some(2));
+
+        touch(&mut controller, &mut tree, 1, 10, 10, PointerEvent::Up);
+        assert_eq!(received(&log, 2), &["touch up"]);
+        assert_eq!(controller.touch_capture(1), None);
+    }
+
+    #[test]
+    fn primary_touch_is_emulated_as_pointer() {
+        fn handle_pointer(_phase: EventPhase, event: InputEvent) -> Response {
+            if let InputEvent::PointerEvent(..) = event {
+                Response::Handle
+            } else {
+                Response::Ignore
+            }
+        }
+
+        let log = Log::default();
+        let mut tree = Tree::new(
+            &log,
+            &[
+                (0, (0, 0, 100, 100), ignore),
+                (0, (0, 0, 100, 100), handle_pointer),
+            ],
+        );
+        let mut controller = DefaultInputController::new();
+
+        touch(&mut controller, &mut tree, 4, 10, 10, PointerEvent::Down);
+        touch(&mut controller, &mut tree, 5, 60, 10, PointerEvent::Down);
+        touch(&mut controller, &mut tree, 5, 70, 10, PointerEvent::Drag);
+        touch(&mut controller, &mut tree, 4, 20, 10, PointerEvent::Drag);
+        assert_eq!(controller.pointer_capture(), Some(1));
+
+        // The primary touch point lifts first. The other touch point doesn't take over.
+        touch(&mut controller, &mut tree, 4, 20, 10, PointerEvent::Up);
+        touch(&mut controller, &mut tree, 5, 80, 10, PointerEvent::Drag);
+        touch(&mut controller, &mut tree, 5, 80, 10, PointerEvent::Up);
+        assert_eq!(controller.pointer_capture(), None);
+
+        assert_eq!(
+            received(&log, 1),
+            &[
+                "touch down",
+                "down",
+                "touch down",
+                "touch drag",
+                "touch drag",
+                "drag",
+                "touch up",
+                "up",
+                "touch drag",
+                "touch up",
+            ]
+        );
+
+        // A new touch point becomes the primary touch point
+        take(&log);
+        touch(&mut controller, &mut tree, 6, 30, 10, PointerEvent::Down);
+        touch(&mut controller, &mut tree, 6, 30, 10, PointerEvent::Up);
+        assert_eq!(received(&log, 1), &["touch down", "down", "touch up", "up"]);
+    }
+
+    #[test]
+    fn handled_primary_touch_cancels_pointer_emulation() {
+        fn handle_touch_drag(_phase: EventPhase, event: InputEvent) -> Response {
+            match event {
+                InputEvent::TouchEvent(_, PointerEvent::Drag) => Response::Handle,
+                InputEvent::PointerEvent(..) => Response::Handle,
+                _ => Response::Ignore,
+            }
+        }
+
+        let log = Log::default();
+        let mut tree = Tree::new(
+            &log,
+            &[
+                (0, (0, 0, 100, 100), ignore),
+                (0, (0, 0, 100, 100), handle_touch_drag),
+            ],
+        );
+        let mut controller = DefaultInputController::new();
+
+        touch(&mut controller, &mut tree, 0, 10, 10, PointerEvent::Down);
+        touch(&mut controller, &mut tree, 0, 20, 10, PointerEvent::Drag);
+        touch(&mut controller, &mut tree, 0, 30, 10, PointerEvent::Drag);
+        touch(&mut controller, &mut tree, 0, 30, 10, PointerEvent::Up);
+
+        assert_eq!(
+            received(&log, 1),
+            &[
+                "touch down",
+                "down",
+                "touch drag",
+                "cancel",
+                "touch drag",
+                "touch up",
+            ]
+        );
+        assert_eq!(controller.pointer_capture(), None);
     }
 }
@@ -1,4 +1,4 @@
-use crate::Position;
+use crate::{geometry::MeasuredSize, Position};
 
 #[derive(Copy, Clone, Debug)]
 pub enum Key {
@@ -131,6 +131,19 @@ pub enum InputEvent {
     KeyEvent(KeyEvent),
     PointerEvent(Position, PointerEvent),
     ScrollEvent(ScrollEvent),
+    /// An event of a single touch point. Touch panels report `Down`, `Drag` and `Up` events.
+    TouchEvent(Touch, PointerEvent),
+}
+
+impl InputEvent {
+    /// Returns the position of pointer and touch events.
+    pub fn position(&self) -> Option<Position> {
+        match self {
+            InputEvent::PointerEvent(position, _) => Some(*position),
+            InputEvent::TouchEvent(touch, _) => Some(touch.position),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -145,6 +158,43 @@ pub enum PointerEvent {
     Down,
     Drag,
     Up,
+    /// The pointer moved over the widget. Sent by the input controller, not by the platform.
+    Enter,
+    /// The pointer moved off the widget. Sent by the input controller, not by the platform.
+    Leave,
+}
+
+/// A touch point of a (multi-)touch panel.
+#[derive(Copy, Clone, Debug)]
+pub struct Touch {
+    /// Identifies the touch point while it is in contact with the panel.
+    pub id: u8,
+    pub position: Position,
+    /// The contact pressure in device specific units, if reported by the panel.
+    pub pressure: Option<u16>,
+    /// The size of the contact area, if reported by the panel.
+    pub contact_size: Option<MeasuredSize>,
+}
+
+impl Touch {
+    pub fn new(id: u8, position: Position) -> Self {
+        Self {
+            id,
+            position,
+            pressure: None,
+            contact_size: None,
+        }
+    }
+
+    pub fn pressure(mut self, pressure: u16) -> Self {
+        self.pressure = Some(pressure);
+        self
+    }
+
+    pub fn contact_size(mut self, contact_size: MeasuredSize) -> Self {
+        self.contact_size = Some(contact_size);
+        self
+    }
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -0,0 +1,259 @@
+//! Gestures built on touch events.
+
+use crate::{
+    geometry::{Position, PositionDelta},
+    input::event::{PointerEvent, Touch},
+};
+
+/// The state of an ongoing pinch gesture.
+#[derive(Copy, Clone, Debug)]
+pub struct Pinch {
+    /// The midpoint between the two touch points.
+    pub center: Position,
+
+    /// The movement of `center` since the previous update.
+    pub center_delta: PositionDelta,
+
+    /// The distance between the touch points when the gesture started.
+    pub start_distance: u32,
+
+    /// The current distance between the touch points.
+    pub distance: u32,
+}
+
+impl Pinch {
+    /// Scales `value` by the ratio of the current and the initial distance of the touch points.
+    pub fn scale(&self, value: u32) -> u32 {
+        let scaled = value as u64 * self.distance as u64 / self.start_distance.max(1) as u64;
+
+        scaled.min(u32::MAX as u64) as u32
+    }
+}
+
+/// Recognizes two-finger pinch gestures.
+///
+/// The recognizer follows the first two touch points it sees. Feed it every touch event that
+/// belongs to the area the gesture is recognized in.
+pub struct PinchRecognizer {
+    touches: [Option<(u8, Position)>; 2],
+    start_distance: u32,
+    last_center: Position,
+}
+
+impl PinchRecognizer {
+    pub const fn new() -> Self {
+        Self {
+            touches: [None, None],
+            start_distance: 0,
+            last_center: Position { x: 0, y: 0 },
+        }
+    }
+
+    /// Returns whether a pinch gesture is in progress.
+    pub fn is_active(&self) -> bool {
+        self.touches.iter().all(Option::is_some)
+    }
+
+    /// Forgets every touch point.
+    pub fn reset(&mut self) {
+        self.touches = [None, None];
+    }
+
+    /// Processes a touch event and returns the state of the gesture, if it is in progress.
+    pub fn update(&mut self, touch: Touch, event: PointerEvent) -> Option<Pinch> {
+        let existing = self
+            .touches
+            .iter()
+            .position(|slot| matches!(slot, Some((id, _)) if *id == touch.id));
+
+        match event {
+            PointerEvent::Down | PointerEvent::Drag => {
+                let slot = existing.or_else(|| {
+                    // Only a new touch point can start the gesture
+                    if let PointerEvent::Down = event {
+                        self.touches.iter().position(Option::is_none)
+                    } else {
+                        None
+                    }
+                })?;
+
+                let was_active = self.is_active();
+                self.touches[slot] = Some((touch.id, touch.position));
+
+                if !self.is_active() {
+                    return None;
+                }
+
+                if !was_active {
+                    self.start_distance = self.distance();
+                    self.last_center = self.center();
+                }
+            }
+
+            PointerEvent::Up => {
+                if let Some(slot) = existing {
+                    self.touches[slot] = None;
+                }
+                return None;
+            }
+
+            _ => return None,
+        }
+
+        let center = self.center();
+        let pinch = Pinch {
+            center,
+            center_delta: center - self.last_center,
+            start_distance: self.start_distance,
+            distance: self.distance(),
+        };
+        self.last_center = center;
+
+        Some(pinch)
+    }
+
+    fn positions(&self) -> (Position, Position) {
+        match self.touches {
+            [Some((_, a)), Some((_, b))] => (a, b),
+            _ => unreachable!(),
+        }
+    }
+
+    fn center(&self) -> Position {
+        let (a, b) = self.positions();
+
+        Position {
+            x: ((a.x as i64 + b.x as i64) / 2) as i32,
+            y: ((a.y as i64 + b.y as i64) / 2) as i32,
+        }
+    }
+
+    fn distance(&self) -> u32 {
+        let (a, b) = self.positions();
+        let dx = (a.x as i64 - b.x as i64).unsigned_abs();
+        let dy = (a.y as i64 - b.y as i64).unsigned_abs();
+
+        let distance = isqrt(dx.saturating_mul(dx).saturating_add(dy.saturating_mul(dy)));
+
+        distance.min(u32::MAX as u64) as u32
+    }
+}
+
+impl Default for PinchRecognizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Integer square root, rounded down.
+fn isqrt(value: u64) -> u64 {
+    if value < 2 {
+        return value;
+    }
+
+    // Newton's method, starting from an overestimate
+    let mut x = value;
+    let mut y = x / 2 + x % 2;
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+
+    x
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        geometry::Position,
+        input::{
+            event::{PointerEvent, Touch},
+            gesture::{isqrt, PinchRecognizer},
+        },
+    };
+
+    fn touch(id: u8, x: i32, y: i32) -> Touch {
+        Touch::new(id, Position { x, y })
+    }
+
+    #[test]
+    fn integer_square_root() {
+        assert_eq!(isqrt(0), 0);
+        assert_eq!(isqrt(1), 1);
+        assert_eq!(isqrt(2), 1);
+        assert_eq!(isqrt(3), 1);
+        assert_eq!(isqrt(4), 2);
+        assert_eq!(isqrt(15), 3);
+        assert_eq!(isqrt(16), 4);
+        assert_eq!(isqrt(17), 4);
+        assert_eq!(isqrt(1_000_000), 1000);
+        assert_eq!(isqrt(u64::MAX), u32::MAX as u64);
+    }
+
+    #[test]
+    fn pinch_sequence() {
+        let mut recognizer = PinchRecognizer::new();
+
+        assert!(recognizer
+            .update(touch(3, 0, 10), PointerEvent::Down)
+            .is_none());
+        assert!(!recognizer.is_active());
+
+        let pinch = recognizer
+            .update(touch(7, 40, 10), PointerEvent::Down)
+            .unwrap();
+        assert!(recognizer.is_active());
+        assert_eq!(pinch.start_distance, 40);
+        assert_eq!(pinch.distance, 40);
+        assert_eq!(pinch.center, Position { x: 20, y: 10 });
+        assert_eq!((pinch.center_delta.x, pinch.center_delta.y), (0, 0));
+        assert_eq!(pinch.scale(100), 100);
+
+        let pinch = recognizer
+            .update(touch(7, 80, 10), PointerEvent::Drag)
+            .unwrap();
+        assert_eq!(pinch.start_distance, 40);
+        assert_eq!(pinch.distance, 80);
+        assert_eq!(pinch.center, Position { x: 40, y: 10 });
+        assert_eq!((pinch.center_delta.x, pinch.center_delta.y), (20, 0));
+        assert_eq!(pinch.scale(100), 200);
+
+        // A third touch point is ignored
+        assert!(recognizer
+            .update(touch(9, 20, 20), PointerEvent::Down)
+            .is_none());
+        let pinch = recognizer
+            .update(touch(3, 20, 10), PointerEvent::Drag)
+            .unwrap();
+        assert_eq!(pinch.distance, 60);
+        assert_eq!((pinch.center_delta.x, pinch.center_delta.y), (10, 0));
+
+        // Lifting either finger ends the gesture
+        assert!(recognizer
+            .update(touch(7, 80, 10), PointerEvent::Up)
+            .is_none());
+        assert!(!recognizer.is_active());
+        assert!(recognizer
+            .update(touch(3, 10, 10), PointerEvent::Drag)
+            .is_none());
+    }
+
+    #[test]
+    fn distant_touch_points_dont_overflow() {
+        let mut recognizer = PinchRecognizer::new();
+
+        recognizer.update(touch(0, i32::MIN, 0), PointerEvent::Down);
+        let pinch = recognizer
+            .update(touch(1, i32::MAX, 0), PointerEvent::Down)
+            .unwrap();
+
+        assert_eq!(pinch.distance, u32::MAX);
+        assert_eq!(pinch.center, Position { x: 0, y: 0 });
+
+        let pinch = recognizer
+            .update(touch(1, 40_000, 30_000), PointerEvent::Drag)
+            .unwrap();
+        assert_eq!(pinch.start_distance, u32::MAX);
+        assert!(pinch.distance > 2_000_000_000);
+    }
+}
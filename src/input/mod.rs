@@ -1,2 +1,6 @@
 pub mod controller;
 pub mod event;
+pub mod gesture;
+#[cfg(feature = "std")]
+pub mod recording;
+pub mod transform;
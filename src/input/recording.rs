@@ -0,0 +1,491 @@
+//! Recording and replaying input.
+//!
+//! Requires the `std` feature.
+//!
+//! A [`RecordingController`] wraps the input controller of a [`Window`] and writes every input event
+//! it receives, and every [`Window::update`] call, to a text file. A [`Player`] reads the recording
+//! back and feeds the same sequence of events and updates to a window, so widgets that animate on
+//! update (like a flinging `Scroll`) behave the same way they did while recording.
+//!
+//! Widgets that measure time should use a [`ReplayClock`] during replay. The player sets the clock
+//! to the recorded time before each record.
+//!
+//! ```rust,ignore
+//! // Recording
+//! let file = File::create("session.rec")?;
+//! let mut window = Window::new(canvas, gui)
+//!     .with_input_controller(RecordingController::new(DefaultInputController::new(), &clock, file));
+//!
+//! // Replay
+//! let clock = ReplayClock::new();
+//! let mut window = Window::new(canvas, gui);
+//! let mut player = Player::new(BufReader::new(File::open("session.rec")?));
+//! while player.replay_frame(&mut window, &clock)? {
+//!     window.draw().unwrap();
+//! }
+//! ```
+//!
+//! # Format
+//!
+//! Each line contains one record: the time in milliseconds, the record type and its fields,
+//! separated by spaces.
+//!
+//! ```text
+//! 1000 U
+//! 1012 P 10 20 Down
+//! 1020 T 1 10 21 Drag p=300 s=4x5
+//! 1030 K Down A Shift 0
+//! 1031 K Up A Shift
+//! 1040 S V -3
+//! 1050 C
+//! ```
+//!
+//! [`Window`]: crate::Window
+//! [`Window::update`]: crate::Window::update
+
+use core::cell::Cell;
+use std::{
+    format,
+    io::{self, BufRead, Write},
+    string::String,
+    vec::Vec,
+};
+
+use crate::{
+    geometry::{MeasuredSize, Position},
+    input::{
+        controller::InputController,
+        event::{InputEvent, Key, KeyEvent, Modifier, PointerEvent, ScrollEvent, Touch},
+    },
+    time::{Instant, TimeSource},
+    widgets::Widget,
+    Canvas, WidgetRenderer, Window,
+};
+
+const KEYS: [Key; 48] = [
+    Key::A,
+    Key::B,
+    Key::C,
+    Key::D,
+    Key::E,
+    Key::F,
+    Key::G,
+    Key::H,
+    Key::I,
+    Key::J,
+    Key::K,
+    Key::L,
+    Key::M,
+    Key::N,
+    Key::O,
+    Key::P,
+    Key::Q,
+    Key::R,
+    Key::S,
+    Key::T,
+    Key::U,
+    Key::V,
+    Key::W,
+    Key::X,
+    Key::Y,
+    Key::Z,
+    Key::N0,
+    Key::N1,
+    Key::N2,
+    Key::N3,
+    Key::N4,
+    Key::N5,
+    Key::N6,
+    Key::N7,
+    Key::N8,
+    Key::N9,
+    Key::Enter,
+    Key::Backspace,
+    Key::Space,
+    Key::ArrowUp,
+    Key::ArrowRight,
+    Key::ArrowDown,
+    Key::ArrowLeft,
+    Key::Del,
+    Key::Tab,
+    Key::Comma,
+    Key::Period,
+    Key::Minus,
+];
+
+const MODIFIERS: [Modifier; 7] = [
+    Modifier::None,
+    Modifier::Shift,
+    Modifier::Alt,
+    Modifier::AltShift,
+    Modifier::Ctrl,
+    Modifier::CtrlAlt,
+    Modifier::CtrlShift,
+];
+
+const POINTER_EVENTS: [PointerEvent; 6] = [
+    PointerEvent::Hover,
+    PointerEvent::Down,
+    PointerEvent::Drag,
+    PointerEvent::Up,
+    PointerEvent::Enter,
+    PointerEvent::Leave,
+];
+
+/// A single entry of a recording.
+#[derive(Copy, Clone, Debug)]
+pub enum Record {
+    /// The window was updated.
+    Update(Instant),
+
+    /// The window received an input event.
+    Event(Instant, InputEvent),
+}
+
+impl Record {
+    pub fn time(&self) -> Instant {
+        match self {
+            Record::Update(time) | Record::Event(time, _) => *time,
+        }
+    }
+
+    /// Writes the record as a line of text.
+    pub fn write(&self, mut out: impl Write) -> io::Result<()> {
+        write!(out, "{}", self.time().as_millis())?;
+
+        let event = match self {
+            Record::Update(_) => return writeln!(out, " U"),
+            Record::Event(_, event) => *event,
+        };
+
+        match event {
+            InputEvent::Cancel => write!(out, " C")?,
+            InputEvent::PointerEvent(position, pe) => {
+                write!(out, " P {} {} {:?}", position.x, position.y, pe)?
+            }
+            InputEvent::TouchEvent(touch, pe) => {
+                write!(
+                    out,
+                    " T {} {} {} {:?}",
+                    touch.id, touch.position.x, touch.position.y, pe
+                )?;
+                if let Some(pressure) = touch.pressure {
+                    write!(out, " p={}", pressure)?;
+                }
+                if let Some(size) = touch.contact_size {
+                    write!(out, " s={}x{}", size.width, size.height)?;
+                }
+            }
+            InputEvent::KeyEvent(KeyEvent::KeyDown(key, modifier, repeat)) => {
+                write!(out, " K Down {:?} {:?} {}", key, modifier, repeat)?
+            }
+            InputEvent::KeyEvent(KeyEvent::KeyUp(key, modifier)) => {
+                write!(out, " K Up {:?} {:?}", key, modifier)?
+            }
+            InputEvent::ScrollEvent(ScrollEvent::HorizontalScroll(delta)) => {
+                write!(out, " S H {}", delta)?
+            }
+            InputEvent::ScrollEvent(ScrollEvent::VerticalScroll(delta)) => {
+                write!(out, " S V {}", delta)?
+            }
+        }
+
+        writeln!(out)
+    }
+
+    /// Parses a line of text written by [`Record::write`].
+    pub fn parse(line: &str) -> io::Result<Record> {
+        Self::parse_with(line, &Names::new())
+    }
+
+    fn parse_with(line: &str, names: &Names) -> io::Result<Record> {
+        let fields = line.split_whitespace().collect::<Vec<_>>();
+        let invalid = || {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Invalid record: {:?}", line),
+            )
+        };
+
+        let number = |idx: usize| -> io::Result<i32> {
+            fields
+                .get(idx)
+                .and_then(|field| field.parse().ok())
+                .ok_or_else(invalid)
+        };
+        let count = |idx: usize| -> io::Result<u32> {
+            fields
+                .get(idx)
+                .and_then(|field| field.parse().ok())
+                .ok_or_else(invalid)
+        };
+        let named = |idx: usize, names: &[String]| -> io::Result<usize> {
+            let field = fields.get(idx).ok_or_else(invalid)?;
+            names
+                .iter()
+                .position(|name| name == field)
+                .ok_or_else(invalid)
+        };
+
+        let time = Instant::from_millis(
+            fields
+                .first()
+                .and_then(|field| field.parse().ok())
+                .ok_or_else(invalid)?,
+        );
+
+        let event = match fields.get(1).copied() {
+            Some("U") => return Ok(Record::Update(time)),
+            Some("C") => InputEvent::Cancel,
+            Some("P") => InputEvent::PointerEvent(
+                Position {
+                    x: number(2)?,
+                    y: number(3)?,
+                },
+                POINTER_EVENTS[named(4, &names.pointer_events)?],
+            ),
+            Some("T") => {
+                let id = fields
+                    .get(2)
+                    .and_then(|field| field.parse().ok())
+                    .ok_or_else(invalid)?;
+                let mut touch = Touch::new(
+                    id,
+                    Position {
+                        x: number(3)?,
+                        y: number(4)?,
+                    },
+                );
+
+                for field in fields.iter().skip(6) {
+                    if let Some(pressure) = field.strip_prefix("p=") {
+                        touch = touch.pressure(pressure.parse().map_err(|_| invalid())?);
+                    } else if let Some(size) = field.strip_prefix("s=") {
+                        let mut parts = size.split('x').map(str::parse);
+                        match (parts.next(), parts.next()) {
+                            (Some(Ok(width)), Some(Ok(height))) => {
+                                touch = touch.contact_size(MeasuredSize { width, height })
+                            }
+                            _ => return Err(invalid()),
+                        }
+                    } else {
+                        return Err(invalid());
+                    }
+                }
+
+                InputEvent::TouchEvent(touch, POINTER_EVENTS[named(5, &names.pointer_events)?])
+            }
+            Some("K") => {
+                let key = KEYS[named(3, &names.keys)?];
+                let modifier = MODIFIERS[named(4, &names.modifiers)?];
+
+                match fields.get(2).copied() {
+                    Some("Down") => {
+                        InputEvent::KeyEvent(KeyEvent::KeyDown(key, modifier, count(5)?))
+                    }
+                    Some("Up") => InputEvent::KeyEvent(KeyEvent::KeyUp(key, modifier)),
+                    _ => return Err(invalid()),
+                }
+            }
+            Some("S") => match fields.get(2).copied() {
+                Some("H") => InputEvent::ScrollEvent(ScrollEvent::HorizontalScroll(number(3)?)),
+                Some("V") => InputEvent::ScrollEvent(ScrollEvent::VerticalScroll(number(3)?)),
+                _ => return Err(invalid()),
+            },
+            _ => return Err(invalid()),
+        };
+
+        Ok(Record::Event(time, event))
+    }
+}
+
+/// The names of keys, modifiers and pointer events as they appear in a recording.
+struct Names {
+    keys: Vec<String>,
+    modifiers: Vec<String>,
+    pointer_events: Vec<String>,
+}
+
+impl Names {
+    fn new() -> Self {
+        fn names<T: core::fmt::Debug>(values: &[T]) -> Vec<String> {
+            values.iter().map(|value| format!("{:?}", value)).collect()
+        }
+
+        Self {
+            keys: names(&KEYS),
+            modifiers: names(&MODIFIERS),
+            pointer_events: names(&POINTER_EVENTS),
+        }
+    }
+}
+
+/// An input controller adapter that records the events and updates it receives.
+///
+/// Recording stops at the first write error, which is returned by
+/// [`RecordingController::take_error`].
+pub struct RecordingController<I, T, O> {
+    inner: I,
+    time_source: T,
+    output: O,
+    error: Option<io::Error>,
+}
+
+impl<I, T, O> RecordingController<I, T, O>
+where
+    I: InputController,
+    T: TimeSource,
+    O: Write,
+{
+    pub fn new(inner: I, time_source: T, output: O) -> Self {
+        Self {
+            inner,
+            time_source,
+            output,
+            error: None,
+        }
+    }
+
+    pub fn inner(&self) -> &I {
+        &self.inner
+    }
+
+    pub fn inner_mut(&mut self) -> &mut I {
+        &mut self.inner
+    }
+
+    /// Returns the error that stopped the recording, if any.
+    pub fn take_error(&mut self) -> Option<io::Error> {
+        self.error.take()
+    }
+
+    /// Flushes the output and returns it.
+    pub fn finish(mut self) -> io::Result<O> {
+        if let Some(error) = self.error.take() {
+            return Err(error);
+        }
+        self.output.flush()?;
+
+        Ok(self.output)
+    }
+
+    fn record(&mut self, record: Record) {
+        if self.error.is_none() {
+            if let Err(error) = record.write(&mut self.output) {
+                self.error = Some(error);
+            }
+        }
+    }
+}
+
+impl<I, T, O> InputController for RecordingController<I, T, O>
+where
+    I: InputController,
+    T: TimeSource,
+    O: Write,
+{
+    fn input_event(&mut self, root: &mut impl Widget, event: InputEvent) {
+        self.record(Record::Event(self.time_source.now(), event));
+        self.inner.input_event(root, event);
+    }
+
+    fn update(&mut self, root: &mut impl Widget) {
+        self.record(Record::Update(self.time_source.now()));
+        self.inner.update(root);
+    }
+}
+
+/// A clock that shows the time of the record being replayed.
+pub struct ReplayClock {
+    now: Cell<Instant>,
+}
+
+impl ReplayClock {
+    pub fn new() -> Self {
+        Self {
+            now: Cell::new(Instant::from_millis(0)),
+        }
+    }
+
+    pub fn set(&self, now: Instant) {
+        self.now.set(now);
+    }
+}
+
+impl Default for ReplayClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TimeSource for ReplayClock {
+    fn now(&self) -> Instant {
+        self.now.get()
+    }
+}
+
+/// Replays a recording.
+pub struct Player<R> {
+    input: R,
+    line: String,
+    names: Names,
+}
+
+impl<R> Player<R>
+where
+    R: BufRead,
+{
+    pub fn new(input: R) -> Self {
+        Self {
+            input,
+            line: String::new(),
+            names: Names::new(),
+        }
+    }
+
+    /// Reads the next record, or returns `None` at the end of the recording.
+    pub fn next_record(&mut self) -> io::Result<Option<Record>> {
+        loop {
+            self.line.clear();
+            if self.input.read_line(&mut self.line)? == 0 {
+                return Ok(None);
+            }
+
+            if !self.line.trim().is_empty() {
+                return Record::parse_with(&self.line, &self.names).map(Some);
+            }
+        }
+    }
+
+    /// Replays records up to and including the next update.
+    ///
+    /// Input events are sent to the window the same way they were received while recording. An
+    /// update record updates, measures and arranges the window, like the main loop of an
+    /// application does before drawing. Returns `false` if the recording has ended.
+    pub fn replay_frame<C, W, I>(
+        &mut self,
+        window: &mut Window<C, W, I>,
+        clock: &ReplayClock,
+    ) -> io::Result<bool>
+    where
+        C: Canvas,
+        W: Widget + WidgetRenderer<C>,
+        I: InputController,
+    {
+        while let Some(record) = self.next_record()? {
+            clock.set(record.time());
+
+            match record {
+                Record::Event(_, event) => window.input_event(event),
+                Record::Update(_) => {
+                    window.update();
+                    window.measure();
+                    window.arrange();
+                    return Ok(true);
+                }
+            }
+        }
+
+        Ok(false)
+    }
+}
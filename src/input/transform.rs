@@ -0,0 +1,300 @@
+//! Coordinate transformation of pointer input.
+//!
+//! Touch panels report positions in their own coordinate system. Resistive panels, for example,
+//! report raw ADC readings. [`InputTransform`] sits in front of an [`InputController`] and maps
+//! every position to display pixels, using an [`AffineTransform`] (usually obtained by calibrating
+//! the panel) and an [`Orientation`].
+//!
+//! A calibration that was recorded on the rotated display (for example, by the
+//! [`CalibrationScreen`] widget) already contains the rotation, so it should be used without an
+//! additional `Orientation`.
+//!
+//! [`CalibrationScreen`]: crate::widgets::calibration::CalibrationScreen
+
+use crate::{
+    geometry::{orientation::Orientation, MeasuredSize, Position},
+    input::{controller::InputController, event::InputEvent},
+    widgets::Widget,
+};
+
+/// Number of fractional bits of the transform's coefficients.
+const FRACTION_BITS: u32 = 16;
+
+/// An affine transformation with fixed-point coefficients.
+///
+/// Maps `(x, y)` to `(a * x + b * y + c, d * x + e * y + f)`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct AffineTransform {
+    coefficients: [i32; 6],
+}
+
+impl AffineTransform {
+    /// The transformation that doesn't change positions.
+    pub const fn identity() -> Self {
+        let one = 1 << FRACTION_BITS;
+
+        Self {
+            coefficients: [one, 0, 0, 0, one, 0],
+        }
+    }
+
+    /// Creates a transformation from `[a, b, c, d, e, f]` coefficients in 16.16 fixed-point
+    /// format, e.g. ones that were previously returned by [`AffineTransform::coefficients`].
+    pub const fn from_coefficients(coefficients: [i32; 6]) -> Self {
+        Self { coefficients }
+    }
+
+    /// Returns the `[a, b, c, d, e, f]` coefficients in 16.16 fixed-point format.
+    pub const fn coefficients(&self) -> [i32; 6] {
+        self.coefficients
+    }
+
+    /// Computes the transformation that maps the first position of each pair to the second one.
+    ///
+    /// Three pairs determine the transformation exactly. With more pairs (e.g. in a 5-point
+    /// calibration), the result is the least-squares fit, which averages out measurement noise.
+    /// Returns `None` if there are fewer than three pairs, the positions are collinear, or the
+    /// coordinates are too large to compute the transformation without overflowing (roughly beyond
+    /// a million).
+    pub fn from_points(points: &[(Position, Position)]) -> Option<Self> {
+        if points.len() < 3 {
+            return None;
+        }
+
+        // Normal equations of the least-squares problem
+        let mut m = [[0i128; 3]; 3];
+        let mut rhs_x = [0i128; 3];
+        let mut rhs_y = [0i128; 3];
+        for &(from, to) in points {
+            let row = [from.x as i128, from.y as i128, 1];
+            for i in 0..3 {
+                for j in 0..3 {
+                    m[i][j] = m[i][j].checked_add(row[i] * row[j])?;
+                }
+                rhs_x[i] = rhs_x[i].checked_add(row[i] * to.x as i128)?;
+                rhs_y[i] = rhs_y[i].checked_add(row[i] * to.y as i128)?;
+            }
+        }
+
+        let det = det3(m)?;
+        if det == 0 {
+            return None;
+        }
+
+        let mut coefficients = [0; 6];
+        for (column, coefficient) in coefficients.iter_mut().enumerate() {
+            let rhs = if column < 3 { rhs_x } else { rhs_y };
+
+            // Cramer's rule
+            let mut replaced = m;
+            for (row, value) in rhs.iter().enumerate() {
+                replaced[row][column % 3] = *value;
+            }
+
+            let numerator = det3(replaced)?.checked_mul(1 << FRACTION_BITS)?;
+            let value = div_round(numerator, det)?;
+            if value < i32::MIN as i128 || value > i32::MAX as i128 {
+                return None;
+            }
+            *coefficient = value as i32;
+        }
+
+        Some(Self { coefficients })
+    }
+
+    /// Transforms `position`.
+    pub fn apply(&self, position: Position) -> Position {
+        let [a, b, c, d, e, f] = self.coefficients;
+        let x = position.x as i64;
+        let y = position.y as i64;
+        let half = 1 << (FRACTION_BITS - 1);
+
+        Position {
+            x: ((a as i64 * x + b as i64 * y + c as i64 + half) >> FRACTION_BITS) as i32,
+            y: ((d as i64 * x + e as i64 * y + f as i64 + half) >> FRACTION_BITS) as i32,
+        }
+    }
+}
+
+impl Default for AffineTransform {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+/// Returns the determinant of `m`, or `None` if it overflows.
+fn det3(m: [[i128; 3]; 3]) -> Option<i128> {
+    // a * d - b * c
+    let minor =
+        |a: i128, b: i128, c: i128, d: i128| a.checked_mul(d)?.checked_sub(b.checked_mul(c)?);
+
+    m[0][0]
+        .checked_mul(minor(m[1][1], m[1][2], m[2][1], m[2][2])?)?
+        .checked_sub(m[0][1].checked_mul(minor(m[1][0], m[1][2], m[2][0], m[2][2])?)?)?
+        .checked_add(m[0][2].checked_mul(minor(m[1][0], m[1][1], m[2][0], m[2][1])?)?)
+}
+
+fn div_round(numerator: i128, denominator: i128) -> Option<i128> {
+    let (numerator, denominator) = if denominator < 0 {
+        (numerator.checked_neg()?, denominator.checked_neg()?)
+    } else {
+        (numerator, denominator)
+    };
+
+    Some(
+        numerator
+            .checked_add(denominator / 2)?
+            .div_euclid(denominator),
+    )
+}
+
+/// An input controller adapter that transforms pointer and touch positions before passing the
+/// events to the wrapped controller.
+pub struct InputTransform<I> {
+    inner: I,
+    calibration: AffineTransform,
+    orientation: Orientation,
+    native_size: MeasuredSize,
+}
+
+impl<I> InputTransform<I>
+where
+    I: InputController,
+{
+    pub fn new(inner: I) -> Self {
+        Self {
+            inner,
+            calibration: AffineTransform::identity(),
+            orientation: Orientation::default(),
+            native_size: MeasuredSize {
+                width: 0,
+                height: 0,
+            },
+        }
+    }
+
+    /// Sets the transformation from panel coordinates to native display pixels.
+    pub fn calibration(mut self, calibration: AffineTransform) -> Self {
+        self.set_calibration(calibration);
+        self
+    }
+
+    pub fn set_calibration(&mut self, calibration: AffineTransform) {
+        self.calibration = calibration;
+    }
+
+    /// Sets the orientation of a display with the native size `native_size`.
+    pub fn orientation(mut self, orientation: Orientation, native_size: MeasuredSize) -> Self {
+        self.set_orientation(orientation, native_size);
+        self
+    }
+
+    pub fn set_orientation(&mut self, orientation: Orientation, native_size: MeasuredSize) {
+        self.orientation = orientation;
+        self.native_size = native_size;
+    }
+
+    pub fn inner(&self) -> &I {
+        &self.inner
+    }
+
+    pub fn inner_mut(&mut self) -> &mut I {
+        &mut self.inner
+    }
+
+    /// Maps a position reported by the panel to display coordinates.
+    pub fn transform(&self, position: Position) -> Position {
+        let native = self.calibration.apply(position);
+
+        self.orientation.transform(native, self.native_size)
+    }
+}
+
+impl<I> InputController for InputTransform<I>
+where
+    I: InputController,
+{
+    fn input_event(&mut self, root: &mut impl Widget, event: InputEvent) {
+        let event = match event {
+            InputEvent::PointerEvent(position, pe) => {
+                InputEvent::PointerEvent(self.transform(position), pe)
+            }
+            InputEvent::TouchEvent(mut touch, pe) => {
+                touch.position = self.transform(touch.position);
+                InputEvent::TouchEvent(touch, pe)
+            }
+            _ => event,
+        };
+
+        self.inner.input_event(root, event);
+    }
+
+    fn update(&mut self, root: &mut impl Widget) {
+        self.inner.update(root);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{geometry::Position, input::transform::AffineTransform};
+
+    fn pos(x: i32, y: i32) -> Position {
+        Position { x, y }
+    }
+
+    #[test]
+    fn three_point_calibration() {
+        // Raw readings of a panel with swapped axes and ~12 ADC counts per pixel
+        let raw = |p: Position| pos(200 + 12 * p.y, 3900 - 12 * p.x);
+
+        let targets = [pos(16, 16), pos(112, 32), pos(64, 56)];
+        let mut points = [(pos(0, 0), pos(0, 0)); 3];
+        for (point, &target) in points.iter_mut().zip(targets.iter()) {
+            *point = (raw(target), target);
+        }
+
+        let transform = AffineTransform::from_points(&points).unwrap();
+
+        assert_eq!(transform.apply(raw(pos(0, 0))), pos(0, 0));
+        assert_eq!(transform.apply(raw(pos(127, 63))), pos(127, 63));
+        assert_eq!(transform.apply(raw(pos(40, 10))), pos(40, 10));
+    }
+
+    #[test]
+    fn five_point_calibration_averages_noise() {
+        let points = [
+            (pos(101, 99), pos(10, 10)),
+            (pos(899, 101), pos(90, 10)),
+            (pos(901, 899), pos(90, 90)),
+            (pos(99, 901), pos(10, 90)),
+            (pos(500, 500), pos(50, 50)),
+        ];
+
+        let transform = AffineTransform::from_points(&points).unwrap();
+
+        assert_eq!(transform.apply(pos(100, 100)), pos(10, 10));
+        assert_eq!(transform.apply(pos(500, 300)), pos(50, 30));
+    }
+
+    #[test]
+    fn degenerate_calibration() {
+        let points = [
+            (pos(0, 0), pos(0, 0)),
+            (pos(10, 10), pos(10, 10)),
+            (pos(20, 20), pos(20, 20)),
+        ];
+
+        assert_eq!(AffineTransform::from_points(&points), None);
+    }
+
+    #[test]
+    fn huge_coordinates_do_not_overflow() {
+        let points = [
+            (pos(0, 0), pos(0, 0)),
+            (pos(i32::MAX, 0), pos(100, 0)),
+            (pos(0, i32::MAX), pos(0, 100)),
+        ];
+
+        assert_eq!(AffineTransform::from_points(&points), None);
+    }
+}
@@ -35,6 +35,12 @@
 //! States always propagate from the outside in, from a wrapper widget to the wrapped one.
 //! Usually, when a widget defines it's own state, it will not propagate the parent's state further.
 //!
+//! Recording input
+//! ---------------
+//!
+//! With the `std` feature, the input a window receives can be recorded to a file and replayed
+//! later, e.g. to reproduce a bug report or to write a regression test. See [`input::recording`].
+//!
 //! Supported platforms
 //! -------------------
 //!
@@ -45,14 +51,19 @@
 
 #![no_std]
 
+#[cfg(feature = "std")]
+extern crate std;
+
 pub mod data;
 pub mod geometry;
+mod gui;
 pub mod input;
 pub mod state;
+pub mod time;
 pub mod widgets;
 
 use crate::{
-    geometry::{measurement::MeasureSpec, MeasuredSize, Position},
+    geometry::{measurement::MeasureSpec, BoundingBox, MeasuredSize, Position},
     input::{
         controller::{DefaultInputController, InputController},
         event::InputEvent,
@@ -76,6 +87,29 @@ pub trait Canvas {
     type Error;
 
     fn size(&self) -> MeasuredSize;
+
+    /// Restricts drawing to the intersection of `bounds` and the current clip area, until the
+    /// matching [`Canvas::pop_clip`] call.
+    ///
+    /// The default implementation does nothing, so canvases that don't support clipping draw
+    /// everything unclipped.
+    fn push_clip(&mut self, _bounds: BoundingBox) {}
+
+    /// Restores the clip area that was active before the last [`Canvas::push_clip`] call.
+    fn pop_clip(&mut self) {}
+
+    /// Draws `widget`, clipped to `bounds`.
+    fn draw_clipped<W>(&mut self, bounds: BoundingBox, widget: &mut W) -> Result<(), Self::Error>
+    where
+        Self: Sized,
+        W: WidgetRenderer<Self> + ?Sized,
+    {
+        self.push_clip(bounds);
+        let result = widget.draw(self);
+        self.pop_clip();
+
+        result
+    }
 }
 
 pub struct Window<C, W, I>
@@ -87,6 +121,7 @@ where
     pub canvas: C,
     pub root: W,
     pub input_controller: I,
+    viewport: Option<BoundingBox>,
 }
 
 impl<C, W> Window<C, W, DefaultInputController>
@@ -100,6 +135,7 @@ where
             canvas,
             root,
             input_controller: DefaultInputController::new(),
+            viewport: None,
         }
     }
 }
@@ -118,28 +154,126 @@ where
             canvas: self.canvas,
             root: self.root,
             input_controller,
+            viewport: self.viewport,
         }
     }
 
+    /// Restricts the window to the `viewport` area of the canvas.
+    ///
+    /// The widgets are laid out in, and drawn to the viewport. Input positions are still given in
+    /// canvas coordinates.
+    pub fn with_viewport(mut self, viewport: BoundingBox) -> Self {
+        self.set_viewport(viewport);
+        self
+    }
+
+    /// Restricts the window to the `viewport` area of the canvas. Call [`Window::measure`] and
+    /// [`Window::arrange`] afterwards to lay out the widgets for the new viewport.
+    pub fn set_viewport(&mut self, viewport: BoundingBox) {
+        self.viewport = Some(viewport);
+    }
+
+    /// Returns the area of the canvas the window occupies.
+    pub fn viewport(&self) -> BoundingBox {
+        self.viewport.unwrap_or(BoundingBox {
+            position: Position { x: 0, y: 0 },
+            size: self.canvas.size(),
+        })
+    }
+
     pub fn update(&mut self) {
+        self.input_controller.update(&mut self.root);
         self.root.update();
         self.root.reset_changed();
     }
 
     pub fn measure(&mut self) {
         self.root
-            .measure(MeasureSpec::from_measured_at_most(self.canvas.size()));
+            .measure(MeasureSpec::from_measured_at_most(self.viewport().size));
     }
 
     pub fn arrange(&mut self) {
-        self.root.arrange(Position { x: 0, y: 0 });
+        self.root.arrange(self.viewport().position);
     }
 
     pub fn draw(&mut self) -> Result<(), C::Error> {
-        self.root.draw(&mut self.canvas)
+        match self.viewport {
+            Some(viewport) => self.canvas.draw_clipped(viewport, &mut self.root),
+            None => self.root.draw(&mut self.canvas),
+        }
     }
 
     pub fn input_event(&mut self, event: InputEvent) {
         self.input_controller.input_event(&mut self.root, event);
     }
+
+    /// Returns the widget at `index`. Index 0 is the root widget, and the rest of the widgets are
+    /// numbered in the order of [`Widget::get_child`].
+    pub fn widget(&self, index: usize) -> Option<&dyn Widget> {
+        if index == 0 {
+            Some(&self.root)
+        } else if index <= self.root.children() {
+            Some(self.root.get_child(index - 1))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the index of the first widget that matches `predicate`.
+    pub fn find_widget(&self, predicate: impl Fn(&dyn Widget) -> bool) -> Option<usize> {
+        (0..=self.root.children()).find(|&index| match self.widget(index) {
+            Some(widget) => predicate(widget),
+            None => false,
+        })
+    }
+
+    /// Returns the index of the first widget that displays `text`.
+    pub fn find_text(&self, text: &str) -> Option<usize> {
+        self.find_widget(|widget| widget.text() == Some(text))
+    }
+
+    /// Returns the index of the next selectable widget after `index`, or the first one if `index`
+    /// is `None`. The search wraps around at the end of the widget tree.
+    ///
+    /// Widgets are visited in the order of their indices, so the layers of a `FrameLayout` are
+    /// visited from bottom to top. Widgets that can't be reached by the pointer, e.g. because an
+    /// opaque layer covers them, are skipped.
+    pub fn next_selectable(&self, index: Option<usize>) -> Option<usize> {
+        let count = self.root.children() + 1;
+        let start = index.map_or(0, |index| index + 1);
+
+        (0..count)
+            .map(|i| (start + i) % count)
+            .find(|&index| self.is_focusable(index))
+    }
+
+    /// Returns the index of the previous selectable widget before `index`, or the last one if
+    /// `index` is `None`. See [`Window::next_selectable`].
+    pub fn previous_selectable(&self, index: Option<usize>) -> Option<usize> {
+        let count = self.root.children() + 1;
+        let start = index.unwrap_or(0) + count;
+
+        (1..=count)
+            .map(|i| (start - i) % count)
+            .find(|&index| self.is_focusable(index))
+    }
+
+    fn is_focusable(&self, index: usize) -> bool {
+        let widget = match self.widget(index) {
+            Some(widget) if widget.is_selectable() => widget,
+            _ => return false,
+        };
+
+        let bounds = widget.bounding_box();
+        let center = Position {
+            x: bounds.position.x + bounds.size.width as i32 / 2,
+            y: bounds.position.y + bounds.size.height as i32 / 2,
+        };
+
+        // The widget, or one of its children, must be the topmost widget at its center
+        match self.root.hit_test(center) {
+            Some(hit) => hit >= index && hit <= index + widget.children(),
+            None => false,
+        }
+    }
 }
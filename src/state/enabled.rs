@@ -0,0 +1,9 @@
+//! Enabled state, inherited from an `Enabled` widget.
+
+crate::state_group! {
+    shared
+    [EnabledStateGroup: 0x4000_0000] = {
+        Enabled = 0,
+        Disabled = 0x4000_0000,
+    }
+}
@@ -1,6 +1,32 @@
 //! Visual state container.
+//!
+//! A [`WidgetState`] is a set of bits, divided into state groups. Each group holds exactly one of
+//! its states at a time, e.g. a button is either idle, hovered or pressed.
+//!
+//! Groups are declared using [`state_group!`](crate::state_group). The lower half of the bits
+//! belongs to the widget that owns the state, so different widgets may reuse the same bits for
+//! unrelated groups. The upper half is reserved for shared states, like selection, which are
+//! passed down the widget tree and mean the same thing for every widget. Only this crate declares
+//! shared groups. Overlapping groups are rejected at compile time.
+//!
+//! ```rust,ignore
+//! state_group! {
+//!     [SpinnerStateGroup: 0x0000_0003] = {
+//!         Stopped = 0,
+//!         Spinning = 0x0000_0001,
+//!         Finished = 0x0000_0002,
+//!     }
+//! }
+//! ```
+pub mod enabled;
 pub mod selection;
 
+use enabled::EnabledStateGroup;
+use selection::SelectionStateGroup;
+
+/// The bits of [`WidgetState`] reserved for shared state groups.
+pub const SHARED_STATES: u32 = 0xFFFF_0000;
+
 pub trait StateGroup {
     const MASK: u32;
 }
@@ -11,9 +37,105 @@ pub trait State {
     const VALUE: u32;
 }
 
+/// The masks of the shared state groups. A shared group that is missing from this list fails to
+/// compile.
+const SHARED_GROUPS: &[u32] = &[SelectionStateGroup::MASK, EnabledStateGroup::MASK];
+
+// Every widget may receive the shared states from its parent, so they must not overlap.
+crate::const_assert!(masks_are_disjoint(SHARED_GROUPS));
+crate::const_assert!(union(SHARED_GROUPS) & !SHARED_STATES == 0);
+
+/// The bits of the registered shared state groups.
+///
+/// Only visible inside this crate, so that `state_group!` rejects shared groups declared elsewhere.
+pub(crate) const REGISTERED_SHARED_STATES: u32 = union(SHARED_GROUPS);
+
+/// Fails to compile if the condition is false.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! const_assert {
+    ($condition:expr) => {
+        const _: [(); 0] = [(); !$condition as usize];
+    };
+}
+
+/// Returns whether no two of the `masks` have a bit in common.
+#[doc(hidden)]
+pub const fn masks_are_disjoint(masks: &[u32]) -> bool {
+    let mut seen = 0;
+    let mut i = 0;
+    while i < masks.len() {
+        if seen & masks[i] != 0 {
+            return false;
+        }
+        seen |= masks[i];
+        i += 1;
+    }
+
+    true
+}
+
+const fn union(masks: &[u32]) -> u32 {
+    let mut bits = 0;
+    let mut i = 0;
+    while i < masks.len() {
+        bits |= masks[i];
+        i += 1;
+    }
+
+    bits
+}
+
+/// Returns whether every value is different and only uses the bits of `mask`.
+#[doc(hidden)]
+pub const fn values_fit_mask(mask: u32, values: &[u32]) -> bool {
+    let mut i = 0;
+    while i < values.len() {
+        if values[i] & !mask != 0 {
+            return false;
+        }
+
+        let mut j = 0;
+        while j < i {
+            if values[i] == values[j] {
+                return false;
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+
+    true
+}
+
+/// Declares state groups and their states.
+///
+/// Groups declared in the same invocation must not overlap, and must not use the bits reserved
+/// for shared states.
+///
+/// Shared groups are declared by starting the invocation with `shared`. They are only available
+/// inside this crate, and must be registered in the `state` module, which checks that they don't
+/// overlap. Other crates can't declare shared groups:
+///
+/// ```compile_fail
+/// embedded_gui::state_group! {
+///     shared
+///     [MineStateGroup: 0x8000_0000] = {
+///         Off = 0,
+///         On = 0x8000_0000,
+///     }
+/// }
+/// ```
 #[macro_export]
 macro_rules! state_group {
-    ($([$group:ident: $mask:literal] = {
+    (shared $([$group:ident: $mask:literal] = {
+        $($state:ident = $value:literal),+ $(,)?
+    })+) => {
+        $crate::state_group!(@groups $([$group: $mask] = { $($state = $value),+ })+);
+        $crate::const_assert!(($($mask)|+) & !$crate::state::REGISTERED_SHARED_STATES == 0);
+    };
+
+    (@groups $([$group:ident: $mask:literal] = {
         $($state:ident = $value:literal),+ $(,)?
     })+) => {
         $(
@@ -30,7 +152,18 @@ macro_rules! state_group {
                     const VALUE: u32 = $value;
                 }
             )+
+
+            $crate::const_assert!($crate::state::values_fit_mask($mask, &[$($value),+]));
         )+
+
+        $crate::const_assert!($crate::state::masks_are_disjoint(&[$($mask),+]));
+    };
+
+    ($([$group:ident: $mask:literal] = {
+        $($state:ident = $value:literal),+ $(,)?
+    })+) => {
+        $crate::state_group!(@groups $([$group: $mask] = { $($state = $value),+ })+);
+        $crate::const_assert!(($($mask)|+) & $crate::state::SHARED_STATES == 0);
     };
 }
 
@@ -47,4 +180,29 @@ impl WidgetState {
         self.0 = (self.0 & !S::Group::MASK) | S::VALUE;
         self.0 != old
     }
+
+    /// Copies the states of `group` from the `parent` state. Returns whether the state changed.
+    pub fn inherit<G: StateGroup>(&mut self, parent: WidgetState, _group: G) -> bool {
+        let old = self.0;
+        self.0 = (self.0 & !G::MASK) | (parent.0 & G::MASK);
+        self.0 != old
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::state::{masks_are_disjoint, values_fit_mask};
+
+    #[test]
+    fn overlapping_masks_are_rejected() {
+        assert!(masks_are_disjoint(&[0x3, 0x4, 0x8000_0000]));
+        assert!(!masks_are_disjoint(&[0x3, 0x4, 0x6]));
+    }
+
+    #[test]
+    fn values_must_fit_their_group() {
+        assert!(values_fit_mask(0x3, &[0, 1, 2]));
+        assert!(!values_fit_mask(0x3, &[0, 4]));
+        assert!(!values_fit_mask(0x3, &[0, 1, 1]));
+    }
 }
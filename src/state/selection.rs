@@ -1,6 +1,7 @@
 //! Focused state.
 
 crate::state_group! {
+    shared
     [SelectionStateGroup: 0x8000_0000] = {
         Unselected = 0,
         Selected = 0x8000_0000,
@@ -0,0 +1,48 @@
+//! Time measurement.
+//!
+//! `embedded-gui` doesn't assume any particular clock. Widgets that need to measure elapsed time
+//! (for example, a [`Button`] that repeats while held) take a [`TimeSource`] that is provided by the
+//! application.
+//!
+//! [`Button`]: crate::widgets::button::Button
+
+/// A point in time, measured in milliseconds since an arbitrary, platform-defined epoch.
+///
+/// The millisecond counter is allowed to wrap around, so `Instant`s should only be compared using
+/// [`Instant::duration_since`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Instant {
+    millis: u32,
+}
+
+impl Instant {
+    /// Creates an `Instant` from a millisecond counter value.
+    pub const fn from_millis(millis: u32) -> Self {
+        Self { millis }
+    }
+
+    /// Returns the millisecond counter value.
+    pub const fn as_millis(self) -> u32 {
+        self.millis
+    }
+
+    /// Returns the number of milliseconds elapsed since `earlier`.
+    pub fn duration_since(self, earlier: Instant) -> u32 {
+        self.millis.wrapping_sub(earlier.millis)
+    }
+}
+
+/// A monotonic clock.
+pub trait TimeSource {
+    /// Returns the current time.
+    fn now(&self) -> Instant;
+}
+
+impl<T> TimeSource for &T
+where
+    T: TimeSource,
+{
+    fn now(&self) -> Instant {
+        (*self).now()
+    }
+}
@@ -140,6 +140,12 @@ where
         });
     }
 
+    fn baseline(&self) -> Option<u32> {
+        let bw = self.border_properties.get_border_width();
+
+        self.inner.baseline().map(|baseline| baseline + bw)
+    }
+
     fn fire_on_state_changed(&mut self, state: WidgetState) {
         (self.on_state_changed)(self, state);
     }
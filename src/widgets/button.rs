@@ -2,6 +2,16 @@
 //!
 //! The `Button` widget makes the wrapped widget clickable.
 //!
+//! Auto-repeat
+//! -----------
+//!
+//! By default, a button fires `on_clicked` once, when it is released. A button can be configured to
+//! repeatedly fire `on_clicked` while it is being held down by calling `auto_repeat`. Auto-repeat
+//! needs a [`TimeSource`] to measure the initial delay and the interval between repeated clicks.
+//!
+//! A quick tap on an auto-repeating button still fires `on_clicked` once on release. If the button
+//! has already fired repeated clicks, releasing it does not fire an additional one.
+//!
 
 use crate::{
     data::WidgetData,
@@ -9,8 +19,12 @@ use crate::{
         controller::InputContext,
         event::{InputEvent, PointerEvent},
     },
-    state::{State, WidgetState},
+    state::{
+        enabled::{Enabled, EnabledStateGroup},
+        State, WidgetState,
+    },
     state_group,
+    time::{Instant, TimeSource},
     widgets::{
         utils::{decorator::WidgetDecorator, WidgetDataHolder},
         Widget,
@@ -24,7 +38,11 @@ pub struct ButtonFields<W, D> {
     pub parent_index: usize,
     pub inner: W,
     pub on_clicked: fn(&mut D),
+    pub on_pressed: fn(&mut D),
+    pub on_released: fn(&mut D),
     pub state: WidgetState,
+    /// The widget is inactive if this is `false`, or if it is inside a disabled `Enabled` widget.
+    pub active: bool,
 }
 
 impl<W, D> ButtonFields<W, D>
@@ -40,7 +58,19 @@ where
     }
 
     pub fn set_active(&mut self, active: bool) {
-        if active {
+        self.active = active;
+        self.update_active_state();
+    }
+
+    fn inherit_state(&mut self, parent: WidgetState) {
+        if self.state.inherit(parent, EnabledStateGroup) {
+            self.inner.on_state_changed(self.state);
+            self.update_active_state();
+        }
+    }
+
+    fn update_active_state(&mut self) {
+        if self.active && self.state.has_state(Enabled) {
             self.change_state(Button::STATE_ACTIVE);
         } else {
             self.change_state(Button::STATE_INACTIVE);
@@ -48,12 +78,129 @@ where
     }
 }
 
-pub struct Button<W, D = ()>
+/// Decides when a held-down [`Button`] fires repeated clicks.
+///
+/// The button calls [`start`](RepeatController::start) on every press and
+/// [`stop`](RepeatController::stop) when the press ends. While pressed, the button polls the
+/// controller on every update and fires one click for each `true` returned by
+/// [`poll`](RepeatController::poll). On release, the usual click is only fired if
+/// [`has_repeated`](RepeatController::has_repeated) returns `false`, so a press either ends with a
+/// single click or with the repeated ones, never both.
+pub trait RepeatController {
+    /// Called when the button is pressed. Resets the state left over from any previous press.
+    fn start(&mut self);
+
+    /// Called when the button is released or the press is cancelled.
+    fn stop(&mut self);
+
+    /// Returns `true` if a repeated click is due, at most once per call.
+    ///
+    /// Only called between `start` and `stop`. Returning `true` consumes the repeat, so the next
+    /// call returns `false` until the following one is due.
+    fn poll(&mut self) -> bool;
+
+    /// Returns `true` if `poll` has returned `true` since the last `start`.
+    ///
+    /// Called on release, before `stop`.
+    fn has_repeated(&self) -> bool;
+}
+
+/// The button fires a single click on release.
+pub struct NoRepeat;
+
+impl RepeatController for NoRepeat {
+    fn start(&mut self) {}
+    fn stop(&mut self) {}
+
+    fn poll(&mut self) -> bool {
+        false
+    }
+
+    fn has_repeated(&self) -> bool {
+        false
+    }
+}
+
+/// The button fires clicks repeatedly while held down.
+pub struct AutoRepeat<T>
+where
+    T: TimeSource,
+{
+    time_source: T,
+    delay: u32,
+    interval: u32,
+    pressed_at: Option<Instant>,
+    repeats: u32,
+}
+
+impl<T> AutoRepeat<T>
+where
+    T: TimeSource,
+{
+    /// Creates a new auto-repeat controller.
+    ///
+    /// The first repeated click is fired `delay` milliseconds after the button was pressed, then
+    /// every `interval` milliseconds until the button is released.
+    pub fn new(time_source: T, delay: u32, interval: u32) -> Self {
+        Self {
+            time_source,
+            delay,
+            interval: interval.max(1),
+            pressed_at: None,
+            repeats: 0,
+        }
+    }
+
+    pub fn set_delay(&mut self, delay: u32) {
+        self.delay = delay;
+    }
+
+    pub fn set_interval(&mut self, interval: u32) {
+        self.interval = interval.max(1);
+    }
+}
+
+impl<T> RepeatController for AutoRepeat<T>
+where
+    T: TimeSource,
+{
+    fn start(&mut self) {
+        self.pressed_at = Some(self.time_source.now());
+        self.repeats = 0;
+    }
+
+    fn stop(&mut self) {
+        self.pressed_at = None;
+    }
+
+    fn poll(&mut self) -> bool {
+        if let Some(pressed_at) = self.pressed_at {
+            let elapsed = self.time_source.now().duration_since(pressed_at);
+            let next_repeat = self
+                .delay
+                .saturating_add(self.repeats.saturating_mul(self.interval));
+
+            if elapsed >= next_repeat {
+                self.repeats += 1;
+                return true;
+            }
+        }
+
+        false
+    }
+
+    fn has_repeated(&self) -> bool {
+        self.repeats != 0
+    }
+}
+
+pub struct Button<W, D = (), R = NoRepeat>
 where
     D: WidgetData,
 {
     pub fields: ButtonFields<W, D::Data>,
     data_holder: WidgetDataHolder<ButtonFields<W, D::Data>, D>,
+    repeat: R,
 }
 
 state_group! {
@@ -89,13 +236,23 @@ where
                 parent_index: 0,
                 inner,
                 on_clicked: |_| (),
+                on_pressed: |_| (),
+                on_released: |_| (),
                 state,
+                active: true,
             },
             data_holder: WidgetDataHolder::default(),
+            repeat: NoRepeat,
         }
     }
+}
 
-    pub fn bind<D>(self, data: D) -> Button<W, D>
+impl<W, R> Button<W, (), R>
+where
+    W: Widget,
+    R: RepeatController,
+{
+    pub fn bind<D>(self, data: D) -> Button<W, D, R>
     where
         D: WidgetData,
     {
@@ -104,17 +261,48 @@ where
                 parent_index: self.fields.parent_index,
                 inner: self.fields.inner,
                 on_clicked: |_| (),
+                on_pressed: |_| (),
+                on_released: |_| (),
                 state: self.fields.state,
+                active: self.fields.active,
             },
             data_holder: WidgetDataHolder::new(data),
+            repeat: self.repeat,
+        }
+    }
+}
+
+impl<W, D> Button<W, D, NoRepeat>
+where
+    W: Widget,
+    D: WidgetData,
+{
+    /// Repeatedly fire `on_clicked` while the button is held down.
+    ///
+    /// The first repeated click is fired `delay` milliseconds after the button was pressed, then
+    /// every `interval` milliseconds until the button is released.
+    pub fn auto_repeat<T>(
+        self,
+        time_source: T,
+        delay: u32,
+        interval: u32,
+    ) -> Button<W, D, AutoRepeat<T>>
+    where
+        T: TimeSource,
+    {
+        Button {
+            fields: self.fields,
+            data_holder: self.data_holder,
+            repeat: AutoRepeat::new(time_source, delay, interval),
         }
     }
 }
 
-impl<W, D> Button<W, D>
+impl<W, D, R> Button<W, D, R>
 where
     W: Widget,
     D: WidgetData,
+    R: RepeatController,
 {
     pub fn active(mut self, active: bool) -> Self {
         self.set_active(active);
@@ -138,16 +326,57 @@ where
         self
     }
 
+    /// Sets the callback that is fired when the button is pressed down.
+    pub fn on_pressed(mut self, callback: fn(&mut D::Data)) -> Self {
+        self.fields.on_pressed = callback;
+        self
+    }
+
+    /// Sets the callback that is fired when a press ends, either by releasing the button or by
+    /// cancelling the press.
+    pub fn on_released(mut self, callback: fn(&mut D::Data)) -> Self {
+        self.fields.on_released = callback;
+        self
+    }
+
     fn fire_on_clicked(&mut self) {
         let callback = self.fields.on_clicked;
         self.data_holder.data.update(callback);
     }
+
+    fn fire_on_pressed(&mut self) {
+        let callback = self.fields.on_pressed;
+        self.data_holder.data.update(callback);
+    }
+
+    fn fire_on_released(&mut self) {
+        let callback = self.fields.on_released;
+        self.data_holder.data.update(callback);
+    }
+
+    fn press(&mut self) {
+        self.fields.change_state(Button::STATE_PRESSED);
+        self.repeat.start();
+        self.fire_on_pressed();
+    }
+
+    /// Ends the current press, if any, and moves the button into `state`.
+    fn end_press(&mut self, state: impl State) {
+        let was_pressed = self.fields.state.has_state(Button::STATE_PRESSED);
+        self.fields.change_state(state);
+
+        if was_pressed {
+            self.repeat.stop();
+            self.fire_on_released();
+        }
+    }
 }
 
-impl<W, D> WidgetDecorator for Button<W, D>
+impl<W, D, R> WidgetDecorator for Button<W, D, R>
 where
     W: Widget,
     D: WidgetData,
+    R: RepeatController,
 {
     type Widget = W;
 
@@ -165,7 +394,15 @@ where
         self.fields.inner.attach(self_index, self_index + 1);
     }
 
+    fn parent_index(&self) -> usize {
+        self.fields.parent_index
+    }
+
     fn update(&mut self) {
+        if self.fields.state.has_state(Button::STATE_PRESSED) && self.repeat.poll() {
+            self.fire_on_clicked();
+        }
+
         self.data_holder.update(&mut self.fields);
         self.fields.inner.update();
     }
@@ -181,7 +418,7 @@ where
 
         match event {
             InputEvent::Cancel => {
-                self.fields.change_state(Button::STATE_IDLE);
+                self.end_press(Button::STATE_IDLE);
                 None
             }
 
@@ -204,7 +441,7 @@ where
                     Some(0)
                 } else {
                     // Drag outside = cancel
-                    self.fields.change_state(Button::STATE_IDLE);
+                    self.end_press(Button::STATE_IDLE);
                     None
                 }
             }
@@ -221,7 +458,7 @@ where
             InputEvent::PointerEvent(position, PointerEvent::Hover) => {
                 if let Some(idx) = self.fields.inner.test_input(event) {
                     // we give priority to our child
-                    self.fields.change_state(Button::STATE_IDLE);
+                    self.end_press(Button::STATE_IDLE);
                     Some(idx + 1)
                 } else if self.fields.inner.bounding_box().contains(position) {
                     self.fields.change_state(Button::STATE_HOVERED);
@@ -231,39 +468,70 @@ where
                 } else {
                     // Make sure we reset our state if we don't handle the pointer event.
                     // It's possible we were the target for the last one.
-                    self.fields.change_state(Button::STATE_IDLE);
+                    self.end_press(Button::STATE_IDLE);
                     None
                 }
             }
+            InputEvent::PointerEvent(_, PointerEvent::Enter)
+            | InputEvent::PointerEvent(_, PointerEvent::Leave) => None,
             InputEvent::KeyEvent(_) => {
                 // TODO we do care about some key events
                 None
             }
-            InputEvent::ScrollEvent(_) => None,
+            InputEvent::ScrollEvent(_) | InputEvent::TouchEvent(..) => None,
         }
     }
 
-    fn handle_input(&mut self, _ctxt: InputContext, event: InputEvent) -> bool {
-        if self.fields.state.has_state(Button::STATE_INACTIVE) {
+    fn handle_input(&mut self, ctxt: InputContext, event: InputEvent) -> bool {
+        if !ctxt.is_target() || self.fields.state.has_state(Button::STATE_INACTIVE) {
             return false;
         }
 
         match event {
             InputEvent::Cancel => {
                 if self.fields.state.has_state(Button::STATE_PRESSED) {
-                    self.fields.change_state(Button::STATE_HOVERED);
+                    self.end_press(Button::STATE_HOVERED);
                 }
                 true
             }
-            InputEvent::PointerEvent(_, pe) => match pe {
-                PointerEvent::Hover | PointerEvent::Drag => false,
+            InputEvent::PointerEvent(position, pe) => match pe {
+                PointerEvent::Hover => false,
+                PointerEvent::Enter => {
+                    if !self.fields.state.has_state(Button::STATE_PRESSED) {
+                        self.fields.change_state(Button::STATE_HOVERED);
+                    }
+                    false
+                }
+                PointerEvent::Leave => {
+                    self.end_press(Button::STATE_IDLE);
+                    false
+                }
+                PointerEvent::Drag => {
+                    // We receive every drag while we capture the pointer
+                    if self.fields.inner.bounding_box().contains(position) {
+                        if !self.fields.state.has_state(Button::STATE_PRESSED) {
+                            self.fields.change_state(Button::STATE_HOVERED);
+                        }
+                    } else {
+                        // Drag outside = cancel
+                        self.end_press(Button::STATE_IDLE);
+                    }
+                    // Let parents (e.g. Scroll) take over the drag
+                    false
+                }
                 PointerEvent::Down => {
-                    self.fields.change_state(Button::STATE_PRESSED);
+                    self.press();
                     true
                 }
                 PointerEvent::Up => {
-                    self.fields.change_state(Button::STATE_HOVERED);
-                    self.fire_on_clicked();
+                    // The press may have been cancelled by dragging outside
+                    if self.fields.state.has_state(Button::STATE_PRESSED) {
+                        let repeated = self.repeat.has_repeated();
+                        self.end_press(Button::STATE_HOVERED);
+                        if !repeated {
+                            self.fire_on_clicked();
+                        }
+                    }
                     true
                 }
             },
@@ -274,8 +542,9 @@ where
         }
     }
 
-    fn on_state_changed(&mut self, _state: WidgetState) {
-        // don't react to parent's state change
+    fn on_state_changed(&mut self, state: WidgetState) {
+        // only react to a parent being disabled
+        self.fields.inherit_state(state);
     }
 
     fn is_selectable(&self) -> bool {
@@ -283,11 +552,12 @@ where
     }
 }
 
-impl<C, W, D> WidgetRenderer<C> for Button<W, D>
+impl<C, W, D, R> WidgetRenderer<C> for Button<W, D, R>
 where
     W: Widget + WidgetRenderer<C>,
     C: Canvas,
     D: WidgetData,
+    R: RepeatController,
 {
     fn draw(&mut self, canvas: &mut C) -> Result<(), C::Error> {
         self.fields.inner.draw(canvas)
@@ -0,0 +1,268 @@
+//! Touch screen calibration screen.
+//!
+//! The calibration screen displays a series of targets. The user touches each target, and once all
+//! targets are recorded, the screen computes the [`AffineTransform`] that maps raw panel
+//! coordinates to display coordinates, and passes it to the `on_calibrated` callback.
+//!
+//! The calibration screen expects untransformed input, so it should be used with an input
+//! controller that doesn't have a calibration applied.
+//!
+//! The calibration screen takes up all the space its parent offers, and the targets are placed
+//! relative to it. It has no size of its own, so it must not be placed where its size is
+//! unspecified, e.g. inside a `Scroll`, because it would be empty there. Making it the root widget
+//! works best.
+//!
+//! ```rust,ignore
+//! let calibration = BoundData::new(AffineTransform::identity(), |_| ());
+//!
+//! let screen = CalibrationScreen::new(CalibrationPoints::Five)
+//!     .bind(&calibration)
+//!     .on_calibrated(|data, transform| *data = transform);
+//! ```
+
+use crate::{
+    data::WidgetData,
+    geometry::{measurement::MeasureSpec, BoundingBox, MeasuredSize, Position},
+    input::{
+        controller::InputContext,
+        event::{InputEvent, PointerEvent},
+        transform::AffineTransform,
+    },
+    state::WidgetState,
+    widgets::Widget,
+};
+
+pub trait CalibrationProperties {
+    type Color;
+
+    fn set_background_color(&mut self, color: Self::Color);
+
+    fn set_target_color(&mut self, color: Self::Color);
+}
+
+/// The number of targets the user needs to touch.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CalibrationPoints {
+    /// Three targets, the minimum to compute the transformation.
+    Three,
+
+    /// Five targets, to average out measurement noise.
+    Five,
+}
+
+impl CalibrationPoints {
+    fn count(self) -> usize {
+        match self {
+            CalibrationPoints::Three => 3,
+            CalibrationPoints::Five => 5,
+        }
+    }
+
+    /// Target position in eighths of the screen size.
+    fn target(self, idx: usize) -> (i32, i32) {
+        const THREE: [(i32, i32); 3] = [(1, 1), (7, 4), (4, 7)];
+        const FIVE: [(i32, i32); 5] = [(1, 1), (7, 1), (7, 7), (1, 7), (4, 4)];
+
+        match self {
+            CalibrationPoints::Three => THREE[idx],
+            CalibrationPoints::Five => FIVE[idx],
+        }
+    }
+}
+
+pub struct CalibrationScreen<P, D = ()>
+where
+    P: CalibrationProperties,
+    D: WidgetData,
+{
+    pub calibration_properties: P,
+    pub parent_index: usize,
+    pub bounds: BoundingBox,
+    points: CalibrationPoints,
+    samples: [Position; 5],
+    current: usize,
+    data: D,
+    on_calibrated: fn(&mut D::Data, AffineTransform),
+}
+
+impl<P> CalibrationScreen<P, ()>
+where
+    P: CalibrationProperties,
+{
+    pub fn new(points: CalibrationPoints) -> Self
+    where
+        P: Default,
+    {
+        CalibrationScreen {
+            calibration_properties: P::default(),
+            parent_index: 0,
+            bounds: BoundingBox::default(),
+            points,
+            samples: [Position { x: 0, y: 0 }; 5],
+            current: 0,
+            data: (),
+            on_calibrated: |_, _| (),
+        }
+    }
+
+    pub fn bind<D>(self, data: D) -> CalibrationScreen<P, D>
+    where
+        D: WidgetData,
+    {
+        CalibrationScreen {
+            calibration_properties: self.calibration_properties,
+            parent_index: self.parent_index,
+            bounds: self.bounds,
+            points: self.points,
+            samples: self.samples,
+            current: self.current,
+            data,
+            on_calibrated: |_, _| (),
+        }
+    }
+}
+
+impl<P, D> CalibrationScreen<P, D>
+where
+    P: CalibrationProperties,
+    D: WidgetData,
+{
+    pub fn background_color(mut self, color: P::Color) -> Self {
+        self.set_background_color(color);
+        self
+    }
+
+    pub fn set_background_color(&mut self, color: P::Color) {
+        self.calibration_properties.set_background_color(color);
+    }
+
+    pub fn target_color(mut self, color: P::Color) -> Self {
+        self.set_target_color(color);
+        self
+    }
+
+    pub fn set_target_color(&mut self, color: P::Color) {
+        self.calibration_properties.set_target_color(color);
+    }
+
+    /// Sets the callback that receives the computed transformation.
+    pub fn on_calibrated(mut self, callback: fn(&mut D::Data, AffineTransform)) -> Self {
+        self.on_calibrated = callback;
+        self
+    }
+
+    /// Returns the position of the target the user should touch, or `None` if the calibration is
+    /// complete.
+    pub fn target(&self) -> Option<Position> {
+        if self.current < self.points.count() {
+            Some(self.target_position(self.current))
+        } else {
+            None
+        }
+    }
+
+    /// Discards the recorded samples and starts over with the first target.
+    pub fn restart(&mut self) {
+        self.current = 0;
+    }
+
+    fn target_position(&self, idx: usize) -> Position {
+        let (x, y) = self.points.target(idx);
+        let MeasuredSize { width, height } = self.bounds.size;
+
+        self.bounds.position
+            + Position {
+                x: x * width as i32 / 8,
+                y: y * height as i32 / 8,
+            }
+    }
+
+    fn record(&mut self, position: Position) {
+        self.samples[self.current] = position;
+        self.current += 1;
+
+        let count = self.points.count();
+        if self.current < count {
+            return;
+        }
+
+        let mut pairs = [(Position { x: 0, y: 0 }, Position { x: 0, y: 0 }); 5];
+        for (idx, pair) in pairs.iter_mut().enumerate().take(count) {
+            *pair = (self.samples[idx], self.target_position(idx));
+        }
+
+        if let Some(transform) = AffineTransform::from_points(&pairs[0..count]) {
+            let callback = self.on_calibrated;
+            self.data.update(|data| callback(data, transform));
+        } else {
+            // The touches were unusable, try again.
+            self.restart();
+        }
+    }
+}
+
+impl<P, D> Widget for CalibrationScreen<P, D>
+where
+    P: CalibrationProperties,
+    D: WidgetData,
+{
+    fn bounding_box(&self) -> BoundingBox {
+        self.bounds
+    }
+
+    fn bounding_box_mut(&mut self) -> &mut BoundingBox {
+        &mut self.bounds
+    }
+
+    fn measure(&mut self, measure_spec: MeasureSpec) {
+        // Take up all the available space. There is no sensible size to fall back to, so an
+        // unspecified dimension is 0.
+        self.bounds.size = MeasuredSize {
+            width: measure_spec.width.largest().unwrap_or(0),
+            height: measure_spec.height.largest().unwrap_or(0),
+        };
+    }
+
+    fn parent_index(&self) -> usize {
+        self.parent_index
+    }
+
+    fn set_parent(&mut self, index: usize) {
+        self.parent_index = index;
+    }
+
+    fn reset_changed(&mut self) {
+        self.data.reset_changed();
+    }
+
+    fn test_input(&mut self, event: InputEvent) -> Option<usize> {
+        // Raw positions may be anywhere, so we accept every pointer event while calibrating
+        match event {
+            InputEvent::PointerEvent(_, PointerEvent::Down)
+            | InputEvent::PointerEvent(_, PointerEvent::Up)
+                if self.target().is_some() =>
+            {
+                Some(0)
+            }
+            _ => None,
+        }
+    }
+
+    fn handle_input(&mut self, ctxt: InputContext, event: InputEvent) -> bool {
+        if !ctxt.is_target() || self.target().is_none() {
+            return false;
+        }
+
+        match event {
+            InputEvent::PointerEvent(_, PointerEvent::Down) => true,
+            InputEvent::PointerEvent(position, PointerEvent::Up) => {
+                // The position settles by the time the pointer is released
+                self.record(position);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn on_state_changed(&mut self, _state: WidgetState) {}
+}
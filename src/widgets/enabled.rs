@@ -0,0 +1,170 @@
+//! Enable or disable the contained widgets
+//!
+//! `Enabled` makes every widget inside it inactive while it is disabled, so there's no need to
+//! call `set_active` on each of them individually. Disabled widgets don't receive input and are
+//! drawn in their inactive style.
+//!
+//! ```rust,ignore
+//! Enabled::new(
+//!     Column::new()
+//!         .add(TextBox::new(name))
+//!         .add(Toggle::new(check_box)),
+//! )
+//! .bind(&data)
+//! .on_data_changed(|widget, data| widget.set_enabled(data.editable))
+//! ```
+//!
+//! Widgets that were made inactive by calling `set_active(false)` stay inactive when the `Enabled`
+//! is enabled again.
+
+use crate::{
+    data::WidgetData,
+    geometry::{BoundingBox, Position},
+    input::{controller::InputContext, event::InputEvent},
+    state::{enabled::Disabled, WidgetState},
+    widgets::{
+        utils::{
+            decorator::WidgetDecorator,
+            wrapper::{Wrapper, WrapperBindable},
+        },
+        Widget,
+    },
+    Canvas, WidgetRenderer,
+};
+
+pub struct Enabled<W> {
+    pub inner: W,
+    pub enabled: bool,
+    /// The last state received from the parent widget.
+    pub parent_state: WidgetState,
+    pub on_state_changed: fn(&mut Self, WidgetState),
+}
+
+impl<W> Enabled<W>
+where
+    W: Widget,
+{
+    pub fn new(inner: W) -> Enabled<W> {
+        Enabled {
+            inner,
+            enabled: true,
+            parent_state: WidgetState::default(),
+            on_state_changed: |_, _| (),
+        }
+    }
+
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.set_enabled(enabled);
+        self
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        if self.enabled != enabled {
+            self.enabled = enabled;
+            self.inner.on_state_changed(self.state());
+        }
+    }
+
+    /// Returns whether the contained widgets are enabled, taking the parent widgets into account.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled && !self.parent_state.has_state(Disabled)
+    }
+
+    /// The state passed to the contained widgets.
+    fn state(&self) -> WidgetState {
+        let mut state = self.parent_state;
+        if !self.enabled {
+            state.set_state(Disabled);
+        }
+        state
+    }
+}
+
+impl<W> Enabled<W> {
+    pub fn on_state_changed(mut self, callback: fn(&mut Self, WidgetState)) -> Self {
+        self.on_state_changed = callback;
+        self
+    }
+}
+
+impl<W> WrapperBindable for Enabled<W> where W: Widget {}
+
+impl<W, D> Wrapper<Enabled<W>, D>
+where
+    W: Widget,
+    D: WidgetData,
+{
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.widget.set_enabled(enabled);
+        self
+    }
+
+    pub fn on_state_changed(mut self, callback: fn(&mut Enabled<W>, WidgetState)) -> Self {
+        self.widget.on_state_changed = callback;
+        self
+    }
+}
+
+impl<W> WidgetDecorator for Enabled<W>
+where
+    W: Widget,
+{
+    type Widget = W;
+
+    fn widget(&self) -> &Self::Widget {
+        &self.inner
+    }
+
+    fn widget_mut(&mut self) -> &mut Self::Widget {
+        &mut self.inner
+    }
+
+    fn bounding_box(&self) -> BoundingBox {
+        self.inner.bounding_box()
+    }
+
+    fn on_state_changed(&mut self, state: WidgetState) {
+        self.parent_state = state;
+        self.fire_on_state_changed(state);
+        self.inner.on_state_changed(self.state());
+    }
+
+    fn fire_on_state_changed(&mut self, state: WidgetState) {
+        (self.on_state_changed)(self, state);
+    }
+
+    fn test_input(&mut self, event: InputEvent) -> Option<usize> {
+        if self.is_enabled() {
+            self.inner.test_input(event).map(|i| i + 1)
+        } else {
+            None
+        }
+    }
+
+    fn hit_test(&self, position: Position) -> Option<usize> {
+        if !self.is_enabled() {
+            // Disabled widgets still cover whatever is below them
+            return if self.inner.bounding_box().contains(position) {
+                Some(0)
+            } else {
+                None
+            };
+        }
+
+        self.inner.hit_test(position).map(|i| i + 1)
+    }
+
+    fn handle_input(&mut self, ctxt: InputContext, event: InputEvent) -> bool {
+        self.is_enabled() && self.inner.handle_input(ctxt, event)
+    }
+}
+
+impl<C, W> WidgetRenderer<C> for Enabled<W>
+where
+    W: Widget + WidgetRenderer<C>,
+    C: Canvas,
+{
+    fn draw(&mut self, canvas: &mut C) -> Result<(), C::Error> {
+        self.inner.draw(canvas)
+    }
+}
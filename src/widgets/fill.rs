@@ -227,6 +227,18 @@ where
 
         D::measure(self, self.inner.bounding_box().size, measure_spec);
     }
+
+    fn baseline(&self) -> Option<u32> {
+        // Our position may be outdated during measurement, so calculate the offset from the sizes.
+        let offset = V::vertical_offset(
+            self.bounds.size.height,
+            self.inner.bounding_box().size.height,
+        );
+
+        self.inner
+            .baseline()
+            .map(|baseline| (baseline as i32 + offset) as u32)
+    }
 }
 
 impl<C, W, FD, H, V> WidgetRenderer<C> for FillParent<W, FD, H, V>
@@ -8,6 +8,11 @@ use crate::{
 
 pub trait LabelProperties {
     fn measure_text(&self, text: &str) -> MeasuredSize;
+
+    /// Returns the distance of the text baseline from the top of the measured text.
+    fn baseline(&self, _text: &str) -> Option<u32> {
+        None
+    }
 }
 
 pub struct Label<S, P> {
@@ -66,6 +71,14 @@ where
     fn is_selectable(&self) -> bool {
         false
     }
+
+    fn text(&self) -> Option<&str> {
+        Some(self.text.as_ref())
+    }
+
+    fn baseline(&self) -> Option<u32> {
+        self.label_properties.baseline(self.text.as_ref())
+    }
 }
 
 impl<S, P> WrapperBindable for Label<S, P>
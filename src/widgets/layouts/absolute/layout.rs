@@ -0,0 +1,405 @@
+use object_chain::{Chain, ChainElement, Link};
+
+use crate::{
+    geometry::{
+        measurement::{MeasureConstraint, MeasureSpec},
+        BoundingBox, MeasuredSize, Position,
+    },
+    input::event::InputEvent,
+    state::WidgetState,
+    widgets::{
+        layouts::absolute::{AbsoluteCell, Anchor},
+        Widget,
+    },
+    Canvas, WidgetRenderer,
+};
+
+/// A layout that places widgets at explicit, anchored positions.
+///
+/// See [`Absolute`](super::Absolute) for details.
+pub struct AbsoluteLayout<CE> {
+    pub widgets: CE,
+    pub bounds: BoundingBox,
+}
+
+impl<CE> AbsoluteLayout<CE>
+where
+    CE: AbsoluteLayoutChainElement + ChainElement,
+{
+    /// Adds a widget to the layout, at the top left corner, on top of the previous widgets.
+    pub fn add<W>(self, inner: W) -> AbsoluteLayout<Link<AbsoluteCell<W>, CE>>
+    where
+        W: Widget,
+    {
+        AbsoluteLayout {
+            widgets: self.widgets.append(AbsoluteCell::new(inner)),
+            bounds: self.bounds,
+        }
+    }
+
+    fn locate(&self, mut idx: usize) -> Option<(usize, usize)> {
+        let children = self.widgets.len();
+
+        for i in 0..children {
+            let child = self.widgets.at(i).widget();
+            let grandchildren = child.children();
+            if idx <= grandchildren {
+                return Some((i, idx));
+            }
+
+            idx -= grandchildren + 1;
+        }
+
+        None
+    }
+}
+
+impl<W, CE> AbsoluteLayout<Link<AbsoluteCell<W>, CE>>
+where
+    W: Widget,
+    CE: AbsoluteLayoutChainElement + ChainElement,
+{
+    /// Sets the anchor of the last added widget.
+    pub fn anchor(mut self, anchor: Anchor) -> Self {
+        self.widgets.object.anchor = anchor;
+        self
+    }
+
+    /// Sets the distance of the last added widget from its anchored edges.
+    pub fn offset(mut self, x: i32, y: i32) -> Self {
+        self.widgets.object.offset = Position { x, y };
+        self
+    }
+
+    /// Sets the exact size of the last added widget.
+    pub fn size(mut self, width: u32, height: u32) -> Self {
+        self.widgets.object.size = Some(MeasuredSize { width, height });
+        self
+    }
+}
+
+impl<W> AbsoluteLayout<Chain<AbsoluteCell<W>>>
+where
+    W: Widget,
+{
+    /// Sets the anchor of the last added widget.
+    pub fn anchor(mut self, anchor: Anchor) -> Self {
+        self.widgets.object.anchor = anchor;
+        self
+    }
+
+    /// Sets the distance of the last added widget from its anchored edges.
+    pub fn offset(mut self, x: i32, y: i32) -> Self {
+        self.widgets.object.offset = Position { x, y };
+        self
+    }
+
+    /// Sets the exact size of the last added widget.
+    pub fn size(mut self, width: u32, height: u32) -> Self {
+        self.widgets.object.size = Some(MeasuredSize { width, height });
+        self
+    }
+}
+
+pub trait AbsoluteLayoutCell {
+    fn anchor(&self) -> Anchor;
+
+    fn offset(&self) -> Position;
+
+    fn size(&self) -> Option<MeasuredSize>;
+
+    fn widget(&self) -> &dyn Widget;
+
+    fn widget_mut(&mut self) -> &mut dyn Widget;
+}
+
+impl<W> AbsoluteLayoutCell for AbsoluteCell<W>
+where
+    W: Widget,
+{
+    fn anchor(&self) -> Anchor {
+        self.anchor
+    }
+
+    fn offset(&self) -> Position {
+        self.offset
+    }
+
+    fn size(&self) -> Option<MeasuredSize> {
+        self.size
+    }
+
+    fn widget(&self) -> &dyn Widget {
+        &self.inner
+    }
+
+    fn widget_mut(&mut self) -> &mut dyn Widget {
+        &mut self.inner
+    }
+}
+
+pub trait AbsoluteLayoutChainElement {
+    fn at(&self, index: usize) -> &dyn AbsoluteLayoutCell;
+
+    fn at_mut(&mut self, index: usize) -> &mut dyn AbsoluteLayoutCell;
+
+    fn test_input(&mut self, event: InputEvent) -> Option<usize>;
+
+    fn count_widgets(&self) -> usize;
+
+    fn on_state_changed(&mut self, state: WidgetState);
+
+    fn update(&mut self);
+}
+
+impl<W> AbsoluteLayoutChainElement for Chain<AbsoluteCell<W>>
+where
+    W: Widget,
+{
+    fn at(&self, index: usize) -> &dyn AbsoluteLayoutCell {
+        debug_assert!(index == 0);
+
+        &self.object
+    }
+
+    fn at_mut(&mut self, index: usize) -> &mut dyn AbsoluteLayoutCell {
+        debug_assert!(index == 0);
+
+        &mut self.object
+    }
+
+    fn test_input(&mut self, event: InputEvent) -> Option<usize> {
+        self.object.inner.test_input(event)
+    }
+
+    fn count_widgets(&self) -> usize {
+        self.object.inner.children() + 1
+    }
+
+    fn on_state_changed(&mut self, state: WidgetState) {
+        self.object.inner.on_state_changed(state);
+    }
+
+    fn update(&mut self) {
+        self.object.inner.update();
+    }
+}
+
+impl<W, CE> AbsoluteLayoutChainElement for Link<AbsoluteCell<W>, CE>
+where
+    W: Widget,
+    CE: AbsoluteLayoutChainElement + ChainElement,
+{
+    fn at(&self, index: usize) -> &dyn AbsoluteLayoutCell {
+        if index == Link::len(self) - 1 {
+            return &self.object;
+        }
+
+        self.parent.at(index)
+    }
+
+    fn at_mut(&mut self, index: usize) -> &mut dyn AbsoluteLayoutCell {
+        if index == Link::len(self) - 1 {
+            return &mut self.object;
+        }
+
+        self.parent.at_mut(index)
+    }
+
+    fn test_input(&mut self, event: InputEvent) -> Option<usize> {
+        // Later widgets are on top, so they get the first chance to handle the event
+        self.object
+            .inner
+            .test_input(event)
+            .map(|idx| idx + self.parent.count_widgets())
+            .or_else(|| self.parent.test_input(event))
+    }
+
+    fn count_widgets(&self) -> usize {
+        self.object.inner.children() + 1 + self.parent.count_widgets()
+    }
+
+    fn on_state_changed(&mut self, state: WidgetState) {
+        self.object.inner.on_state_changed(state);
+        self.parent.on_state_changed(state);
+    }
+
+    fn update(&mut self) {
+        self.object.inner.update();
+        self.parent.update();
+    }
+}
+
+impl<C, W> WidgetRenderer<C> for Chain<AbsoluteCell<W>>
+where
+    W: Widget,
+    AbsoluteCell<W>: WidgetRenderer<C>,
+    C: Canvas,
+{
+    fn draw(&mut self, canvas: &mut C) -> Result<(), C::Error> {
+        self.object.draw(canvas)
+    }
+}
+
+impl<C, W, CE> WidgetRenderer<C> for Link<AbsoluteCell<W>, CE>
+where
+    W: Widget,
+    CE: AbsoluteLayoutChainElement + ChainElement + WidgetRenderer<C>,
+    AbsoluteCell<W>: WidgetRenderer<C>,
+    C: Canvas,
+{
+    fn draw(&mut self, canvas: &mut C) -> Result<(), C::Error> {
+        self.parent.draw(canvas)?;
+        self.object.draw(canvas)
+    }
+}
+
+impl<CE> Widget for AbsoluteLayout<CE>
+where
+    CE: AbsoluteLayoutChainElement + ChainElement,
+{
+    fn attach(&mut self, parent: usize, index: usize) {
+        debug_assert!(index == 0 || parent != index);
+        let mut children = index;
+
+        for i in 0..self.widgets.len() {
+            let widget = self.widgets.at_mut(i).widget_mut();
+
+            widget.attach(parent, children + i + 1);
+            children += widget.children();
+        }
+    }
+
+    fn bounding_box(&self) -> BoundingBox {
+        self.bounds
+    }
+
+    fn bounding_box_mut(&mut self) -> &mut BoundingBox {
+        &mut self.bounds
+    }
+
+    fn measure(&mut self, measure_spec: MeasureSpec) {
+        let mut content_width = 0;
+        let mut content_height = 0;
+
+        for i in 0..self.widgets.len() {
+            let cell = self.widgets.at_mut(i);
+            let offset = cell.offset();
+            let spec = match cell.size() {
+                Some(size) => MeasureSpec {
+                    width: MeasureConstraint::Exactly(size.width),
+                    height: MeasureConstraint::Exactly(size.height),
+                },
+                None => MeasureSpec {
+                    width: MeasureConstraint::Unspecified,
+                    height: MeasureConstraint::Unspecified,
+                },
+            };
+
+            let widget = cell.widget_mut();
+            widget.measure(spec);
+
+            let size = widget.bounding_box().size;
+            content_width = content_width.max(size.width + offset.x.unsigned_abs());
+            content_height = content_height.max(size.height + offset.y.unsigned_abs());
+        }
+
+        self.bounds.size = MeasuredSize {
+            width: measure_spec.width.largest().unwrap_or(content_width),
+            height: measure_spec.height.largest().unwrap_or(content_height),
+        };
+    }
+
+    fn arrange(&mut self, position: Position) {
+        self.bounds.position = position;
+
+        for i in 0..self.widgets.len() {
+            let cell = self.widgets.at_mut(i);
+            let anchor = cell.anchor();
+            let offset = cell.offset();
+
+            let widget = cell.widget_mut();
+            let size = widget.bounding_box().size;
+            widget.arrange(anchor.place(self.bounds, size, offset));
+        }
+    }
+
+    fn children(&self) -> usize {
+        self.widgets.count_widgets()
+    }
+
+    fn get_child(&self, idx: usize) -> &dyn Widget {
+        let (child, grandchild) = self.locate(idx).unwrap();
+
+        let widget = self.widgets.at(child).widget();
+        if grandchild == 0 {
+            widget
+        } else {
+            widget.get_child(grandchild - 1)
+        }
+    }
+
+    fn get_mut_child(&mut self, idx: usize) -> &mut dyn Widget {
+        let (child, grandchild) = self.locate(idx).unwrap();
+
+        let widget = self.widgets.at_mut(child).widget_mut();
+        if grandchild == 0 {
+            widget
+        } else {
+            widget.get_mut_child(grandchild - 1)
+        }
+    }
+
+    fn parent_index(&self) -> usize {
+        self.widgets.at(0).widget().parent_index()
+    }
+
+    fn set_parent(&mut self, _index: usize) {}
+
+    fn update(&mut self) {
+        self.widgets.update();
+    }
+
+    fn test_input(&mut self, event: InputEvent) -> Option<usize> {
+        self.widgets.test_input(event).map(|idx| idx + 1)
+    }
+
+    fn hit_test(&self, position: Position) -> Option<usize> {
+        let mut offset = 1;
+        let mut hit = None;
+
+        // Later widgets are drawn on top, so they take precedence
+        for i in 0..self.widgets.len() {
+            let widget = self.widgets.at(i).widget();
+
+            if let Some(idx) = widget.hit_test(position) {
+                hit = Some(offset + idx);
+            }
+            offset += widget.children() + 1;
+        }
+
+        if hit.is_none() && self.bounds.contains(position) {
+            hit = Some(0);
+        }
+
+        hit
+    }
+
+    fn on_state_changed(&mut self, state: WidgetState) {
+        self.widgets.on_state_changed(state);
+    }
+
+    fn is_selectable(&self) -> bool {
+        false
+    }
+}
+
+impl<C, CE> WidgetRenderer<C> for AbsoluteLayout<CE>
+where
+    CE: AbsoluteLayoutChainElement + ChainElement + WidgetRenderer<C>,
+    C: Canvas,
+{
+    fn draw(&mut self, canvas: &mut C) -> Result<(), C::Error> {
+        self.widgets.draw(canvas)
+    }
+}
@@ -0,0 +1,159 @@
+//! Absolute layout.
+//!
+//! The Absolute layout places widgets at explicit positions, relative to one of the corners, edges
+//! or the center of the layout. This is useful for dashboards drawn over a background image.
+//!
+//! ```rust,ignore
+//! Absolute::new()
+//!     .add(Label::new("12:00"))
+//!     .anchor(Anchor::TopRight)
+//!     .offset(4, 4)
+//!     .add(gauge)
+//!     .anchor(Anchor::Center)
+//!     .size(48, 48)
+//! ```
+
+use object_chain::Chain;
+
+use crate::{
+    geometry::{BoundingBox, MeasuredSize, Position},
+    widgets::Widget,
+    Canvas, WidgetRenderer,
+};
+
+mod layout;
+
+pub use layout::AbsoluteLayout;
+
+/// The point of the layout a widget is positioned relative to.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Anchor {
+    TopLeft,
+    TopCenter,
+    TopRight,
+    CenterLeft,
+    Center,
+    CenterRight,
+    BottomLeft,
+    BottomCenter,
+    BottomRight,
+}
+
+/// Position of a widget along one axis of the layout.
+#[derive(Copy, Clone)]
+enum Edge {
+    Start,
+    Center,
+    End,
+}
+
+impl Edge {
+    fn place(self, start: i32, space: u32, size: u32, offset: i32) -> i32 {
+        let free_space = space as i32 - size as i32;
+
+        match self {
+            Edge::Start => start + offset,
+            Edge::Center => start + free_space / 2 + offset,
+            Edge::End => start + free_space - offset,
+        }
+    }
+}
+
+impl Anchor {
+    fn edges(self) -> (Edge, Edge) {
+        match self {
+            Anchor::TopLeft => (Edge::Start, Edge::Start),
+            Anchor::TopCenter => (Edge::Center, Edge::Start),
+            Anchor::TopRight => (Edge::End, Edge::Start),
+            Anchor::CenterLeft => (Edge::Start, Edge::Center),
+            Anchor::Center => (Edge::Center, Edge::Center),
+            Anchor::CenterRight => (Edge::End, Edge::Center),
+            Anchor::BottomLeft => (Edge::Start, Edge::End),
+            Anchor::BottomCenter => (Edge::Center, Edge::End),
+            Anchor::BottomRight => (Edge::End, Edge::End),
+        }
+    }
+
+    /// Returns the position of a widget of the given `size`, anchored inside `bounds`.
+    ///
+    /// `offset` moves the widget away from the anchored edges, towards the inside of the layout.
+    /// Along a centered axis, `offset` moves the widget right or down.
+    pub fn place(self, bounds: BoundingBox, size: MeasuredSize, offset: Position) -> Position {
+        let (horizontal, vertical) = self.edges();
+
+        Position {
+            x: horizontal.place(bounds.position.x, bounds.size.width, size.width, offset.x),
+            y: vertical.place(bounds.position.y, bounds.size.height, size.height, offset.y),
+        }
+    }
+}
+
+/// Absolute layout.
+///
+/// Widgets are measured with an `Unspecified` constraint, unless a size is set for them, and are
+/// placed relative to their [`Anchor`]. The layout takes up all the available space. If the
+/// available space is not limited, the layout is just large enough to hold its widgets.
+///
+/// Widgets added later are drawn on top of, and receive input before, widgets added earlier.
+pub struct Absolute;
+
+impl Absolute {
+    /// Creates a new, empty absolute layout.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Adds a widget to the layout, at the top left corner.
+    pub fn add<W>(self, inner: W) -> AbsoluteLayout<Chain<AbsoluteCell<W>>>
+    where
+        W: Widget,
+    {
+        AbsoluteLayout {
+            widgets: Chain::new(AbsoluteCell::new(inner)),
+            bounds: BoundingBox::default(),
+        }
+    }
+}
+
+impl Default for Absolute {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A single cell in an absolute layout.
+pub struct AbsoluteCell<W>
+where
+    W: Widget,
+{
+    pub anchor: Anchor,
+    /// The distance of the widget from the anchored edges.
+    pub offset: Position,
+    /// The exact size of the widget. The widget is measured freely if `None`.
+    pub size: Option<MeasuredSize>,
+    pub inner: W,
+}
+
+impl<W> AbsoluteCell<W>
+where
+    W: Widget,
+{
+    fn new(inner: W) -> Self {
+        Self {
+            anchor: Anchor::TopLeft,
+            offset: Position { x: 0, y: 0 },
+            size: None,
+            inner,
+        }
+    }
+}
+
+impl<C, W> WidgetRenderer<C> for AbsoluteCell<W>
+where
+    W: Widget + WidgetRenderer<C>,
+    C: Canvas,
+{
+    fn draw(&mut self, canvas: &mut C) -> Result<(), C::Error> {
+        canvas.draw_clipped(self.inner.bounding_box(), &mut self.inner)
+    }
+}
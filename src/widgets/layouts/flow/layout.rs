@@ -0,0 +1,398 @@
+use object_chain::{Chain, ChainElement, Link};
+
+use crate::{
+    geometry::{
+        measurement::{MeasureConstraint, MeasureSpec},
+        BoundingBox, MeasuredSize, Position,
+    },
+    input::event::InputEvent,
+    state::WidgetState,
+    widgets::{layouts::linear::MainAxisAlignment, Widget},
+    Canvas, WidgetRenderer,
+};
+
+/// A layout that places widgets in lines, wrapping them when the width is exhausted.
+///
+/// See [`Flow`](super::Flow) for details.
+pub struct FlowLayout<CE> {
+    pub widgets: CE,
+    pub bounds: BoundingBox,
+    pub horizontal_gap: u32,
+    pub vertical_gap: u32,
+    pub line_alignment: MainAxisAlignment,
+}
+
+impl<CE> FlowLayout<CE>
+where
+    CE: ChainElement,
+{
+    /// Appends a widget to the layout.
+    pub fn add<W>(self, inner: W) -> FlowLayout<Link<W, CE>> {
+        FlowLayout {
+            widgets: self.widgets.append(inner),
+            bounds: self.bounds,
+            horizontal_gap: self.horizontal_gap,
+            vertical_gap: self.vertical_gap,
+            line_alignment: self.line_alignment,
+        }
+    }
+}
+
+impl<CE> FlowLayout<CE>
+where
+    CE: ChainElement + FlowLayoutChainElement,
+{
+    /// Sets the space between widgets of the same line.
+    pub fn horizontal_gap(mut self, gap: u32) -> Self {
+        self.horizontal_gap = gap;
+        self
+    }
+
+    /// Sets the space between lines.
+    pub fn vertical_gap(mut self, gap: u32) -> Self {
+        self.vertical_gap = gap;
+        self
+    }
+
+    /// Sets how the free space of each line is distributed between its widgets.
+    pub fn line_alignment(mut self, alignment: MainAxisAlignment) -> Self {
+        self.line_alignment = alignment;
+        self
+    }
+
+    /// Collects the line that starts with the widget at `start`, using the measured widget sizes.
+    ///
+    /// Returns the index of the first widget of the next line, and the size of the line. A line
+    /// always contains at least one widget, even if it is wider than `max_width`.
+    fn line(&self, start: usize, max_width: u32) -> (usize, MeasuredSize) {
+        let mut end = start;
+        let mut size = MeasuredSize {
+            width: 0,
+            height: 0,
+        };
+
+        while end < self.widgets.len() {
+            let widget_size = self.widgets.at(end).bounding_box().size;
+            let width = if end == start {
+                widget_size.width
+            } else {
+                size.width
+                    .saturating_add(self.horizontal_gap)
+                    .saturating_add(widget_size.width)
+            };
+
+            if end != start && width > max_width {
+                break;
+            }
+
+            size.width = width;
+            size.height = size.height.max(widget_size.height);
+            end += 1;
+        }
+
+        (end, size)
+    }
+
+    fn locate(&self, mut idx: usize) -> Option<(usize, usize)> {
+        let children = self.widgets.len();
+
+        for i in 0..children {
+            let child = self.widgets.at(i);
+            let grandchildren = child.children();
+            if idx <= grandchildren {
+                return Some((i, idx));
+            }
+
+            idx -= grandchildren + 1;
+        }
+
+        None
+    }
+}
+
+pub trait FlowLayoutChainElement {
+    fn at(&self, index: usize) -> &dyn Widget;
+
+    fn at_mut(&mut self, index: usize) -> &mut dyn Widget;
+
+    fn test_input(&mut self, event: InputEvent) -> Option<usize>;
+
+    fn count_widgets(&self) -> usize;
+
+    fn on_state_changed(&mut self, state: WidgetState);
+
+    fn update(&mut self);
+}
+
+impl<W> FlowLayoutChainElement for Chain<W>
+where
+    W: Widget,
+{
+    fn at(&self, index: usize) -> &dyn Widget {
+        debug_assert!(index == 0);
+
+        &self.object
+    }
+
+    fn at_mut(&mut self, index: usize) -> &mut dyn Widget {
+        debug_assert!(index == 0);
+
+        &mut self.object
+    }
+
+    fn test_input(&mut self, event: InputEvent) -> Option<usize> {
+        self.object.test_input(event)
+    }
+
+    fn count_widgets(&self) -> usize {
+        self.object.children() + 1
+    }
+
+    fn on_state_changed(&mut self, state: WidgetState) {
+        self.object.on_state_changed(state);
+    }
+
+    fn update(&mut self) {
+        self.object.update();
+    }
+}
+
+impl<W, CE> FlowLayoutChainElement for Link<W, CE>
+where
+    W: Widget,
+    CE: FlowLayoutChainElement + ChainElement,
+{
+    fn at(&self, index: usize) -> &dyn Widget {
+        if index == Link::len(self) - 1 {
+            return &self.object;
+        }
+
+        self.parent.at(index)
+    }
+
+    fn at_mut(&mut self, index: usize) -> &mut dyn Widget {
+        if index == Link::len(self) - 1 {
+            return &mut self.object;
+        }
+
+        self.parent.at_mut(index)
+    }
+
+    fn test_input(&mut self, event: InputEvent) -> Option<usize> {
+        self.parent.test_input(event).or_else(|| {
+            self.object
+                .test_input(event)
+                .map(|idx| idx + self.parent.count_widgets())
+        })
+    }
+
+    fn count_widgets(&self) -> usize {
+        self.object.children() + 1 + self.parent.count_widgets()
+    }
+
+    fn on_state_changed(&mut self, state: WidgetState) {
+        self.object.on_state_changed(state);
+        self.parent.on_state_changed(state);
+    }
+
+    fn update(&mut self) {
+        self.object.update();
+        self.parent.update();
+    }
+}
+
+impl<C, W> WidgetRenderer<C> for Chain<W>
+where
+    W: Widget + WidgetRenderer<C>,
+    C: Canvas,
+{
+    fn draw(&mut self, canvas: &mut C) -> Result<(), C::Error> {
+        canvas.draw_clipped(self.object.bounding_box(), &mut self.object)
+    }
+}
+
+impl<C, W, CE> WidgetRenderer<C> for Link<W, CE>
+where
+    W: Widget + WidgetRenderer<C>,
+    CE: FlowLayoutChainElement + ChainElement + WidgetRenderer<C>,
+    C: Canvas,
+{
+    fn draw(&mut self, canvas: &mut C) -> Result<(), C::Error> {
+        self.parent.draw(canvas)?;
+        canvas.draw_clipped(self.object.bounding_box(), &mut self.object)
+    }
+}
+
+impl<CE> Widget for FlowLayout<CE>
+where
+    CE: FlowLayoutChainElement + ChainElement,
+{
+    fn attach(&mut self, parent: usize, index: usize) {
+        debug_assert!(index == 0 || parent != index);
+        let mut children = index;
+
+        for i in 0..self.widgets.len() {
+            let widget = self.widgets.at_mut(i);
+
+            widget.attach(parent, children + i + 1);
+            children += widget.children();
+        }
+    }
+
+    fn bounding_box(&self) -> BoundingBox {
+        self.bounds
+    }
+
+    fn bounding_box_mut(&mut self) -> &mut BoundingBox {
+        &mut self.bounds
+    }
+
+    fn measure(&mut self, measure_spec: MeasureSpec) {
+        let max_width = measure_spec.width.largest().unwrap_or(u32::MAX);
+        let spec = MeasureSpec {
+            width: MeasureConstraint::AtMost(max_width),
+            height: measure_spec.height.to_at_most(),
+        };
+
+        for i in 0..self.widgets.len() {
+            self.widgets.at_mut(i).measure(spec);
+        }
+
+        let mut content_width = 0;
+        let mut content_height = 0;
+        let mut start = 0;
+        while start < self.widgets.len() {
+            let (end, line) = self.line(start, max_width);
+
+            if start != 0 {
+                content_height += self.vertical_gap;
+            }
+            content_width = content_width.max(line.width);
+            content_height += line.height;
+
+            start = end;
+        }
+
+        let width = if self.line_alignment == MainAxisAlignment::Start {
+            measure_spec.width.apply_to_measured(content_width)
+        } else {
+            measure_spec.width.largest().unwrap_or(content_width)
+        };
+
+        self.bounds.size = MeasuredSize {
+            width,
+            height: measure_spec.height.apply_to_measured(content_height),
+        };
+    }
+
+    fn arrange(&mut self, position: Position) {
+        self.bounds.position = position;
+
+        let max_width = self.bounds.size.width;
+        let mut y = position.y;
+        let mut start = 0;
+
+        while start < self.widgets.len() {
+            let (end, line) = self.line(start, max_width);
+
+            let free_space = max_width.saturating_sub(line.width);
+            let (mut offset, gap, mut remainder) = self
+                .line_alignment
+                .distribute(free_space, (end - start) as u32);
+
+            for i in start..end {
+                let widget = self.widgets.at_mut(i);
+                let width = widget.bounding_box().size.width;
+
+                widget.arrange(Position {
+                    x: position.x + offset as i32,
+                    y,
+                });
+
+                let rem = if remainder > 0 { 1 } else { 0 };
+                remainder -= rem;
+                offset += width + self.horizontal_gap + gap + rem;
+            }
+
+            y += (line.height + self.vertical_gap) as i32;
+            start = end;
+        }
+    }
+
+    fn children(&self) -> usize {
+        self.widgets.count_widgets()
+    }
+
+    fn get_child(&self, idx: usize) -> &dyn Widget {
+        let (child, grandchild) = self.locate(idx).unwrap();
+
+        let widget = self.widgets.at(child);
+        if grandchild == 0 {
+            widget
+        } else {
+            widget.get_child(grandchild - 1)
+        }
+    }
+
+    fn get_mut_child(&mut self, idx: usize) -> &mut dyn Widget {
+        let (child, grandchild) = self.locate(idx).unwrap();
+
+        let widget = self.widgets.at_mut(child);
+        if grandchild == 0 {
+            widget
+        } else {
+            widget.get_mut_child(grandchild - 1)
+        }
+    }
+
+    fn parent_index(&self) -> usize {
+        self.widgets.at(0).parent_index()
+    }
+
+    fn set_parent(&mut self, _index: usize) {}
+
+    fn update(&mut self) {
+        self.widgets.update();
+    }
+
+    fn test_input(&mut self, event: InputEvent) -> Option<usize> {
+        self.widgets.test_input(event).map(|idx| idx + 1)
+    }
+
+    fn hit_test(&self, position: Position) -> Option<usize> {
+        let mut offset = 1;
+
+        for i in 0..self.widgets.len() {
+            let widget = self.widgets.at(i);
+
+            if let Some(idx) = widget.hit_test(position) {
+                return Some(offset + idx);
+            }
+            offset += widget.children() + 1;
+        }
+
+        if self.bounds.contains(position) {
+            Some(0)
+        } else {
+            None
+        }
+    }
+
+    fn on_state_changed(&mut self, state: WidgetState) {
+        self.widgets.on_state_changed(state);
+    }
+
+    fn is_selectable(&self) -> bool {
+        false
+    }
+}
+
+impl<C, CE> WidgetRenderer<C> for FlowLayout<CE>
+where
+    CE: FlowLayoutChainElement + ChainElement + WidgetRenderer<C>,
+    C: Canvas,
+{
+    fn draw(&mut self, canvas: &mut C) -> Result<(), C::Error> {
+        self.widgets.draw(canvas)
+    }
+}
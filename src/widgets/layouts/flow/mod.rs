@@ -0,0 +1,85 @@
+//! Flow layout.
+//!
+//! The Flow layout places widgets next to each other, left to right, and starts a new line when the
+//! next widget would not fit in the available width. This is useful for tag lists, icon grids and
+//! similar collections of small widgets.
+//!
+//! ```rust,ignore
+//! Flow::new()
+//!     .horizontal_gap(2)
+//!     .vertical_gap(1)
+//!     .line_alignment(MainAxisAlignment::Center)
+//!     .add(Label::new("embedded"))
+//!     .add(Label::new("gui"))
+//!     .add(Label::new("no_std"))
+//! ```
+
+use object_chain::Chain;
+
+use crate::{geometry::BoundingBox, widgets::layouts::linear::MainAxisAlignment};
+
+mod layout;
+
+pub use layout::FlowLayout;
+
+/// Flow layout.
+///
+/// Widgets are measured with at most the width available to the layout, then placed in lines. Each
+/// line is as tall as its tallest widget, and widgets are placed at the top of their line. The
+/// layout is as wide as its widest line, and as tall as all of its lines together.
+///
+/// If the available width is not limited, all widgets are placed in a single line.
+#[derive(Copy, Clone)]
+pub struct Flow {
+    horizontal_gap: u32,
+    vertical_gap: u32,
+    line_alignment: MainAxisAlignment,
+}
+
+impl Flow {
+    /// Creates a new, empty flow layout.
+    pub fn new() -> Self {
+        Self {
+            horizontal_gap: 0,
+            vertical_gap: 0,
+            line_alignment: MainAxisAlignment::Start,
+        }
+    }
+
+    /// Sets the space between widgets of the same line.
+    pub fn horizontal_gap(mut self, gap: u32) -> Self {
+        self.horizontal_gap = gap;
+        self
+    }
+
+    /// Sets the space between lines.
+    pub fn vertical_gap(mut self, gap: u32) -> Self {
+        self.vertical_gap = gap;
+        self
+    }
+
+    /// Sets how the free space of each line is distributed between its widgets.
+    ///
+    /// A layout with an alignment other than `Start` takes up all the available width.
+    pub fn line_alignment(mut self, alignment: MainAxisAlignment) -> Self {
+        self.line_alignment = alignment;
+        self
+    }
+
+    /// Adds the first widget to the layout.
+    pub fn add<W>(self, inner: W) -> FlowLayout<Chain<W>> {
+        FlowLayout {
+            widgets: Chain::new(inner),
+            bounds: BoundingBox::default(),
+            horizontal_gap: self.horizontal_gap,
+            vertical_gap: self.vertical_gap,
+            line_alignment: self.line_alignment,
+        }
+    }
+}
+
+impl Default for Flow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,10 +1,16 @@
 use object_chain::{Chain, ChainElement, Link};
 
 use crate::{
-    geometry::{measurement::MeasureSpec, BoundingBox, MeasuredSize, Position},
+    geometry::{
+        measurement::{MeasureConstraint, MeasureSpec},
+        BoundingBox, MeasuredSize, Position,
+    },
     input::event::InputEvent,
     state::WidgetState,
-    widgets::Widget,
+    widgets::{
+        layouts::{absolute::Anchor, frame::Layer},
+        Widget,
+    },
     Canvas, WidgetRenderer,
 };
 
@@ -15,82 +21,187 @@ pub struct FrameLayout<CE> {
 
 impl<CE> FrameLayout<CE>
 where
-    CE: ChainElement,
+    CE: FrameLayoutChainElement + ChainElement,
 {
-    pub fn add_layer<W>(self, inner: W) -> FrameLayout<Link<W, CE>> {
+    pub fn add_layer<W>(self, inner: W) -> FrameLayout<Link<Layer<W>, CE>>
+    where
+        W: Widget,
+    {
         FrameLayout {
-            widgets: self.widgets.append(inner),
+            widgets: self.widgets.append(Layer::new(inner)),
             bounds: self.bounds,
         }
     }
 }
 
+impl<W, CE> FrameLayout<Link<Layer<W>, CE>>
+where
+    W: Widget,
+    CE: FrameLayoutChainElement + ChainElement,
+{
+    /// Sets the alignment of the last added layer.
+    pub fn anchor(mut self, anchor: Anchor) -> Self {
+        self.widgets.object.anchor = anchor;
+        self
+    }
+
+    /// Sets the width of the last added layer, in percent of the layout.
+    pub fn width_percent(mut self, percent: u32) -> Self {
+        self.widgets.object.width_percent = Some(percent);
+        self
+    }
+
+    /// Sets the height of the last added layer, in percent of the layout.
+    pub fn height_percent(mut self, percent: u32) -> Self {
+        self.widgets.object.height_percent = Some(percent);
+        self
+    }
+
+    /// Lets input through the last added layer to the layers below it.
+    pub fn pass_through(mut self) -> Self {
+        self.widgets.object.pass_through = true;
+        self
+    }
+}
+
+impl<W> FrameLayout<Chain<Layer<W>>>
+where
+    W: Widget,
+{
+    /// Sets the alignment of the last added layer.
+    pub fn anchor(mut self, anchor: Anchor) -> Self {
+        self.widgets.object.anchor = anchor;
+        self
+    }
+
+    /// Sets the width of the last added layer, in percent of the layout.
+    pub fn width_percent(mut self, percent: u32) -> Self {
+        self.widgets.object.width_percent = Some(percent);
+        self
+    }
+
+    /// Sets the height of the last added layer, in percent of the layout.
+    pub fn height_percent(mut self, percent: u32) -> Self {
+        self.widgets.object.height_percent = Some(percent);
+        self
+    }
+
+    /// Lets input through the last added layer to the layers below it.
+    pub fn pass_through(mut self) -> Self {
+        self.widgets.object.pass_through = true;
+        self
+    }
+}
+
+pub trait FrameLayer {
+    fn anchor(&self) -> Anchor;
+
+    fn width_percent(&self) -> Option<u32>;
+
+    fn height_percent(&self) -> Option<u32>;
+
+    fn pass_through(&self) -> bool;
+
+    fn widget(&self) -> &dyn Widget;
+
+    fn widget_mut(&mut self) -> &mut dyn Widget;
+}
+
+impl<W> Layer<W>
+where
+    W: Widget,
+{
+    /// Returns whether the layer keeps `event` from reaching the layers below it.
+    fn blocks(&self, event: InputEvent) -> bool {
+        match event.position() {
+            Some(position) => !self.pass_through && self.inner.hit_test(position).is_some(),
+            None => false,
+        }
+    }
+}
+
+impl<W> FrameLayer for Layer<W>
+where
+    W: Widget,
+{
+    fn anchor(&self) -> Anchor {
+        self.anchor
+    }
+
+    fn width_percent(&self) -> Option<u32> {
+        self.width_percent
+    }
+
+    fn height_percent(&self) -> Option<u32> {
+        self.height_percent
+    }
+
+    fn pass_through(&self) -> bool {
+        self.pass_through
+    }
+
+    fn widget(&self) -> &dyn Widget {
+        &self.inner
+    }
+
+    fn widget_mut(&mut self) -> &mut dyn Widget {
+        &mut self.inner
+    }
+}
+
 pub trait FrameLayoutChainElement {
-    fn at(&self, index: usize) -> &dyn Widget;
+    fn at(&self, index: usize) -> &dyn FrameLayer;
 
-    fn at_mut(&mut self, index: usize) -> &mut dyn Widget;
+    fn at_mut(&mut self, index: usize) -> &mut dyn FrameLayer;
 
     fn test_input(&mut self, event: InputEvent) -> Option<usize>;
 
     fn count_widgets(&self) -> usize;
 
-    fn measure(&mut self, spec: MeasureSpec) -> MeasuredSize;
-
-    fn arrange(&mut self, position: Position);
-
     fn on_state_changed(&mut self, state: WidgetState);
 
     fn update(&mut self);
 }
 
-impl<W> FrameLayoutChainElement for Chain<W>
+impl<W> FrameLayoutChainElement for Chain<Layer<W>>
 where
     W: Widget,
 {
-    fn at(&self, index: usize) -> &dyn Widget {
+    fn at(&self, index: usize) -> &dyn FrameLayer {
         debug_assert!(index == 0);
 
         &self.object
     }
 
-    fn at_mut(&mut self, index: usize) -> &mut dyn Widget {
+    fn at_mut(&mut self, index: usize) -> &mut dyn FrameLayer {
         debug_assert!(index == 0);
 
         &mut self.object
     }
 
-    fn measure(&mut self, spec: MeasureSpec) -> MeasuredSize {
-        self.object.measure(spec);
-        self.object.bounding_box().size
-    }
-
     fn test_input(&mut self, event: InputEvent) -> Option<usize> {
-        self.object.test_input(event)
+        self.object.inner.test_input(event)
     }
 
     fn count_widgets(&self) -> usize {
-        self.object.children() + 1
-    }
-
-    fn arrange(&mut self, position: Position) {
-        self.object.arrange(position);
+        self.object.inner.children() + 1
     }
 
     fn on_state_changed(&mut self, state: WidgetState) {
-        self.object.on_state_changed(state);
+        self.object.inner.on_state_changed(state);
     }
 
     fn update(&mut self) {
-        self.object.update();
+        self.object.inner.update();
     }
 }
 
-impl<W, CE> FrameLayoutChainElement for Link<W, CE>
+impl<W, CE> FrameLayoutChainElement for Link<Layer<W>, CE>
 where
     W: Widget,
     CE: FrameLayoutChainElement + ChainElement,
 {
-    fn at(&self, index: usize) -> &dyn Widget {
+    fn at(&self, index: usize) -> &dyn FrameLayer {
         if index == Link::len(self) - 1 {
             return &self.object;
         }
@@ -98,7 +209,7 @@ where
         self.parent.at(index)
     }
 
-    fn at_mut(&mut self, index: usize) -> &mut dyn Widget {
+    fn at_mut(&mut self, index: usize) -> &mut dyn FrameLayer {
         if index == Link::len(self) - 1 {
             return &mut self.object;
         }
@@ -106,48 +217,38 @@ where
         self.parent.at_mut(index)
     }
 
-    fn measure(&mut self, spec: MeasureSpec) -> MeasuredSize {
-        let inner_size = self.parent.measure(spec);
-        self.object.measure(spec);
-        let size = self.object.bounding_box().size;
+    fn test_input(&mut self, event: InputEvent) -> Option<usize> {
+        // We need to pass to object first because it is the "upper" layer
+        if let Some(idx) = self.object.inner.test_input(event) {
+            return Some(idx + self.parent.count_widgets());
+        }
 
-        MeasuredSize {
-            width: inner_size.width.max(size.width),
-            height: inner_size.height.max(size.height),
+        if self.object.blocks(event) {
+            return None;
         }
-    }
 
-    fn test_input(&mut self, event: InputEvent) -> Option<usize> {
-        // We need to pass to object first because it is the "upper" layer
-        self.object
-            .test_input(event)
-            .map(|idx| idx + self.parent.count_widgets())
-            .or_else(|| self.parent.test_input(event))
+        self.parent.test_input(event)
     }
 
     fn count_widgets(&self) -> usize {
-        self.object.children() + 1 + self.parent.count_widgets()
-    }
-
-    fn arrange(&mut self, position: Position) {
-        self.parent.arrange(position);
-        self.object.arrange(position);
+        self.object.inner.children() + 1 + self.parent.count_widgets()
     }
 
     fn on_state_changed(&mut self, state: WidgetState) {
-        self.object.on_state_changed(state);
+        self.object.inner.on_state_changed(state);
         self.parent.on_state_changed(state);
     }
 
     fn update(&mut self) {
-        self.object.update();
+        self.object.inner.update();
         self.parent.update();
     }
 }
 
-impl<C, W> WidgetRenderer<C> for Chain<W>
+impl<C, W> WidgetRenderer<C> for Chain<Layer<W>>
 where
-    W: Widget + WidgetRenderer<C>,
+    W: Widget,
+    Layer<W>: WidgetRenderer<C>,
     C: Canvas,
 {
     fn draw(&mut self, canvas: &mut C) -> Result<(), C::Error> {
@@ -155,10 +256,11 @@ where
     }
 }
 
-impl<C, W, CE> WidgetRenderer<C> for Link<W, CE>
+impl<C, W, CE> WidgetRenderer<C> for Link<Layer<W>, CE>
 where
-    W: Widget + WidgetRenderer<C>,
+    W: Widget,
     CE: FrameLayoutChainElement + ChainElement + WidgetRenderer<C>,
+    Layer<W>: WidgetRenderer<C>,
     C: Canvas,
 {
     fn draw(&mut self, canvas: &mut C) -> Result<(), C::Error> {
@@ -167,6 +269,10 @@ where
     }
 }
 
+fn percent_of(size: u32, percent: u32) -> u32 {
+    (size as u64 * percent as u64 / 100) as u32
+}
+
 impl<CE> FrameLayout<CE>
 where
     CE: ChainElement + FrameLayoutChainElement,
@@ -175,7 +281,7 @@ where
         let children = self.widgets.len();
 
         for i in 0..children {
-            let child = self.widgets.at(i);
+            let child = self.widgets.at(i).widget();
             let grandchildren = child.children();
             if idx <= grandchildren {
                 return Some((i, idx));
@@ -197,9 +303,9 @@ where
         let mut children = index;
 
         for i in 0..self.widgets.len() {
-            let widget = self.widgets.at_mut(i);
+            let widget = self.widgets.at_mut(i).widget_mut();
 
-            widget.attach(parent, children + i);
+            widget.attach(parent, children + i + 1);
             children += widget.children();
         }
     }
@@ -213,13 +319,95 @@ where
     }
 
     fn measure(&mut self, measure_spec: MeasureSpec) {
-        self.bounds.size = self.widgets.measure(measure_spec);
+        let mut size = MeasuredSize {
+            width: 0,
+            height: 0,
+        };
+        let mut fill = false;
+
+        // Anchored layers take their natural size, otherwise an `Exactly` constraint would stretch
+        // them over the whole frame and there would be nothing left to place.
+        let anchored_spec = MeasureSpec {
+            width: measure_spec.width.to_at_most(),
+            height: measure_spec.height.to_at_most(),
+        };
+        let layer_spec = |anchor| {
+            if anchor == Anchor::TopLeft {
+                measure_spec
+            } else {
+                anchored_spec
+            }
+        };
+
+        // Measure the layers without percentages first, so that they can serve as reference
+        for i in 0..self.widgets.len() {
+            let layer = self.widgets.at_mut(i);
+            if layer.anchor() != Anchor::TopLeft {
+                fill = true;
+            }
+            if layer.width_percent().is_some() || layer.height_percent().is_some() {
+                fill = true;
+                continue;
+            }
+
+            let spec = layer_spec(layer.anchor());
+            let widget = layer.widget_mut();
+            widget.measure(spec);
+
+            let widget_size = widget.bounding_box().size;
+            size.width = size.width.max(widget_size.width);
+            size.height = size.height.max(widget_size.height);
+        }
+
+        let reference = MeasuredSize {
+            width: measure_spec.width.largest().unwrap_or(size.width),
+            height: measure_spec.height.largest().unwrap_or(size.height),
+        };
+
+        for i in 0..self.widgets.len() {
+            let layer = self.widgets.at_mut(i);
+            let (width_percent, height_percent) = (layer.width_percent(), layer.height_percent());
+            if width_percent.is_none() && height_percent.is_none() {
+                continue;
+            }
+
+            let spec = layer_spec(layer.anchor());
+            let widget = layer.widget_mut();
+            widget.measure(MeasureSpec {
+                width: width_percent.map_or(spec.width, |percent| {
+                    MeasureConstraint::Exactly(percent_of(reference.width, percent))
+                }),
+                height: height_percent.map_or(spec.height, |percent| {
+                    MeasureConstraint::Exactly(percent_of(reference.height, percent))
+                }),
+            });
+
+            let widget_size = widget.bounding_box().size;
+            size.width = size.width.max(widget_size.width);
+            size.height = size.height.max(widget_size.height);
+        }
+
+        self.bounds.size = if fill {
+            MeasuredSize {
+                width: reference.width.max(size.width),
+                height: reference.height.max(size.height),
+            }
+        } else {
+            size
+        };
     }
 
     fn arrange(&mut self, position: Position) {
         self.bounds.position = position;
 
-        self.widgets.arrange(position);
+        for i in 0..self.widgets.len() {
+            let layer = self.widgets.at_mut(i);
+            let anchor = layer.anchor();
+
+            let widget = layer.widget_mut();
+            let size = widget.bounding_box().size;
+            widget.arrange(anchor.place(self.bounds, size, Position { x: 0, y: 0 }));
+        }
     }
 
     fn children(&self) -> usize {
@@ -229,7 +417,7 @@ where
     fn get_child(&self, idx: usize) -> &dyn Widget {
         let (child, grandchild) = self.locate(idx).unwrap();
 
-        let widget = self.widgets.at(child);
+        let widget = self.widgets.at(child).widget();
         if grandchild == 0 {
             widget
         } else {
@@ -240,7 +428,7 @@ where
     fn get_mut_child(&mut self, idx: usize) -> &mut dyn Widget {
         let (child, grandchild) = self.locate(idx).unwrap();
 
-        let widget = self.widgets.at_mut(child);
+        let widget = self.widgets.at_mut(child).widget_mut();
         if grandchild == 0 {
             widget
         } else {
@@ -249,7 +437,7 @@ where
     }
 
     fn parent_index(&self) -> usize {
-        self.widgets.at(0).parent_index()
+        self.widgets.at(0).widget().parent_index()
     }
 
     fn set_parent(&mut self, _index: usize) {}
@@ -262,6 +450,31 @@ where
         self.widgets.test_input(event).map(|idx| idx + 1)
     }
 
+    fn hit_test(&self, position: Position) -> Option<usize> {
+        let mut offset = self.widgets.count_widgets() + 1;
+
+        // Later layers are drawn on top, so they take precedence
+        for i in (0..self.widgets.len()).rev() {
+            let layer = self.widgets.at(i);
+            let widget = layer.widget();
+            offset -= widget.children() + 1;
+
+            if layer.pass_through() {
+                continue;
+            }
+
+            if let Some(idx) = widget.hit_test(position) {
+                return Some(offset + idx);
+            }
+        }
+
+        if self.bounds.contains(position) {
+            Some(0)
+        } else {
+            None
+        }
+    }
+
     fn on_state_changed(&mut self, state: WidgetState) {
         self.widgets.on_state_changed(state);
     }
@@ -1,18 +1,47 @@
 //! Frame layout.
 //!
 //! The Frame layout places widgets on top of each other.
+//!
+//! ## Layers
+//!
+//! Each layer can be aligned to one of the corners, edges or the center of the layout, and can be
+//! sized as a percentage of the layout, similar to how cells of a `LinearLayout` carry weight.
+//!
+//! ```rust,ignore
+//! Frame::new()
+//!     .add_layer(background)
+//!     .add_layer(dialog)
+//!     .width_percent(50)
+//!     .anchor(Anchor::Center)
+//! ```
 
 use object_chain::Chain;
 
-use crate::{geometry::BoundingBox, widgets::layouts::frame::layout::FrameLayout};
+use crate::{
+    geometry::BoundingBox,
+    widgets::{layouts::absolute::Anchor, Widget},
+    Canvas, WidgetRenderer,
+};
 
 mod layout;
 
+pub use layout::FrameLayout;
+
 /// Frame layout.
 ///
 /// This layout contains multiple widgets that are placed at the same area of the display.
 /// The layout will take up as much space as the largest widget inside it. Widgets are placed
-/// at the top left corner of the layout.
+/// at the top left corner of the layout, unless their layer is anchored elsewhere. Anchored layers
+/// are measured at their natural size, limited by the available space. A layout that contains
+/// anchored layers, or layers sized in percent, takes up all the available space.
+///
+/// Layers are opaque to input: pointer and touch events are delivered to the topmost layer under
+/// the pointer, and don't reach the layers below it, even if the topmost layer doesn't handle them.
+/// Layers marked as pass-through are ignored when looking for the target of such events.
+///
+/// Layers sized as a percentage of the layout are measured after the other layers. Percentages
+/// refer to the space available to the layout, or to the size of the other layers if the available
+/// space is not limited.
 pub struct Frame;
 impl Frame {
     /// Creates a new, empty frame layout.
@@ -21,10 +50,53 @@ impl Frame {
     }
 
     /// Adds a new widget on top of the previous layers.
-    pub fn add_layer<W>(self, inner: W) -> FrameLayout<Chain<W>> {
+    pub fn add_layer<W>(self, inner: W) -> FrameLayout<Chain<Layer<W>>>
+    where
+        W: Widget,
+    {
         FrameLayout {
-            widgets: Chain::new(inner),
+            widgets: Chain::new(Layer::new(inner)),
             bounds: BoundingBox::default(),
         }
     }
 }
+
+/// A single layer in a frame layout.
+pub struct Layer<W>
+where
+    W: Widget,
+{
+    pub anchor: Anchor,
+    /// The width of the layer, in percent of the layout.
+    pub width_percent: Option<u32>,
+    /// The height of the layer, in percent of the layout.
+    pub height_percent: Option<u32>,
+    /// Lets input through to the layers below, e.g. for decorative overlays.
+    pub pass_through: bool,
+    pub inner: W,
+}
+
+impl<W> Layer<W>
+where
+    W: Widget,
+{
+    fn new(inner: W) -> Self {
+        Self {
+            anchor: Anchor::TopLeft,
+            width_percent: None,
+            height_percent: None,
+            pass_through: false,
+            inner,
+        }
+    }
+}
+
+impl<C, W> WidgetRenderer<C> for Layer<W>
+where
+    W: Widget + WidgetRenderer<C>,
+    C: Canvas,
+{
+    fn draw(&mut self, canvas: &mut C) -> Result<(), C::Error> {
+        canvas.draw_clipped(self.inner.bounding_box(), &mut self.inner)
+    }
+}
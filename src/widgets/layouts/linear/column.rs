@@ -4,8 +4,8 @@ use crate::{
     geometry::{axis_order::Vertical, BoundingBox},
     widgets::{
         layouts::linear::{
-            layout::LinearLayout, private::LayoutDirection, Cell, CellWeight, ElementSpacing,
-            NoSpacing, NoWeight, WithSpacing,
+            layout::LinearLayout, private::LayoutDirection, Cell, CellWeight, CrossAxisAlignment,
+            ElementSpacing, MainAxisAlignment, NoSpacing, NoWeight, WithSpacing,
         },
         Widget,
     },
@@ -58,6 +58,8 @@ where
             bounds: BoundingBox::default(),
             widgets: Chain::new(widget),
             direction: self,
+            cross_axis_alignment: CrossAxisAlignment::Start,
+            main_axis_alignment: MainAxisAlignment::Start,
         }
     }
 }
@@ -10,7 +10,7 @@ use crate::{
     widgets::{
         layouts::linear::{
             private::{LayoutDirection, LinearLayoutChainElement},
-            Cell, NoWeight, Weight,
+            Cell, CrossAxisAlignment, MainAxisAlignment, NoWeight, Weight,
         },
         Widget,
     },
@@ -44,10 +44,18 @@ use crate::{
 ///  - The second cell will take up 40 px because it has a fixed height of 40.
 ///  - The third cell will take up 80 px because of weight 2.
 ///
+/// ## Alignment
+///
+/// After measurement, cells are positioned according to the [`MainAxisAlignment`] and
+/// [`CrossAxisAlignment`] of the layout. Cells with `Stretch` alignment are measured again, with the
+/// cross axis size of the layout.
+///
 pub struct LinearLayout<CE, L> {
     pub bounds: BoundingBox,
     pub widgets: CE,
     pub direction: L,
+    pub cross_axis_alignment: CrossAxisAlignment,
+    pub main_axis_alignment: MainAxisAlignment,
 }
 
 impl<CE, L> LinearLayout<CE, L>
@@ -66,7 +74,89 @@ where
             bounds: self.bounds,
             widgets: self.widgets.append(Cell::new(widget)),
             direction: self.direction,
+            cross_axis_alignment: self.cross_axis_alignment,
+            main_axis_alignment: self.main_axis_alignment,
+        }
+    }
+
+    /// Sets how cells are positioned along the cross axis.
+    pub fn cross_axis_alignment(mut self, alignment: CrossAxisAlignment) -> Self {
+        self.cross_axis_alignment = alignment;
+        self
+    }
+
+    /// Sets how the free space is distributed along the main axis.
+    pub fn main_axis_alignment(mut self, alignment: MainAxisAlignment) -> Self {
+        self.main_axis_alignment = alignment;
+        self
+    }
+
+    fn cell_alignment(&self, index: usize) -> CrossAxisAlignment {
+        self.widgets
+            .at(index)
+            .alignment()
+            .unwrap_or(self.cross_axis_alignment)
+    }
+
+    /// Returns the largest distance of the baseline from the top and from the bottom of the cells
+    /// that are aligned by their baseline.
+    fn baseline_extents(&self) -> (u32, u32)
+    where
+        L: LayoutDirection,
+    {
+        let mut above = 0;
+        let mut below = 0;
+
+        for i in 0..self.widgets.len() {
+            if self.cell_alignment(i) != CrossAxisAlignment::Baseline {
+                continue;
+            }
+
+            let widget = self.widgets.at(i).widget();
+            if let Some(baseline) = L::baseline(widget) {
+                above = above.max(baseline);
+                below =
+                    below.max(L::cross_axis_size(widget.bounding_box()).saturating_sub(baseline));
+            }
+        }
+
+        (above, below)
+    }
+
+    /// Sets the size of the layout and stretches the cells that need it.
+    fn finish_measure(&mut self, main_axis_size: u32, max_cross: u32, measure_spec: MeasureSpec)
+    where
+        L: LayoutDirection,
+    {
+        let (above, below) = self.baseline_extents();
+        let content_cross_axis_size = max_cross.max(above + below);
+
+        let cross_axis_spec = L::cross_axis_measure_spec(measure_spec);
+        let fill = (0..self.widgets.len()).any(|i| {
+            matches!(
+                self.cell_alignment(i),
+                CrossAxisAlignment::Center | CrossAxisAlignment::End | CrossAxisAlignment::Stretch
+            )
+        });
+        let cross_axis_size = if fill {
+            cross_axis_spec.largest().unwrap_or(content_cross_axis_size)
+        } else {
+            cross_axis_spec.apply_to_measured(content_cross_axis_size)
+        };
+
+        for i in 0..self.widgets.len() {
+            if self.cell_alignment(i) == CrossAxisAlignment::Stretch {
+                let widget = self.widgets.at_mut(i).widget_mut();
+                let main = L::main_axis_size(widget.bounding_box());
+
+                widget.measure(L::create_measure_spec(
+                    MeasureConstraint::Exactly(main),
+                    MeasureConstraint::Exactly(cross_axis_size),
+                ));
+            }
         }
+
+        self.bounds.size = L::create_measured_size(main_axis_size, cross_axis_size);
     }
 
     fn locate(&self, mut idx: usize) -> Option<(usize, usize)> {
@@ -103,10 +193,37 @@ where
                 parent: self.widgets.parent,
             },
             direction: self.direction,
+            cross_axis_alignment: self.cross_axis_alignment,
+            main_axis_alignment: self.main_axis_alignment,
         }
     }
 }
 
+impl<W, CW, CE, L> LinearLayout<Link<Cell<W, CW>, CE>, L>
+where
+    W: Widget,
+    CE: LinearLayoutChainElement + ChainElement,
+{
+    /// Sets the cross axis alignment of the most recently appended cell, overriding the alignment
+    /// of the layout.
+    pub fn align_cell(mut self, alignment: CrossAxisAlignment) -> Self {
+        self.widgets.object.alignment = Some(alignment);
+        self
+    }
+}
+
+impl<W, CW, L> LinearLayout<Chain<Cell<W, CW>>, L>
+where
+    W: Widget,
+{
+    /// Sets the cross axis alignment of the most recently appended cell, overriding the alignment
+    /// of the layout.
+    pub fn align_cell(mut self, alignment: CrossAxisAlignment) -> Self {
+        self.widgets.object.alignment = Some(alignment);
+        self
+    }
+}
+
 impl<W, L> LinearLayout<Chain<Cell<W, NoWeight>>, L>
 where
     W: Widget,
@@ -122,6 +239,8 @@ where
                 object: self.widgets.object.weight(weight),
             },
             direction: self.direction,
+            cross_axis_alignment: self.cross_axis_alignment,
+            main_axis_alignment: self.main_axis_alignment,
         }
     }
 }
@@ -138,7 +257,7 @@ where
         for i in 0..self.widgets.len() {
             let widget = self.widgets.at_mut(i).widget_mut();
 
-            widget.attach(parent, children + i);
+            widget.attach(parent, children + i + 1);
             children += widget.children();
         }
     }
@@ -170,11 +289,7 @@ where
 
                 total_fixed_main_axis_size += (count as u32 - 1) * self.direction.element_spacing();
 
-                // TODO this is almost the same as the bottom of the function. Should deduplicate.
-                self.bounds.size = L::create_measured_size(
-                    total_fixed_main_axis_size,
-                    L::cross_axis_measure_spec(measure_spec).apply_to_measured(max_cross),
-                );
+                self.finish_measure(total_fixed_main_axis_size, max_cross, measure_spec);
 
                 return;
             }
@@ -237,21 +352,55 @@ where
             }
         }
 
-        self.bounds.size = L::create_measured_size(
-            if total_weight == 0 {
+        let main_axis_size =
+            if total_weight == 0 && self.main_axis_alignment == MainAxisAlignment::Start {
                 total_fixed_main_axis_size
             } else {
                 max_main_axis_size
-            },
-            L::cross_axis_measure_spec(measure_spec).apply_to_measured(max_cross),
-        );
+            };
+
+        self.finish_measure(main_axis_size, max_cross, measure_spec);
     }
 
     fn arrange(&mut self, position: Position) {
         self.bounds.position = position;
 
-        self.widgets
-            .arrange(position, self.direction, self.direction.element_spacing());
+        let count = self.widgets.len() as u32;
+        let spacing = self.direction.element_spacing();
+        let cross_axis_size = L::cross_axis_size(self.bounds) as i32;
+
+        let mut content_size = (count - 1) * spacing;
+        for i in 0..self.widgets.len() {
+            content_size += L::main_axis_size(self.widgets.at(i).widget().bounding_box());
+        }
+        let free_space = L::main_axis_size(self.bounds).saturating_sub(content_size);
+
+        let (mut offset, gap, mut remainder) =
+            self.main_axis_alignment.distribute(free_space, count);
+
+        let (baseline, _) = self.baseline_extents();
+
+        for i in 0..self.widgets.len() {
+            let alignment = self.cell_alignment(i);
+            let widget = self.widgets.at_mut(i).widget_mut();
+
+            let bounds = widget.bounding_box();
+            let free_cross_space = cross_axis_size - L::cross_axis_size(bounds) as i32;
+            let cross_offset = match alignment {
+                CrossAxisAlignment::Start | CrossAxisAlignment::Stretch => 0,
+                CrossAxisAlignment::Center => free_cross_space / 2,
+                CrossAxisAlignment::End => free_cross_space,
+                CrossAxisAlignment::Baseline => {
+                    L::baseline(widget).map_or(0, |b| baseline as i32 - b as i32)
+                }
+            };
+
+            widget.arrange(L::create_position(position, offset as i32, cross_offset));
+
+            let rem = if remainder > 0 { 1 } else { 0 };
+            remainder -= rem;
+            offset += L::main_axis_size(bounds) + spacing + gap + rem;
+        }
     }
 
     fn children(&self) -> usize {
@@ -294,6 +443,25 @@ where
         self.widgets.test_input(event).map(|idx| idx + 1)
     }
 
+    fn hit_test(&self, position: Position) -> Option<usize> {
+        let mut offset = 1;
+
+        for i in 0..self.widgets.len() {
+            let widget = self.widgets.at(i).widget();
+
+            if let Some(idx) = widget.hit_test(position) {
+                return Some(offset + idx);
+            }
+            offset += widget.children() + 1;
+        }
+
+        if self.bounds.contains(position) {
+            Some(0)
+        } else {
+            None
+        }
+    }
+
     fn on_state_changed(&mut self, state: WidgetState) {
         self.widgets.on_state_changed(state);
     }
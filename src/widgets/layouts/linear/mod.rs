@@ -2,6 +2,26 @@
 //!
 //! Arrange widgets in a Row or a Column.
 //!
+//! ## Alignment
+//!
+//! Similar to CSS flexbox, cells can be aligned along both axes of the layout. The main axis is the
+//! direction the cells are stacked in (horizontal in a `Row`, vertical in a `Column`), the cross axis
+//! is perpendicular to it.
+//!
+//!  - [`MainAxisAlignment`] distributes the free space of the layout between the cells.
+//!  - [`CrossAxisAlignment`] positions each cell within the height of a `Row`, or the width of a
+//!    `Column`. It can be set for the whole layout, and overridden for individual cells.
+//!
+//! ```rust,ignore
+//! Row::new()
+//!     .add(Label::new("Volume"))
+//!     .add(slider)
+//!     .weight(1)
+//!     .add(button)
+//!     .align_cell(CrossAxisAlignment::Stretch)
+//!     .cross_axis_alignment(CrossAxisAlignment::Center)
+//! ```
+//!
 
 pub use object_chain;
 
@@ -17,6 +37,79 @@ pub use row::Row;
 
 mod private;
 
+/// Positioning of cells along the cross axis of a linear layout.
+///
+/// A layout that contains `Center`, `End` or `Stretch` aligned cells takes up all the available
+/// space along its cross axis.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CrossAxisAlignment {
+    /// Cells are placed at the top of a `Row` or at the left side of a `Column`.
+    Start,
+
+    /// Cells are centered.
+    Center,
+
+    /// Cells are placed at the bottom of a `Row` or at the right side of a `Column`.
+    End,
+
+    /// Cells fill the layout.
+    Stretch,
+
+    /// The text baselines of the cells are lined up. Widgets without a baseline are aligned by
+    /// their bottom edge.
+    ///
+    /// Only applies to `Row`. Cells of a `Column` are placed at the start instead.
+    Baseline,
+}
+
+/// Distribution of free space along the main axis of a linear layout.
+///
+/// A layout with a `MainAxisAlignment` other than `Start` takes up all the available space along its
+/// main axis. Free space only exists if none of the cells have weight.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MainAxisAlignment {
+    /// Cells are packed at the start of the layout.
+    Start,
+
+    /// Cells are packed at the center of the layout.
+    Center,
+
+    /// Cells are packed at the end of the layout.
+    End,
+
+    /// Free space is divided evenly between the cells. The first and last cell are placed at the
+    /// edges of the layout.
+    SpaceBetween,
+
+    /// Free space is divided evenly around the cells. The space at the edges of the layout is half
+    /// of the space between cells.
+    SpaceAround,
+}
+
+impl MainAxisAlignment {
+    /// Divides `free_space` around `count` cells.
+    ///
+    /// Returns the space before the first cell, the extra space after each cell, and the number of
+    /// stray pixels left over, which are handed out one by one to the first gaps.
+    pub(crate) fn distribute(self, free_space: u32, count: u32) -> (u32, u32, u32) {
+        let (offset, gaps) = match self {
+            MainAxisAlignment::Start => (0, 0),
+            MainAxisAlignment::Center => (free_space / 2, 0),
+            MainAxisAlignment::End => (free_space, 0),
+            MainAxisAlignment::SpaceBetween if count > 1 => (0, count - 1),
+            MainAxisAlignment::SpaceBetween => (0, 0),
+            MainAxisAlignment::SpaceAround => (free_space / (2 * count), count - 1),
+        };
+        let gap_space = free_space.saturating_sub(2 * offset);
+
+        (
+            offset,
+            gap_space.checked_div(gaps).unwrap_or(0),
+            gap_space.checked_rem(gaps).unwrap_or(0),
+        )
+    }
+}
+
 #[doc(hidden)]
 #[derive(Copy, Clone)]
 pub struct NoSpacing;
@@ -73,12 +166,14 @@ impl CellWeight for Weight {
 
 /// A single cell in a linear layout.
 ///
-/// Cells wrap widgets and provide a method to specify cell weight.
+/// Cells wrap widgets and provide methods to specify cell weight and alignment.
 pub struct Cell<W, CW = NoWeight>
 where
     W: Widget,
 {
     pub weight: CW,
+    /// Overrides the cross axis alignment of the layout for this cell.
+    pub alignment: Option<CrossAxisAlignment>,
     pub inner: W,
 }
 
@@ -90,6 +185,7 @@ where
         Self {
             inner,
             weight: NoWeight,
+            alignment: None,
         }
     }
 
@@ -101,10 +197,22 @@ where
         Cell {
             inner: self.inner,
             weight: Weight::new(weight),
+            alignment: self.alignment,
         }
     }
 }
 
+impl<W, CW> Cell<W, CW>
+where
+    W: Widget,
+{
+    /// Sets the cross axis alignment of the cell.
+    pub fn align(mut self, alignment: CrossAxisAlignment) -> Self {
+        self.alignment = Some(alignment);
+        self
+    }
+}
+
 impl<C, W, CW> WidgetRenderer<C> for Cell<W, CW>
 where
     W: Widget + WidgetRenderer<C>,
@@ -112,6 +220,7 @@ where
     CW: CellWeight,
 {
     fn draw(&mut self, canvas: &mut C) -> Result<(), C::Error> {
-        self.inner.draw(canvas)
+        // Keep children from drawing over their siblings
+        canvas.draw_clipped(self.inner.bounding_box(), &mut self.inner)
     }
 }
@@ -9,7 +9,7 @@ use crate::{
     input::event::InputEvent,
     state::WidgetState,
     widgets::{
-        layouts::linear::{Cell, CellWeight},
+        layouts::linear::{Cell, CellWeight, CrossAxisAlignment},
         Widget,
     },
     Canvas, Position, WidgetRenderer,
@@ -52,22 +52,28 @@ pub trait LayoutDirection: Copy {
         }
     }
 
-    fn arrange(pos: Position, bb: BoundingBox, spacing: u32) -> Position {
-        let increment = Self::main_axis_size(bb) + spacing;
-        let (x, y) = <Self::AxisOrder as AxisOrder>::merge(increment as i32, 0);
+    fn create_position(origin: Position, main: i32, cross: i32) -> Position {
+        let (x, y) = <Self::AxisOrder as AxisOrder>::merge(main, cross);
 
         Position {
-            x: pos.x + x,
-            y: pos.y + y,
+            x: origin.x + x,
+            y: origin.y + y,
         }
     }
 
+    /// Returns the cross axis position of `widget`'s baseline, if baseline alignment is supported.
+    fn baseline(_widget: &dyn Widget) -> Option<u32> {
+        None
+    }
+
     fn element_spacing(&self) -> u32;
 }
 
 pub trait LinearLayoutCell {
     fn weight(&self) -> u32;
 
+    fn alignment(&self) -> Option<CrossAxisAlignment>;
+
     fn widget(&self) -> &dyn Widget;
 
     fn widget_mut(&mut self) -> &mut dyn Widget;
@@ -82,6 +88,10 @@ where
         self.weight.weight()
     }
 
+    fn alignment(&self) -> Option<CrossAxisAlignment> {
+        self.alignment
+    }
+
     fn widget(&self) -> &dyn Widget {
         &self.inner
     }
@@ -100,10 +110,6 @@ pub trait LinearLayoutChainElement {
 
     fn count_widgets(&self) -> usize;
 
-    fn arrange<L>(&mut self, position: Position, direction: L, spacing: u32) -> Position
-    where
-        L: LayoutDirection;
-
     fn on_state_changed(&mut self, state: WidgetState);
 
     fn update(&mut self);
@@ -134,15 +140,6 @@ where
         self.object.inner.children() + 1
     }
 
-    fn arrange<L>(&mut self, position: Position, _direction: L, spacing: u32) -> Position
-    where
-        L: LayoutDirection,
-    {
-        self.object.inner.arrange(position);
-
-        L::arrange(position, self.object.inner.bounding_box(), spacing)
-    }
-
     fn on_state_changed(&mut self, state: WidgetState) {
         self.object.inner.on_state_changed(state);
     }
@@ -187,17 +184,6 @@ where
         self.object.inner.children() + 1 + self.parent.count_widgets()
     }
 
-    fn arrange<L>(&mut self, position: Position, direction: L, spacing: u32) -> Position
-    where
-        L: LayoutDirection,
-    {
-        let next_pos = self.parent.arrange(position, direction, spacing);
-
-        self.object.inner.arrange(next_pos);
-
-        L::arrange(next_pos, self.object.inner.bounding_box(), spacing)
-    }
-
     fn on_state_changed(&mut self, state: WidgetState) {
         self.object.inner.on_state_changed(state);
         self.parent.on_state_changed(state);
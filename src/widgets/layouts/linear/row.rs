@@ -4,8 +4,8 @@ use crate::{
     geometry::{axis_order::Horizontal, BoundingBox},
     widgets::{
         layouts::linear::{
-            layout::LinearLayout, private::LayoutDirection, Cell, CellWeight, ElementSpacing,
-            NoSpacing, NoWeight, WithSpacing,
+            layout::LinearLayout, private::LayoutDirection, Cell, CellWeight, CrossAxisAlignment,
+            ElementSpacing, MainAxisAlignment, NoSpacing, NoWeight, WithSpacing,
         },
         Widget,
     },
@@ -26,6 +26,14 @@ where
     fn element_spacing(&self) -> u32 {
         self.0.spacing()
     }
+
+    fn baseline(widget: &dyn Widget) -> Option<u32> {
+        Some(
+            widget
+                .baseline()
+                .unwrap_or_else(|| widget.bounding_box().size.height),
+        )
+    }
 }
 
 impl Row {
@@ -58,6 +66,8 @@ where
             bounds: BoundingBox::default(),
             widgets: Chain::new(widget),
             direction: self,
+            cross_axis_alignment: CrossAxisAlignment::Start,
+            main_axis_alignment: MainAxisAlignment::Start,
         }
     }
 }
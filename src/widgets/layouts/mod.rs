@@ -1,4 +1,6 @@
 //! Layout containers
 
+pub mod absolute;
+pub mod flow;
 pub mod frame;
 pub mod linear;
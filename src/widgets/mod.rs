@@ -60,6 +60,10 @@
 //! If we have supplied the compiler with enough information, it will be able to tell us the exact
 //! return type we need to paste in place of the `_`.
 //!
+//! The types of layouts grow with every widget added to them. The [`gui!`](crate::gui) macro can
+//! build a layout from nested blocks, and can declare a type alias for it, so only the types of
+//! the widgets inside the layout need to be spelled out.
+//!
 
 use crate::{
     geometry::{measurement::MeasureSpec, BoundingBox, Position},
@@ -70,11 +74,14 @@ use crate::{
 pub mod background;
 pub mod border;
 pub mod button;
+pub mod calibration;
+pub mod enabled;
 pub mod fill;
 pub mod graphical;
 pub mod label;
 pub mod layouts;
 pub mod scroll;
+pub mod size_constraint;
 pub mod slider;
 pub mod spacing;
 pub mod text_block;
@@ -82,8 +89,15 @@ pub mod text_box;
 pub mod toggle;
 pub mod utils;
 pub mod visibility;
+pub mod zoom;
 
 pub trait Widget {
+    /// Tells the widget its position in the widget tree.
+    ///
+    /// `index` is the index of the widget itself, as used by [`Window::widget`](crate::Window::widget)
+    /// and the input controller, so the contained widgets start at `index + 1`. `parent` is the
+    /// index of the closest widget that handles input on behalf of its contents, like a `Button`.
+    /// Layouts and decorators pass their own `parent` on to the widgets they contain.
     fn attach(&mut self, parent: usize, index: usize) {
         debug_assert!(index == 0 || parent != index);
         debug_assert!(
@@ -131,6 +145,21 @@ pub trait Widget {
         None
     }
 
+    /// Returns the index of the innermost widget under `position`, relative to this widget.
+    ///
+    /// `Some(0)` refers to the widget itself.
+    fn hit_test(&self, position: Position) -> Option<usize> {
+        debug_assert!(
+            self.children() == 0,
+            "Hit testing must be implemented by non-leaf widgets"
+        );
+        if self.bounding_box().contains(position) {
+            Some(0)
+        } else {
+            None
+        }
+    }
+
     fn handle_input(&mut self, _ctxt: InputContext, _event: InputEvent) -> bool {
         false
     }
@@ -142,4 +171,15 @@ pub trait Widget {
     }
 
     fn reset_changed(&mut self) {}
+
+    /// Returns the text the widget displays, if any. Used to look up widgets, e.g. in tests.
+    fn text(&self) -> Option<&str> {
+        None
+    }
+
+    /// Returns the distance of the widget's text baseline from the top of its bounding box, if the
+    /// widget has one. Used to line up text in a `Row`.
+    fn baseline(&self) -> Option<u32> {
+        None
+    }
 }
@@ -8,14 +8,22 @@ use crate::{
         BoundingBox, MeasuredSize, Position, PositionDelta,
     },
     input::{
-        controller::InputContext,
+        controller::{EventPhase, InputContext},
         event::{InputEvent, PointerEvent, ScrollEvent},
     },
-    state::{State, WidgetState},
+    state::{
+        enabled::{Enabled, EnabledStateGroup},
+        State, WidgetState,
+    },
     state_group,
     widgets::{utils::WidgetDataHolder, Widget},
+    Canvas, WidgetRenderer,
 };
 
+/// The distance, in pixels along the scroll direction, a drag that started on a child widget needs
+/// to travel before the `Scroll` takes it over.
+const DRAG_THRESHOLD: i32 = 4;
+
 #[derive(Debug)]
 pub struct ScrollData {
     /// The current scroll position.
@@ -168,6 +176,8 @@ pub struct ScrollFields<W, SD, D> {
     pub bounds: BoundingBox,
     pub direction: SD,
     pub state: WidgetState,
+    /// The widget is inactive if this is `false`, or if it is inside a disabled `Enabled` widget.
+    pub active: bool,
     pub last_pointer_pos: Option<Position>,
     pub on_scroll_changed: fn(&mut D, ScrollData),
     pub offset_target: Option<i32>,
@@ -199,7 +209,19 @@ where
     W: Widget,
 {
     pub fn set_active(&mut self, active: bool) {
-        if active {
+        self.active = active;
+        self.update_active_state();
+    }
+
+    fn inherit_state(&mut self, parent: WidgetState) {
+        if self.state.inherit(parent, EnabledStateGroup) {
+            self.inner.on_state_changed(self.state);
+            self.update_active_state();
+        }
+    }
+
+    fn update_active_state(&mut self) {
+        if self.active && self.state.has_state(Enabled) {
             self.change_state(Scroll::STATE_ACTIVE);
         } else {
             self.change_state(Scroll::STATE_INACTIVE);
@@ -253,6 +275,7 @@ where
                 bounds: BoundingBox::default(),
                 direction: Horizontal { offset: 0 },
                 state: WidgetState::default(),
+                active: true,
                 last_pointer_pos: None,
                 on_scroll_changed: |_, _| (),
                 offset_target: None,
@@ -276,6 +299,7 @@ where
                 bounds: BoundingBox::default(),
                 direction: Vertical { offset: 0 },
                 state: WidgetState::default(),
+                active: true,
                 last_pointer_pos: None,
                 on_scroll_changed: |_, _| (),
                 offset_target: None,
@@ -341,6 +365,7 @@ where
                 bounds: self.fields.bounds,
                 direction: self.fields.direction,
                 state: self.fields.state,
+                active: self.fields.active,
                 last_pointer_pos: self.fields.last_pointer_pos,
                 on_scroll_changed: |_, _| (),
                 offset_target: self.fields.offset_target,
@@ -385,6 +410,49 @@ where
         self.update_scroll_data();
     }
 
+    /// Scrolls by the distance the pointer moved since the last pointer event.
+    fn drag_to(&mut self, position: Position) {
+        self.fling_controller.stop_fling();
+        let delta = if let Some(last) = self.fields.last_pointer_pos {
+            let delta = last - position;
+            self.change_offset(delta);
+            SD::AxisOrder::main_axis(delta.x, delta.y)
+        } else {
+            0
+        };
+        self.fling_controller.set_fling_delta(delta);
+        self.fields.last_pointer_pos = Some(position);
+    }
+
+    /// Handles events in the capture phase, before they reach our children.
+    fn intercept_input(&mut self, event: InputEvent) -> bool {
+        match event {
+            InputEvent::PointerEvent(position, PointerEvent::Down) => {
+                // Touching the content stops the fling, but the child still gets the event
+                self.fling_controller.stop_fling();
+                self.fields.last_pointer_pos = Some(position);
+                false
+            }
+
+            InputEvent::PointerEvent(position, PointerEvent::Drag) => {
+                let moved = self.fields.last_pointer_pos.map_or(0, |last| {
+                    let delta = last - position;
+                    SD::AxisOrder::main_axis(delta.x, delta.y).abs()
+                });
+
+                if moved >= DRAG_THRESHOLD {
+                    // Take the drag away from our child
+                    self.drag_to(position);
+                    true
+                } else {
+                    false
+                }
+            }
+
+            _ => false,
+        }
+    }
+
     fn update_scroll_data(&mut self) {
         // Clamp the offset.
         let child_size = self.fields.inner.bounding_box().size;
@@ -621,12 +689,31 @@ where
         // touch should drag the inner widgets, mouse probably shouldn't
     }
 
-    fn handle_input(&mut self, _ctxt: InputContext, event: InputEvent) -> bool {
+    fn hit_test(&self, position: Position) -> Option<usize> {
+        // The parts of the inner widget that are scrolled out of view can't be hit
+        if !self.bounding_box().contains(position) {
+            return None;
+        }
+
+        self.fields
+            .inner
+            .hit_test(position)
+            .map(|idx| idx + 1)
+            .or(Some(0))
+    }
+
+    fn handle_input(&mut self, ctxt: InputContext, event: InputEvent) -> bool {
         if self.fields.state.has_state(Scroll::STATE_INACTIVE) {
             return false;
         }
 
+        if ctxt.phase() == EventPhase::Capture {
+            return self.intercept_input(event);
+        }
+
         let hovered = self.fields.state.has_state(Scroll::STATE_HOVERED);
+        // Pointer events bubbling up from our children aren't drags of the scroll area.
+        let targeted = ctxt.is_target();
         match event {
             InputEvent::ScrollEvent(ScrollEvent::HorizontalScroll(dx)) => {
                 self.change_offset(PositionDelta { x: -dx, y: 0 });
@@ -644,7 +731,17 @@ where
                 self.fields.offset_target = None;
             }
 
-            InputEvent::PointerEvent(position, evt) if hovered => {
+            InputEvent::PointerEvent(_, PointerEvent::Enter) => {
+                self.fields.change_state(Scroll::STATE_HOVERED);
+                return false;
+            }
+
+            InputEvent::PointerEvent(_, PointerEvent::Leave) => {
+                self.fields.change_state(Scroll::STATE_IDLE);
+                return false;
+            }
+
+            InputEvent::PointerEvent(position, evt) if hovered && targeted => {
                 self.fields.last_pointer_pos = match evt {
                     PointerEvent::Down => {
                         self.fling_controller.stop_fling();
@@ -656,15 +753,7 @@ where
                     }
 
                     PointerEvent::Drag => {
-                        self.fling_controller.stop_fling();
-                        let delta = if let Some(last) = self.fields.last_pointer_pos {
-                            let delta = last - position;
-                            self.change_offset(last - position);
-                            SD::AxisOrder::main_axis(delta.x, delta.y)
-                        } else {
-                            0
-                        };
-                        self.fling_controller.set_fling_delta(delta);
+                        self.drag_to(position);
 
                         Some(position)
                     }
@@ -685,11 +774,24 @@ where
         true
     }
 
-    fn on_state_changed(&mut self, _state: WidgetState) {
-        // don't react to parent's state change
+    fn on_state_changed(&mut self, state: WidgetState) {
+        // only react to a parent being disabled
+        self.fields.inherit_state(state);
     }
 
     fn is_selectable(&self) -> bool {
         false
     }
 }
+
+impl<C, W, SD, D> WidgetRenderer<C> for Scroll<W, SD, D>
+where
+    W: Widget + WidgetRenderer<C>,
+    SD: ScrollDirection,
+    D: WidgetData,
+    C: Canvas,
+{
+    fn draw(&mut self, canvas: &mut C) -> Result<(), C::Error> {
+        canvas.draw_clipped(self.fields.bounds, &mut self.fields.inner)
+    }
+}
@@ -0,0 +1,327 @@
+//! Limit the size of the contained widget
+//!
+//! `SizeConstraint` sets lower and upper bounds for the width and height of a widget, and may keep
+//! the widget at a fixed aspect ratio.
+//!
+//! ```rust,ignore
+//! // A button that is at least 48 px wide
+//! SizeConstraint::new(button).min_width(48)
+//!
+//! // A list that never exceeds 120 px in height
+//! SizeConstraint::new(list).max_height(120)
+//! ```
+//!
+//! The limits take precedence over the constraints received from the parent: a widget with a
+//! minimum width is at least that wide, even if its parent offers less space.
+
+use crate::{
+    data::WidgetData,
+    geometry::{
+        measurement::{MeasureConstraint, MeasureSpec},
+        BoundingBox, MeasuredSize, Position,
+    },
+    state::WidgetState,
+    widgets::{
+        utils::{
+            decorator::WidgetDecorator,
+            wrapper::{Wrapper, WrapperBindable},
+        },
+        Widget,
+    },
+    Canvas, WidgetRenderer,
+};
+
+/// Lower and upper bounds of a size along one axis.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SizeLimits {
+    pub min: u32,
+    pub max: u32,
+}
+
+impl SizeLimits {
+    pub const UNLIMITED: SizeLimits = SizeLimits {
+        min: 0,
+        max: u32::MAX,
+    };
+
+    pub fn clamp(self, size: u32) -> u32 {
+        size.min(self.max).max(self.min)
+    }
+
+    /// Restricts `constraint` to the limits.
+    fn constrain(self, constraint: MeasureConstraint) -> MeasureConstraint {
+        if self.min == self.max {
+            return MeasureConstraint::Exactly(self.min);
+        }
+
+        match constraint {
+            MeasureConstraint::Exactly(size) => MeasureConstraint::Exactly(self.clamp(size)),
+            MeasureConstraint::AtMost(size) => MeasureConstraint::AtMost(self.clamp(size)),
+            MeasureConstraint::Unspecified if self.max == u32::MAX => {
+                MeasureConstraint::Unspecified
+            }
+            MeasureConstraint::Unspecified => MeasureConstraint::AtMost(self.max),
+        }
+    }
+}
+
+impl Default for SizeLimits {
+    fn default() -> Self {
+        Self::UNLIMITED
+    }
+}
+
+pub struct SizeConstraint<W> {
+    pub inner: W,
+    pub width: SizeLimits,
+    pub height: SizeLimits,
+    /// The ratio of width to height, as `(width, height)`.
+    pub aspect_ratio: Option<(u32, u32)>,
+    pub bounds: BoundingBox,
+    pub on_state_changed: fn(&mut Self, WidgetState),
+}
+
+impl<W> SizeConstraint<W>
+where
+    W: Widget,
+{
+    pub fn new(inner: W) -> SizeConstraint<W> {
+        SizeConstraint {
+            inner,
+            width: SizeLimits::UNLIMITED,
+            height: SizeLimits::UNLIMITED,
+            aspect_ratio: None,
+            bounds: BoundingBox::default(),
+            on_state_changed: |_, _| (),
+        }
+    }
+}
+
+impl<W> SizeConstraint<W> {
+    pub fn min_width(mut self, width: u32) -> Self {
+        self.width.min = width;
+        self
+    }
+
+    pub fn max_width(mut self, width: u32) -> Self {
+        self.width.max = width;
+        self
+    }
+
+    /// Sets the exact width of the widget.
+    pub fn width(mut self, width: u32) -> Self {
+        self.width = SizeLimits {
+            min: width,
+            max: width,
+        };
+        self
+    }
+
+    pub fn min_height(mut self, height: u32) -> Self {
+        self.height.min = height;
+        self
+    }
+
+    pub fn max_height(mut self, height: u32) -> Self {
+        self.height.max = height;
+        self
+    }
+
+    /// Sets the exact height of the widget.
+    pub fn height(mut self, height: u32) -> Self {
+        self.height = SizeLimits {
+            min: height,
+            max: height,
+        };
+        self
+    }
+
+    /// Keeps the widget at the given ratio of `width` to `height`.
+    ///
+    /// The widget is made as large as the constraints allow while keeping the ratio. If neither
+    /// dimension is limited, the width of the contained widget is used.
+    ///
+    /// The size limits of both axes are respected when picking the size. If no size with the ratio
+    /// satisfies them, e.g. for `min_width(48).max_height(20).aspect_ratio(1, 1)`, the limits win
+    /// and the ratio is not kept.
+    pub fn aspect_ratio(mut self, width: u32, height: u32) -> Self {
+        debug_assert!(width != 0 && height != 0);
+        self.aspect_ratio = Some((width, height));
+        self
+    }
+
+    pub fn on_state_changed(mut self, callback: fn(&mut Self, WidgetState)) -> Self {
+        self.on_state_changed = callback;
+        self
+    }
+}
+
+impl<W> WrapperBindable for SizeConstraint<W> where W: Widget {}
+
+impl<W, D> Wrapper<SizeConstraint<W>, D>
+where
+    W: Widget,
+    D: WidgetData,
+{
+    pub fn min_width(mut self, width: u32) -> Self {
+        self.widget = self.widget.min_width(width);
+        self
+    }
+
+    pub fn max_width(mut self, width: u32) -> Self {
+        self.widget = self.widget.max_width(width);
+        self
+    }
+
+    pub fn width(mut self, width: u32) -> Self {
+        self.widget = self.widget.width(width);
+        self
+    }
+
+    pub fn min_height(mut self, height: u32) -> Self {
+        self.widget = self.widget.min_height(height);
+        self
+    }
+
+    pub fn max_height(mut self, height: u32) -> Self {
+        self.widget = self.widget.max_height(height);
+        self
+    }
+
+    pub fn height(mut self, height: u32) -> Self {
+        self.widget = self.widget.height(height);
+        self
+    }
+
+    pub fn aspect_ratio(mut self, width: u32, height: u32) -> Self {
+        self.widget = self.widget.aspect_ratio(width, height);
+        self
+    }
+
+    pub fn on_state_changed(mut self, callback: fn(&mut SizeConstraint<W>, WidgetState)) -> Self {
+        self.widget.on_state_changed = callback;
+        self
+    }
+}
+
+impl<W> SizeConstraint<W>
+where
+    W: Widget,
+{
+    fn clamp(&self, size: MeasuredSize) -> MeasuredSize {
+        MeasuredSize {
+            width: self.width.clamp(size.width),
+            height: self.height.clamp(size.height),
+        }
+    }
+
+    /// Returns the largest size with the configured aspect ratio that fits `spec` and the limits.
+    fn size_with_aspect_ratio(
+        &mut self,
+        (ratio_w, ratio_h): (u32, u32),
+        spec: MeasureSpec,
+    ) -> MeasuredSize {
+        let scale = |size: u32, num: u32, den: u32| {
+            (size as u64 * num as u64 / den as u64).min(u32::MAX as u64) as u32
+        };
+        let width_for = |height: u32| scale(height, ratio_w, ratio_h);
+        let height_for = |width: u32| scale(width, ratio_h, ratio_w);
+
+        // The widths whose height at the ratio is within the height limits
+        let mut min_width = width_for(self.height.min);
+        if height_for(min_width) < self.height.min {
+            min_width = min_width.saturating_add(1);
+        }
+        let min_width = min_width.max(self.width.min);
+        let max_width = self.width.max.min(width_for(self.height.max));
+
+        let width = match (spec.width.largest(), spec.height.largest()) {
+            (Some(width), Some(height)) => width.min(width_for(height)),
+            (Some(width), None) => width,
+            (None, Some(height)) => width_for(height),
+            (None, None) => {
+                self.inner.measure(spec);
+                self.inner.bounding_box().size.width
+            }
+        };
+
+        // When the limits conflict, `measure` clamps the size and gives up on the ratio
+        let width = if min_width <= max_width {
+            width.max(min_width).min(max_width)
+        } else {
+            width
+        };
+
+        MeasuredSize {
+            width,
+            height: height_for(width),
+        }
+    }
+}
+
+impl<W> WidgetDecorator for SizeConstraint<W>
+where
+    W: Widget,
+{
+    type Widget = W;
+
+    fn widget(&self) -> &Self::Widget {
+        &self.inner
+    }
+
+    fn widget_mut(&mut self) -> &mut Self::Widget {
+        &mut self.inner
+    }
+
+    fn bounding_box(&self) -> BoundingBox {
+        self.bounds
+    }
+
+    fn bounding_box_mut(&mut self) -> &mut BoundingBox {
+        &mut self.bounds
+    }
+
+    fn measure(&mut self, measure_spec: MeasureSpec) {
+        let spec = MeasureSpec {
+            width: self.width.constrain(measure_spec.width),
+            height: self.height.constrain(measure_spec.height),
+        };
+
+        let size = if let Some(ratio) = self.aspect_ratio {
+            let size = self.size_with_aspect_ratio(ratio, spec);
+            self.clamp(size)
+        } else {
+            self.inner.measure(spec);
+            self.clamp(self.inner.bounding_box().size)
+        };
+
+        // Let the widget fill the space if it turned out to be too small or too large
+        if self.aspect_ratio.is_some() || size != self.inner.bounding_box().size {
+            self.inner.measure(MeasureSpec {
+                width: MeasureConstraint::Exactly(size.width),
+                height: MeasureConstraint::Exactly(size.height),
+            });
+        }
+
+        self.bounds.size = size;
+    }
+
+    fn arrange(&mut self, position: Position) {
+        self.bounds.position = position;
+        self.inner.arrange(position);
+    }
+
+    fn fire_on_state_changed(&mut self, state: WidgetState) {
+        (self.on_state_changed)(self, state);
+    }
+}
+
+impl<C, W> WidgetRenderer<C> for SizeConstraint<W>
+where
+    W: Widget + WidgetRenderer<C>,
+    C: Canvas,
+{
+    fn draw(&mut self, canvas: &mut C) -> Result<(), C::Error> {
+        canvas.draw_clipped(self.bounds, &mut self.inner)
+    }
+}
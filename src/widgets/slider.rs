@@ -16,7 +16,10 @@ use crate::{
         controller::InputContext,
         event::{InputEvent, PointerEvent, ScrollEvent},
     },
-    state::{State, WidgetState},
+    state::{
+        enabled::{Enabled, EnabledStateGroup},
+        State, WidgetState,
+    },
     state_group,
     widgets::{
         scroll::{ScrollData, ScrollDirection, ScrollFields},
@@ -185,6 +188,8 @@ pub struct SliderFields<SP, D> {
     pub bounds: BoundingBox,
     pub properties: SP,
     pub state: WidgetState,
+    /// The widget is inactive if this is `false`, or if it is inside a disabled `Enabled` widget.
+    pub active: bool,
 }
 
 fn lerp(x: i32, x0: i32, x1: i32, y0: i32, y1: i32) -> i32 {
@@ -210,7 +215,18 @@ where
     SP: SliderProperties,
 {
     pub fn set_active(&mut self, active: bool) {
-        if active {
+        self.active = active;
+        self.update_active_state();
+    }
+
+    fn inherit_state(&mut self, parent: WidgetState) {
+        if self.state.inherit(parent, EnabledStateGroup) {
+            self.update_active_state();
+        }
+    }
+
+    fn update_active_state(&mut self) {
+        if self.active && self.state.has_state(Enabled) {
             self.change_state(Slider::STATE_ACTIVE);
         } else {
             self.change_state(Slider::STATE_INACTIVE);
@@ -305,6 +321,7 @@ where
                 limits,
                 properties,
                 state: WidgetState::default(),
+                active: true,
             },
             data_holder: WidgetDataHolder::default(),
             drag_offset: None,
@@ -324,6 +341,7 @@ where
                 limits: self.fields.limits,
                 properties: self.fields.properties,
                 state: self.fields.state,
+                active: self.fields.active,
             },
             data_holder: WidgetDataHolder::new(data),
             drag_offset: None,
@@ -482,8 +500,8 @@ where
         // Scrollwheel/arrows should change the value, dragging should change position directly.
     }
 
-    fn handle_input(&mut self, _ctxt: InputContext, event: InputEvent) -> bool {
-        if self.fields.state.has_state(Slider::STATE_INACTIVE) {
+    fn handle_input(&mut self, ctxt: InputContext, event: InputEvent) -> bool {
+        if !ctxt.is_target() || self.fields.state.has_state(Slider::STATE_INACTIVE) {
             return false;
         }
 
@@ -496,7 +514,7 @@ where
 
                 return true;
             }
-            InputEvent::KeyEvent(_) => {}
+            InputEvent::KeyEvent(_) | InputEvent::TouchEvent(..) => {}
             InputEvent::PointerEvent(position, PointerEvent::Down) => {
                 if self.fields.slider_bounds().contains(position) {
                     let position = position - self.fields.slider_bounds().position;
@@ -534,6 +552,16 @@ where
 
                 return true;
             }
+            InputEvent::PointerEvent(_, PointerEvent::Enter) => {
+                if !self.fields.state.has_state(Slider::STATE_DRAGGED) {
+                    self.fields.state.set_state(Slider::STATE_HOVERED);
+                }
+            }
+            InputEvent::PointerEvent(_, PointerEvent::Leave) => {
+                if !self.fields.state.has_state(Slider::STATE_DRAGGED) {
+                    self.fields.state.set_state(Slider::STATE_IDLE);
+                }
+            }
             InputEvent::PointerEvent(_, _) => {}
             InputEvent::ScrollEvent(scroll) => {
                 let delta = match scroll {
@@ -556,8 +584,9 @@ where
         false
     }
 
-    fn on_state_changed(&mut self, _state: WidgetState) {
-        // don't react to parent's state change
+    fn on_state_changed(&mut self, state: WidgetState) {
+        // only react to a parent being disabled
+        self.fields.inherit_state(state);
     }
 
     fn is_selectable(&self) -> bool {
@@ -150,6 +150,12 @@ where
         });
     }
 
+    fn baseline(&self) -> Option<u32> {
+        self.inner
+            .baseline()
+            .map(|baseline| baseline + self.spacing.top)
+    }
+
     fn fire_on_state_changed(&mut self, state: WidgetState) {
         (self.on_state_changed)(self, state);
     }
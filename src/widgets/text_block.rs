@@ -68,6 +68,10 @@ where
     fn is_selectable(&self) -> bool {
         false
     }
+
+    fn text(&self) -> Option<&str> {
+        Some(self.text.as_ref())
+    }
 }
 
 impl<S, P> WrapperBindable for TextBlock<S, P>
@@ -8,6 +8,7 @@ use crate::{
     },
     prelude::WidgetData,
     state::{
+        enabled::{Enabled, EnabledStateGroup},
         selection::{Selected, Unselected},
         State, WidgetState,
     },
@@ -37,6 +38,8 @@ where
     pub bounds: BoundingBox,
     pub parent_index: usize,
     pub state: WidgetState,
+    /// The widget is inactive if this is `false`, or if it is inside a disabled `Enabled` widget.
+    pub active: bool,
     pub on_text_changed: fn(&mut D, &str),
     pub on_parent_state_changed: fn(&mut Self, WidgetState),
 }
@@ -52,6 +55,25 @@ where
         *self.text.borrow_mut() = String::from(text);
         true
     }
+
+    pub fn set_active(&mut self, active: bool) {
+        self.active = active;
+        self.update_active_state();
+    }
+
+    fn inherit_state(&mut self, parent: WidgetState) {
+        if self.state.inherit(parent, EnabledStateGroup) {
+            self.update_active_state();
+        }
+    }
+
+    fn update_active_state(&mut self) {
+        if self.active && self.state.has_state(Enabled) {
+            self.state.set_state(TextBox::STATE_ACTIVE);
+        } else {
+            self.state.set_state(TextBox::STATE_INACTIVE);
+        }
+    }
 }
 
 pub struct TextBox<B, P, D, const N: usize>
@@ -79,6 +101,7 @@ where
                 bounds: self.fields.bounds,
                 label_properties: self.fields.label_properties,
                 state: self.fields.state,
+                active: self.fields.active,
                 on_text_changed: |_, _| (),
                 on_parent_state_changed: |_, _| (),
             },
@@ -108,11 +131,7 @@ where
     }
 
     pub fn set_active(&mut self, active: bool) {
-        if active {
-            self.change_state(TextBox::STATE_ACTIVE);
-        } else {
-            self.change_state(TextBox::STATE_INACTIVE);
-        }
+        self.fields.set_active(active);
     }
 
     pub fn on_text_changed(mut self, callback: fn(&mut D::Data, &str)) -> Self {
@@ -191,6 +210,7 @@ where
     }
 
     fn on_state_changed(&mut self, state: WidgetState) {
+        self.fields.inherit_state(state);
         (self.fields.on_parent_state_changed)(&mut self.fields, state);
     }
 
@@ -225,7 +245,9 @@ where
 
             InputEvent::PointerEvent(_, PointerEvent::Drag)
             | InputEvent::PointerEvent(_, PointerEvent::Up)
-            | InputEvent::PointerEvent(_, PointerEvent::Hover) => None,
+            | InputEvent::PointerEvent(_, PointerEvent::Hover)
+            | InputEvent::PointerEvent(_, PointerEvent::Enter)
+            | InputEvent::PointerEvent(_, PointerEvent::Leave) => None,
             InputEvent::KeyEvent(_) => {
                 if self.fields.state.has_state(TextBox::STATE_SELECTED) {
                     Some(0)
@@ -237,11 +259,12 @@ where
                 // TODO
                 None
             }
+            InputEvent::TouchEvent(..) => None,
         }
     }
 
-    fn handle_input(&mut self, _ctxt: InputContext, event: InputEvent) -> bool {
-        if self.fields.state.has_state(TextBox::STATE_INACTIVE) {
+    fn handle_input(&mut self, ctxt: InputContext, event: InputEvent) -> bool {
+        if !ctxt.is_target() || self.fields.state.has_state(TextBox::STATE_INACTIVE) {
             return false;
         }
 
@@ -256,6 +279,10 @@ where
                     self.change_state(TextBox::STATE_SELECTED);
                     self.fields.label_properties.handle_cursor_down(pos);
 
+                    // We don't follow the pointer, let parents (e.g. Scroll) handle the drag
+                    // without deselecting us.
+                    ctxt.release_pointer();
+
                     true
                 }
                 _ => false,
@@ -280,4 +307,8 @@ where
     fn is_selectable(&self) -> bool {
         true
     }
+
+    fn text(&self) -> Option<&str> {
+        Some(self.fields.text.borrow().as_str())
+    }
 }
@@ -6,7 +6,10 @@ use crate::{
         controller::InputContext,
         event::{InputEvent, PointerEvent},
     },
-    state::{State, WidgetState},
+    state::{
+        enabled::{Enabled, EnabledStateGroup},
+        State, WidgetState,
+    },
     state_group,
     widgets::{
         utils::{decorator::WidgetDecorator, WidgetDataHolder},
@@ -23,6 +26,8 @@ pub struct ToggleFields<W, D> {
     pub on_selected_changed: fn(bool, &mut D),
     pub on_state_changed: fn(&mut Self, WidgetState),
     pub state: WidgetState,
+    /// The widget is inactive if this is `false`, or if it is inside a disabled `Enabled` widget.
+    pub active: bool,
 }
 
 impl<W, D> ToggleFields<W, D>
@@ -38,7 +43,19 @@ where
     }
 
     pub fn set_active(&mut self, active: bool) {
-        if active {
+        self.active = active;
+        self.update_active_state();
+    }
+
+    fn inherit_state(&mut self, parent: WidgetState) {
+        if self.state.inherit(parent, EnabledStateGroup) {
+            self.inner.on_state_changed(self.state);
+            self.update_active_state();
+        }
+    }
+
+    fn update_active_state(&mut self) {
+        if self.active && self.state.has_state(Enabled) {
             self.change_state(Toggle::STATE_ACTIVE);
         } else {
             self.change_state(Toggle::STATE_INACTIVE);
@@ -107,6 +124,7 @@ where
                 on_selected_changed: |_, _| (),
                 on_state_changed: |_, _| (),
                 state,
+                active: true,
             },
             data_holder: WidgetDataHolder::default(),
         }
@@ -128,6 +146,7 @@ where
                 on_selected_changed: |_, _| (),
                 on_state_changed: |_, _| (),
                 state: self.fields.state,
+                active: self.fields.active,
             },
             data_holder: WidgetDataHolder::new(data),
         }
@@ -217,6 +236,10 @@ where
         self.fields.inner.attach(self_index, self_index + 1);
     }
 
+    fn parent_index(&self) -> usize {
+        self.fields.parent_index
+    }
+
     fn update(&mut self) {
         self.data_holder.update(&mut self.fields);
         self.fields.inner.update();
@@ -287,16 +310,18 @@ where
                     None
                 }
             }
+            InputEvent::PointerEvent(_, PointerEvent::Enter)
+            | InputEvent::PointerEvent(_, PointerEvent::Leave) => None,
             InputEvent::KeyEvent(_) => {
                 // TODO we do care about some key events
                 None
             }
-            InputEvent::ScrollEvent(_) => None,
+            InputEvent::ScrollEvent(_) | InputEvent::TouchEvent(..) => None,
         }
     }
 
-    fn handle_input(&mut self, _ctxt: InputContext, event: InputEvent) -> bool {
-        if self.fields.state.has_state(Toggle::STATE_INACTIVE) {
+    fn handle_input(&mut self, ctxt: InputContext, event: InputEvent) -> bool {
+        if !ctxt.is_target() || self.fields.state.has_state(Toggle::STATE_INACTIVE) {
             return false;
         }
 
@@ -307,15 +332,41 @@ where
                 }
                 true
             }
-            InputEvent::PointerEvent(_, pe) => match pe {
-                PointerEvent::Hover | PointerEvent::Drag => false,
+            InputEvent::PointerEvent(position, pe) => match pe {
+                PointerEvent::Hover => false,
+                PointerEvent::Enter => {
+                    if !self.fields.state.has_state(Toggle::STATE_PRESSED) {
+                        self.fields.change_state(Toggle::STATE_HOVERED);
+                    }
+                    false
+                }
+                PointerEvent::Leave => {
+                    self.fields.change_state(Toggle::STATE_IDLE);
+                    false
+                }
+                PointerEvent::Drag => {
+                    // We receive every drag while we capture the pointer
+                    if self.fields.inner.bounding_box().contains(position) {
+                        if !self.fields.state.has_state(Toggle::STATE_PRESSED) {
+                            self.fields.change_state(Toggle::STATE_HOVERED);
+                        }
+                    } else {
+                        // Drag outside = cancel
+                        self.fields.change_state(Toggle::STATE_IDLE);
+                    }
+                    // Let parents (e.g. Scroll) take over the drag
+                    false
+                }
                 PointerEvent::Down => {
                     self.fields.change_state(Toggle::STATE_PRESSED);
                     true
                 }
                 PointerEvent::Up => {
-                    self.fields.change_state(Toggle::STATE_HOVERED);
-                    self.fire_on_selected_changed();
+                    // The press may have been cancelled by dragging outside
+                    if self.fields.state.has_state(Toggle::STATE_PRESSED) {
+                        self.fields.change_state(Toggle::STATE_HOVERED);
+                        self.fire_on_selected_changed();
+                    }
                     true
                 }
             },
@@ -326,8 +377,9 @@ where
         }
     }
 
-    fn on_state_changed(&mut self, _state: WidgetState) {
-        // don't react to parent's state change
+    fn on_state_changed(&mut self, state: WidgetState) {
+        // only react to a parent being disabled
+        self.fields.inherit_state(state);
     }
 
     fn is_selectable(&self) -> bool {
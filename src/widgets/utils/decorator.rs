@@ -12,7 +12,7 @@ pub trait WidgetDecorator {
 
     fn attach(&mut self, parent: usize, self_index: usize) {
         debug_assert!(self_index == 0 || parent != self_index);
-        self.widget_mut().attach(parent, self_index);
+        self.widget_mut().attach(parent, self_index + 1);
     }
 
     fn bounding_box(&self) -> BoundingBox {
@@ -78,6 +78,16 @@ pub trait WidgetDecorator {
         self.widget_mut().test_input(event).map(|i| i + 1)
     }
 
+    fn hit_test(&self, position: Position) -> Option<usize> {
+        if let Some(idx) = self.widget().hit_test(position) {
+            Some(idx + 1)
+        } else if self.bounding_box().contains(position) {
+            Some(0)
+        } else {
+            None
+        }
+    }
+
     fn handle_input(&mut self, ctxt: InputContext, event: InputEvent) -> bool {
         self.widget_mut().handle_input(ctxt, event)
     }
@@ -86,6 +96,12 @@ pub trait WidgetDecorator {
         self.widget().is_selectable()
     }
 
+    /// Decorators that place the widget below their own top edge need to add the offset. Positions
+    /// may be outdated during measurement, so the offset should not be derived from them.
+    fn baseline(&self) -> Option<u32> {
+        self.widget().baseline()
+    }
+
     fn widget(&self) -> &Self::Widget;
 
     fn widget_mut(&mut self) -> &mut Self::Widget;
@@ -144,6 +160,10 @@ where
         WidgetDecorator::test_input(self, event)
     }
 
+    fn hit_test(&self, position: Position) -> Option<usize> {
+        WidgetDecorator::hit_test(self, position)
+    }
+
     fn handle_input(&mut self, ctxt: InputContext, event: InputEvent) -> bool {
         WidgetDecorator::handle_input(self, ctxt, event)
     }
@@ -155,4 +175,8 @@ where
     fn is_selectable(&self) -> bool {
         WidgetDecorator::is_selectable(self)
     }
+
+    fn baseline(&self) -> Option<u32> {
+        WidgetDecorator::baseline(self)
+    }
 }
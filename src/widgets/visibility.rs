@@ -2,8 +2,8 @@
 
 use crate::{
     data::WidgetData,
-    geometry::{BoundingBox, MeasuredSize},
-    input::event::InputEvent,
+    geometry::{BoundingBox, MeasuredSize, Position},
+    input::{controller::InputContext, event::InputEvent},
     state::WidgetState,
     widgets::{
         utils::{
@@ -15,9 +15,23 @@ use crate::{
     Canvas, WidgetRenderer,
 };
 
+/// The ways a [`Visibility`] can show or hide its contents.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum VisibilityMode {
+    /// The widget is drawn and receives input.
+    Visible,
+
+    /// The widget keeps its space in the layout, but it is not drawn and receives no input.
+    Hidden,
+
+    /// The widget takes up no space, so the surrounding layout reflows as if it wasn't there. It
+    /// is not drawn and receives no input.
+    Collapsed,
+}
+
 pub struct Visibility<W> {
     pub inner: W,
-    pub visibility: bool,
+    pub visibility: VisibilityMode,
     pub on_state_changed: fn(&mut Self, WidgetState),
 }
 
@@ -28,7 +42,7 @@ where
     pub fn new(inner: W) -> Visibility<W> {
         Visibility {
             inner,
-            visibility: true,
+            visibility: VisibilityMode::Visible,
             on_state_changed: |_, _| (),
         }
     }
@@ -37,13 +51,32 @@ where
 impl<W> WrapperBindable for Visibility<W> where W: Widget {}
 
 impl<W> Visibility<W> {
+    /// Shows the widget, or collapses it.
     pub fn visible(mut self, visibility: bool) -> Self {
         self.set_visible(visibility);
         self
     }
 
+    /// Shows the widget, or collapses it.
     pub fn set_visible(&mut self, visibility: bool) {
-        self.visibility = visibility;
+        self.set_mode(if visibility {
+            VisibilityMode::Visible
+        } else {
+            VisibilityMode::Collapsed
+        });
+    }
+
+    pub fn mode(mut self, mode: VisibilityMode) -> Self {
+        self.set_mode(mode);
+        self
+    }
+
+    pub fn set_mode(&mut self, mode: VisibilityMode) {
+        self.visibility = mode;
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visibility == VisibilityMode::Visible
     }
 
     pub fn on_state_changed(mut self, callback: fn(&mut Self, WidgetState)) -> Self {
@@ -62,6 +95,11 @@ where
         self
     }
 
+    pub fn mode(mut self, mode: VisibilityMode) -> Self {
+        self.widget.set_mode(mode);
+        self
+    }
+
     pub fn on_state_changed(mut self, callback: fn(&mut Visibility<W>, WidgetState)) -> Self {
         // TODO this should be pulled up
         self.widget.on_state_changed = callback;
@@ -84,9 +122,7 @@ where
     }
 
     fn bounding_box(&self) -> BoundingBox {
-        if self.visibility {
-            self.inner.bounding_box()
-        } else {
+        if self.visibility == VisibilityMode::Collapsed {
             BoundingBox {
                 position: self.inner.bounding_box().position,
                 size: MeasuredSize {
@@ -94,17 +130,31 @@ where
                     height: 0,
                 },
             }
+        } else {
+            self.inner.bounding_box()
         }
     }
 
     fn test_input(&mut self, event: InputEvent) -> Option<usize> {
-        if self.visibility {
+        if self.is_visible() {
             // We just relay whatever the child desires
             self.inner.test_input(event).map(|i| i + 1)
         } else {
             None
         }
     }
+
+    fn hit_test(&self, position: Position) -> Option<usize> {
+        if !self.is_visible() {
+            return None;
+        }
+
+        self.inner.hit_test(position).map(|i| i + 1)
+    }
+
+    fn handle_input(&mut self, ctxt: InputContext, event: InputEvent) -> bool {
+        self.is_visible() && self.inner.handle_input(ctxt, event)
+    }
 }
 
 impl<C, W> WidgetRenderer<C> for Visibility<W>
@@ -113,7 +163,7 @@ where
     C: Canvas,
 {
     fn draw(&mut self, canvas: &mut C) -> Result<(), C::Error> {
-        if self.visibility {
+        if self.is_visible() {
             self.inner.draw(canvas)
         } else {
             Ok(())
@@ -0,0 +1,327 @@
+//! A container that can be zoomed with a two-finger pinch gesture.
+//!
+//! The zoom level scales the space the inner widget is laid out in, so the inner widget should be
+//! able to fill the space it is given. Moving the fingers while pinching pans the content.
+
+use crate::{
+    data::WidgetData,
+    geometry::{
+        measurement::{MeasureConstraint, MeasureSpec},
+        BoundingBox, MeasuredSize, Position, PositionDelta,
+    },
+    input::{
+        controller::{EventPhase, InputContext},
+        event::{InputEvent, PointerEvent},
+        gesture::{Pinch, PinchRecognizer},
+    },
+    state::WidgetState,
+    widgets::{utils::WidgetDataHolder, Widget},
+    Canvas, WidgetRenderer,
+};
+
+// Need separation because zoom change listeners need the fields.
+pub struct ZoomFields<W, D> {
+    pub parent_index: usize,
+    pub inner: W,
+    pub bounds: BoundingBox,
+    /// The current zoom level, in percent.
+    pub zoom: u32,
+    pub min_zoom: u32,
+    pub max_zoom: u32,
+    /// The offset of the visible area from the top left corner of the content.
+    pub offset: PositionDelta,
+    pub on_zoom_changed: fn(&mut D, u32),
+}
+
+impl<W, D> ZoomFields<W, D> {
+    /// Sets the zoom level, in percent. The zoom level is at least 1%.
+    pub fn set_zoom(&mut self, zoom: u32) {
+        self.zoom = zoom.max(self.min_zoom).min(self.max_zoom).max(1);
+    }
+}
+
+pub struct Zoom<W, D = ()>
+where
+    D: WidgetData,
+{
+    pub fields: ZoomFields<W, D::Data>,
+    data_holder: WidgetDataHolder<ZoomFields<W, D::Data>, D>,
+    recognizer: PinchRecognizer,
+    pinch_start_zoom: u32,
+}
+
+impl<W> Zoom<W, ()>
+where
+    W: Widget,
+{
+    pub fn new(inner: W) -> Self {
+        Zoom {
+            fields: ZoomFields {
+                parent_index: 0,
+                inner,
+                bounds: BoundingBox::default(),
+                zoom: 100,
+                min_zoom: 100,
+                max_zoom: 400,
+                offset: PositionDelta { x: 0, y: 0 },
+                on_zoom_changed: |_, _| (),
+            },
+            data_holder: WidgetDataHolder::default(),
+            recognizer: PinchRecognizer::new(),
+            pinch_start_zoom: 100,
+        }
+    }
+
+    pub fn bind<D>(self, data: D) -> Zoom<W, D>
+    where
+        D: WidgetData,
+    {
+        Zoom {
+            fields: ZoomFields {
+                parent_index: self.fields.parent_index,
+                inner: self.fields.inner,
+                bounds: self.fields.bounds,
+                zoom: self.fields.zoom,
+                min_zoom: self.fields.min_zoom,
+                max_zoom: self.fields.max_zoom,
+                offset: self.fields.offset,
+                on_zoom_changed: |_, _| (),
+            },
+            data_holder: WidgetDataHolder::new(data),
+            recognizer: self.recognizer,
+            pinch_start_zoom: self.pinch_start_zoom,
+        }
+    }
+}
+
+impl<W, D> Zoom<W, D>
+where
+    W: Widget,
+    D: WidgetData,
+{
+    /// Sets the smallest allowed zoom level, in percent. Values below 1% are raised to 1%.
+    pub fn min_zoom(mut self, zoom: u32) -> Self {
+        self.fields.min_zoom = zoom.max(1);
+        self.fields.set_zoom(self.fields.zoom);
+        self
+    }
+
+    /// Sets the largest allowed zoom level, in percent. Values below 1% are raised to 1%.
+    pub fn max_zoom(mut self, zoom: u32) -> Self {
+        self.fields.max_zoom = zoom.max(1);
+        self.fields.set_zoom(self.fields.zoom);
+        self
+    }
+
+    /// Sets the initial zoom level, in percent.
+    pub fn zoom(mut self, zoom: u32) -> Self {
+        self.fields.set_zoom(zoom);
+        self
+    }
+
+    pub fn on_data_changed(mut self, callback: fn(&mut ZoomFields<W, D::Data>, &D::Data)) -> Self {
+        self.data_holder.on_data_changed = callback;
+        self
+    }
+
+    pub fn on_zoom_changed(mut self, callback: fn(&mut D::Data, u32)) -> Self {
+        self.fields.on_zoom_changed = callback;
+        self
+    }
+
+    fn apply_pinch(&mut self, pinch: Pinch) {
+        let old_zoom = self.fields.zoom;
+        self.fields.set_zoom(pinch.scale(self.pinch_start_zoom));
+        let zoom = self.fields.zoom;
+
+        // Keep the content under the fingers in place.
+        let position = self.fields.bounds.position;
+        let center = pinch.center - position;
+        let previous_center = (pinch.center - pinch.center_delta) - position;
+        let offset = self.fields.offset;
+
+        let rescale = |coordinate: i32| (coordinate as i64 * zoom as i64 / old_zoom as i64) as i32;
+        self.fields.offset = PositionDelta {
+            x: rescale(offset.x + previous_center.x) - center.x,
+            y: rescale(offset.y + previous_center.y) - center.y,
+        };
+
+        if zoom != old_zoom {
+            let callback = self.fields.on_zoom_changed;
+            self.data_holder.data.update(|data| callback(data, zoom));
+        }
+    }
+
+    fn clamp_offset(&mut self) {
+        let child_size = self.fields.inner.bounding_box().size;
+        let own_size = self.fields.bounds.size;
+
+        let max_x = child_size.width.saturating_sub(own_size.width) as i32;
+        let max_y = child_size.height.saturating_sub(own_size.height) as i32;
+
+        self.fields.offset = PositionDelta {
+            x: self.fields.offset.x.max(0).min(max_x),
+            y: self.fields.offset.y.max(0).min(max_y),
+        };
+    }
+}
+
+/// Returns `zoom` percent of `size`.
+fn scale(size: u32, zoom: u32) -> u32 {
+    (size as u64 * zoom as u64 / 100).min(u32::MAX as u64) as u32
+}
+
+impl<W, D> Widget for Zoom<W, D>
+where
+    W: Widget,
+    D: WidgetData,
+{
+    fn attach(&mut self, parent: usize, self_index: usize) {
+        debug_assert!(self_index == 0 || parent != self_index);
+        self.set_parent(parent);
+        self.fields.inner.attach(self_index, self_index + 1);
+    }
+
+    fn arrange(&mut self, position: Position) {
+        self.fields.bounds.position = position;
+        self.fields.inner.arrange(position - self.fields.offset);
+    }
+
+    fn bounding_box(&self) -> BoundingBox {
+        self.fields.bounds
+    }
+
+    fn bounding_box_mut(&mut self) -> &mut BoundingBox {
+        &mut self.fields.bounds
+    }
+
+    fn measure(&mut self, measure_spec: MeasureSpec) {
+        // Zoom is as big as parent lets it to be. If parent gives us an unspecified dimension, zoom
+        // will take up as much space as the unzoomed child.
+        self.fields.inner.measure(measure_spec);
+        let child_size = self.fields.inner.bounding_box().size;
+
+        let width = measure_spec.width.largest().unwrap_or(child_size.width);
+        let height = measure_spec.height.largest().unwrap_or(child_size.height);
+        self.fields.bounds.size = MeasuredSize { width, height };
+
+        let zoom = self.fields.zoom;
+        self.fields.inner.measure(MeasureSpec {
+            width: MeasureConstraint::Exactly(scale(width, zoom)),
+            height: MeasureConstraint::Exactly(scale(height, zoom)),
+        });
+
+        self.clamp_offset();
+    }
+
+    fn children(&self) -> usize {
+        1 + self.fields.inner.children()
+    }
+
+    fn get_child(&self, idx: usize) -> &dyn Widget {
+        if idx == 0 {
+            &self.fields.inner
+        } else {
+            self.fields.inner.get_child(idx - 1)
+        }
+    }
+
+    fn get_mut_child(&mut self, idx: usize) -> &mut dyn Widget {
+        if idx == 0 {
+            &mut self.fields.inner
+        } else {
+            self.fields.inner.get_mut_child(idx - 1)
+        }
+    }
+
+    fn parent_index(&self) -> usize {
+        self.fields.parent_index
+    }
+
+    fn set_parent(&mut self, index: usize) {
+        self.fields.parent_index = index;
+    }
+
+    fn update(&mut self) {
+        self.data_holder.update(&mut self.fields);
+        self.fields.inner.update();
+    }
+
+    fn reset_changed(&mut self) {
+        self.data_holder.reset_changed();
+    }
+
+    fn test_input(&mut self, event: InputEvent) -> Option<usize> {
+        if let InputEvent::PointerEvent(position, _) = event {
+            // The parts of the inner widget that are outside of the viewport can't be interacted with
+            if !self.fields.bounds.contains(position) {
+                return None;
+            }
+        }
+
+        self.fields.inner.test_input(event).map(|idx| idx + 1)
+    }
+
+    fn hit_test(&self, position: Position) -> Option<usize> {
+        if !self.fields.bounds.contains(position) {
+            return None;
+        }
+
+        self.fields
+            .inner
+            .hit_test(position)
+            .map(|idx| idx + 1)
+            .or(Some(0))
+    }
+
+    fn handle_input(&mut self, ctxt: InputContext, event: InputEvent) -> bool {
+        // We see the touch events of our children in the capture phase, and our own in the target
+        // phase. The bubble phase would feed the same events to the recognizer again.
+        if ctxt.phase() == EventPhase::Bubble {
+            return false;
+        }
+
+        if let InputEvent::TouchEvent(touch, pe) = event {
+            let was_active = self.recognizer.is_active();
+            let pinch = self.recognizer.update(touch, pe);
+
+            if !was_active && self.recognizer.is_active() {
+                self.pinch_start_zoom = self.fields.zoom;
+            }
+
+            if let Some(pinch) = pinch {
+                self.apply_pinch(pinch);
+                ctxt.capture_pointer();
+                return true;
+            }
+
+            // Swallow the end of the gesture, too.
+            if was_active {
+                if let PointerEvent::Up = pe {
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
+    fn on_state_changed(&mut self, state: WidgetState) {
+        self.fields.inner.on_state_changed(state);
+    }
+
+    fn is_selectable(&self) -> bool {
+        false
+    }
+}
+
+impl<C, W, D> WidgetRenderer<C> for Zoom<W, D>
+where
+    W: Widget + WidgetRenderer<C>,
+    D: WidgetData,
+    C: Canvas,
+{
+    fn draw(&mut self, canvas: &mut C) -> Result<(), C::Error> {
+        canvas.draw_clipped(self.fields.bounds, &mut self.fields.inner)
+    }
+}
@@ -0,0 +1,438 @@
+use backend_embedded_graphics::{
+    snapshot::TestWindow,
+    themes::default::DefaultTheme,
+    widgets::{calibration::CalibrationStyle, label::ascii::LabelConstructor},
+};
+use std::cell::Cell;
+
+use embedded_graphics::pixelcolor::BinaryColor;
+use embedded_gui::{
+    data::BoundData,
+    geometry::Position,
+    input::{
+        event::{InputEvent, PointerEvent},
+        transform::AffineTransform,
+    },
+    prelude::*,
+    time::{Instant, TimeSource},
+    widgets::{
+        button::Button,
+        calibration::{CalibrationPoints, CalibrationScreen},
+        enabled::Enabled,
+        label::Label,
+        layouts::{
+            absolute::Anchor,
+            frame::Frame,
+            linear::{Column, Row},
+        },
+        spacing::Spacing,
+    },
+};
+
+#[path = "../examples/calculator/gui.rs"]
+mod calculator;
+
+#[test]
+fn calculator_example() {
+    let state = BoundData::new(calculator::Calculator::new(), |_| ());
+    let mut window = TestWindow::<_, BinaryColor, 96, 96>::new(calculator::widget_tree(&state));
+    window.layout();
+
+    // The display comes before the keys
+    let display = window.window.find_text("0").unwrap();
+    let displayed = |window: &TestWindow<_, BinaryColor, 96, 96>| {
+        window
+            .window
+            .widget(display)
+            .unwrap()
+            .text()
+            .unwrap()
+            .to_owned()
+    };
+
+    for key in ["7", "x", "6", "="].iter() {
+        window.click_text(key);
+    }
+    assert_eq!(displayed(&window), "42");
+
+    window.click_text("CE");
+    assert_eq!(displayed(&window), "0");
+
+    for key in ["1", "2", "+", "3", "="].iter() {
+        window.click_text(key);
+    }
+    assert_eq!(displayed(&window), "15");
+
+    window.click_text("<");
+    assert_eq!(displayed(&window), "1");
+}
+
+#[test]
+fn click_buttons_by_label() {
+    let counter = BoundData::new(0, |_| ());
+
+    let mut window = TestWindow::<_, BinaryColor, 64, 48>::new(
+        Column::new()
+            .add(
+                Label::new("zero")
+                    .bind(&counter)
+                    .on_data_changed(|label, count| {
+                        label.text = if *count == 0 { "zero" } else { "not zero" }
+                    }),
+            )
+            .add(
+                BinaryColor::primary_button("Add")
+                    .bind(&counter)
+                    .on_clicked(|count| *count += 1),
+            )
+            .add(
+                BinaryColor::secondary_button("Sub")
+                    .bind(&counter)
+                    .on_clicked(|count| *count -= 1),
+            ),
+    );
+
+    window.click_text("Add");
+    window.click_text("Add");
+    assert_eq!(counter.with_data(|count| *count), 2);
+    assert!(window.window.find_text("not zero").is_some());
+
+    window.click_text("Sub");
+    window.click_text("Sub");
+    assert_eq!(counter.with_data(|count| *count), 0);
+    assert!(window.window.find_text("zero").is_some());
+}
+
+#[test]
+fn click_widget_by_index() {
+    let clicked = BoundData::new(false, |_| ());
+
+    let mut window = TestWindow::<_, BinaryColor, 64, 16>::new(
+        BinaryColor::primary_button("OK")
+            .bind(&clicked)
+            .on_clicked(|clicked| *clicked = true),
+    );
+
+    window.click_widget(0);
+    assert!(clicked.with_data(|clicked| *clicked));
+}
+
+macro_rules! covered_button {
+    ($clicked:expr) => {
+        Frame::new()
+            .add_layer(
+                BinaryColor::primary_button_stretched("OK")
+                    .bind($clicked)
+                    .on_clicked(|clicked| *clicked = true),
+            )
+            .add_layer(Label::new("!"))
+            .anchor(Anchor::Center)
+    };
+}
+
+#[test]
+fn opaque_layers_block_input() {
+    let clicked = BoundData::new(false, |_| ());
+    let mut window = TestWindow::<_, BinaryColor, 64, 16>::new(covered_button!(&clicked));
+
+    window.layout();
+    window.click(Position { x: 32, y: 8 });
+    assert!(!clicked.with_data(|clicked| *clicked));
+
+    // Outside of the label
+    window.click(Position { x: 2, y: 2 });
+    assert!(clicked.with_data(|clicked| *clicked));
+
+    let clicked = BoundData::new(false, |_| ());
+    let mut window =
+        TestWindow::<_, BinaryColor, 64, 16>::new(covered_button!(&clicked).pass_through());
+
+    window.layout();
+    window.click(Position { x: 32, y: 8 });
+    assert!(clicked.with_data(|clicked| *clicked));
+}
+
+#[test]
+fn focus_traversal_across_layers() {
+    let mut window = TestWindow::<_, BinaryColor, 64, 48>::new(
+        Frame::new()
+            .add_layer(
+                Column::new()
+                    .add(BinaryColor::primary_button("A"))
+                    .add(BinaryColor::primary_button("B")),
+            )
+            .add_layer(BinaryColor::primary_button("C"))
+            .anchor(Anchor::BottomRight)
+            .add_layer(Label::new("cover"))
+            .anchor(Anchor::TopLeft),
+    );
+    window.layout();
+
+    let window = &window.window;
+    let b = window.find_text("B").unwrap();
+    let c = window.find_text("C").unwrap();
+    let button = |label| window.widget(label).unwrap().parent_index();
+    let (b, c) = (button(b), button(c));
+
+    // "A" is covered by the label
+    assert_eq!(window.next_selectable(None), Some(b));
+    assert_eq!(window.next_selectable(Some(b)), Some(c));
+    assert_eq!(window.next_selectable(Some(c)), Some(b));
+    assert_eq!(window.previous_selectable(Some(b)), Some(c));
+    assert_eq!(window.previous_selectable(None), Some(c));
+}
+
+#[test]
+fn disabled_subtree_ignores_input() {
+    let enabled = BoundData::new(false, |_| ());
+    let counter = BoundData::new(0, |_| ());
+
+    let mut window = TestWindow::<_, BinaryColor, 64, 48>::new(
+        Column::new()
+            .add(
+                Enabled::new(
+                    Column::new().add(
+                        BinaryColor::primary_button("Add")
+                            .bind(&counter)
+                            .on_clicked(|count| *count += 1),
+                    ),
+                )
+                .bind(&enabled)
+                .on_data_changed(|widget, enabled| widget.set_enabled(*enabled)),
+            )
+            .add(
+                BinaryColor::secondary_button("Enable")
+                    .bind(&enabled)
+                    .on_clicked(|enabled| *enabled = true),
+            ),
+    );
+
+    window.click_text("Add");
+    assert_eq!(counter.with_data(|count| *count), 0);
+
+    window.click_text("Enable");
+    window.click_text("Add");
+    assert_eq!(counter.with_data(|count| *count), 1);
+}
+
+#[test]
+fn parent_indices_point_at_parents() {
+    let mut window = TestWindow::<_, BinaryColor, 64, 64>::new(
+        Column::new()
+            .add(Label::new("a"))
+            .add(
+                Row::new()
+                    .add(BinaryColor::primary_button("b"))
+                    .add(Spacing::new(BinaryColor::primary_button("c")).all(1)),
+            )
+            .add(
+                Frame::new()
+                    .add_layer(Label::new("d"))
+                    .add_layer(Button::new(
+                        Column::new().add(Label::new("e")).add(Label::new("f")),
+                    )),
+            ),
+    );
+    window.layout();
+
+    let window = &window.window;
+    let widget = |index| window.widget(index).unwrap();
+    let contains = |ancestor: usize, index: usize| {
+        ancestor < index && index <= ancestor + widget(ancestor).children()
+    };
+
+    for index in 1..=widget(0).children() {
+        // The closest ancestor that handles input for its contents, or the root. Decorators
+        // report the selectability of the widget they contain, so skip those.
+        let owns_input = |ancestor: usize| {
+            widget(ancestor).is_selectable() && !widget(ancestor + 1).is_selectable()
+        };
+        let expected = (1..index)
+            .rev()
+            .find(|&ancestor| contains(ancestor, index) && owns_input(ancestor))
+            .unwrap_or(0);
+
+        assert_eq!(widget(index).parent_index(), expected, "widget {}", index);
+    }
+
+    let e = window.find_text("e").unwrap();
+    let f = window.find_text("f").unwrap();
+    let button = widget(e).parent_index();
+    assert_ne!(button, 0);
+    assert_eq!(widget(f).parent_index(), button);
+    assert_eq!(widget(window.find_text("d").unwrap()).parent_index(), 0);
+}
+
+struct Clock(Cell<u32>);
+
+impl Clock {
+    fn set(&self, millis: u32) {
+        self.0.set(millis);
+    }
+}
+
+impl TimeSource for Clock {
+    fn now(&self) -> Instant {
+        Instant::from_millis(self.0.get())
+    }
+}
+
+/// A button that records its callbacks, and repeats after 300ms, every 100ms.
+macro_rules! repeating_button {
+    ($log:expr, $clock:expr) => {
+        BinaryColor::primary_button("Hold")
+            .bind($log)
+            .on_pressed(|log: &mut Vec<&str>| log.push("pressed"))
+            .on_clicked(|log| log.push("clicked"))
+            .on_released(|log| log.push("released"))
+            .auto_repeat($clock, 300, 100)
+    };
+}
+
+#[test]
+fn auto_repeat_delay_and_interval() {
+    let clock = Clock(Cell::new(0));
+    let log = BoundData::new(Vec::new(), |_| ());
+    let mut window = TestWindow::<_, BinaryColor, 64, 16>::new(repeating_button!(&log, &clock));
+    window.layout();
+
+    let center = window.widget_center(0);
+    window.press(center);
+
+    let clicks_at = |window: &mut TestWindow<_, _, 64, 16>, millis| {
+        clock.set(millis);
+        window.window.update();
+        log.with_data(|log| log.iter().filter(|&&entry| entry == "clicked").count())
+    };
+
+    assert_eq!(clicks_at(&mut window, 299), 0);
+    assert_eq!(clicks_at(&mut window, 300), 1);
+    assert_eq!(clicks_at(&mut window, 399), 1);
+    assert_eq!(clicks_at(&mut window, 400), 2);
+    assert_eq!(clicks_at(&mut window, 500), 3);
+
+    window.input_event(InputEvent::PointerEvent(center, PointerEvent::Up));
+
+    // Releasing after repeated clicks doesn't fire another one
+    assert_eq!(clicks_at(&mut window, 1000), 3);
+    assert_eq!(
+        log.with_data(|log| log.clone()),
+        ["pressed", "clicked", "clicked", "clicked", "released"]
+    );
+}
+
+#[test]
+fn auto_repeat_tap_clicks_once() {
+    let clock = Clock(Cell::new(0));
+    let log = BoundData::new(Vec::new(), |_| ());
+    let mut window = TestWindow::<_, BinaryColor, 64, 16>::new(repeating_button!(&log, &clock));
+    window.layout();
+
+    let center = window.widget_center(0);
+    window.press(center);
+    clock.set(100);
+    window.window.update();
+    window.input_event(InputEvent::PointerEvent(center, PointerEvent::Up));
+
+    clock.set(1000);
+    window.window.update();
+
+    assert_eq!(
+        log.with_data(|log| log.clone()),
+        ["pressed", "released", "clicked"]
+    );
+}
+
+#[test]
+fn auto_repeat_stops_when_press_ends() {
+    let clock = Clock(Cell::new(0));
+    let log = BoundData::new(Vec::new(), |_| ());
+    let mut window = TestWindow::<_, BinaryColor, 64, 16>::new(repeating_button!(&log, &clock));
+    window.layout();
+
+    let center = window.widget_center(0);
+    window.press(center);
+    clock.set(300);
+    window.window.update();
+
+    // Dragging outside ends the press
+    let outside = Position { x: 200, y: 8 };
+    window.input_event(InputEvent::PointerEvent(outside, PointerEvent::Drag));
+    clock.set(1000);
+    window.window.update();
+    window.input_event(InputEvent::PointerEvent(outside, PointerEvent::Up));
+    window.window.update();
+
+    assert_eq!(
+        log.with_data(|log| log.clone()),
+        ["pressed", "clicked", "released"]
+    );
+
+    // A new press starts counting again
+    log.update(|log| log.clear());
+    window.press(center);
+    clock.set(1299);
+    window.window.update();
+    assert_eq!(log.with_data(|log| log.clone()), ["pressed"]);
+}
+
+/// A touch panel that reports twice the display coordinates, shifted by (100, 50).
+fn raw_position(display: Position) -> Position {
+    Position {
+        x: 2 * display.x + 100,
+        y: 2 * display.y + 50,
+    }
+}
+
+macro_rules! calibration_screen {
+    ($calibration:expr) => {
+        CalibrationScreen::<CalibrationStyle<BinaryColor>>::new(CalibrationPoints::Five)
+            .bind($calibration)
+            .on_calibrated(|calibration, transform| *calibration = Some(transform))
+    };
+}
+
+#[test]
+fn calibration_targets() {
+    let calibration = BoundData::new(None::<AffineTransform>, |_| ());
+    let mut window = TestWindow::<_, BinaryColor, 80, 80>::new(calibration_screen!(&calibration));
+    window.layout();
+
+    let targets = [(10, 10), (70, 10), (70, 70), (10, 70), (40, 40)];
+    for &(x, y) in targets.iter() {
+        let target = Position { x, y };
+        assert_eq!(window.window.root.target(), Some(target));
+        assert!(calibration.with_data(Option::is_none));
+
+        window.click(raw_position(target));
+    }
+
+    assert_eq!(window.window.root.target(), None);
+
+    let transform = calibration.with_data(|calibration| calibration.unwrap());
+    for &(x, y) in [(10, 10), (25, 60), (0, 79)].iter() {
+        let display = Position { x, y };
+        assert_eq!(transform.apply(raw_position(display)), display);
+    }
+}
+
+#[test]
+fn calibration_restarts_on_unusable_touches() {
+    let calibration = BoundData::new(None::<AffineTransform>, |_| ());
+    let mut window = TestWindow::<_, BinaryColor, 80, 80>::new(calibration_screen!(&calibration));
+    window.layout();
+
+    // Every touch is reported at the same position
+    for _ in 0..5 {
+        window.click(Position { x: 120, y: 70 });
+    }
+
+    assert!(calibration.with_data(Option::is_none));
+    assert_eq!(window.window.root.target(), Some(Position { x: 10, y: 10 }));
+
+    // The calibration can be completed after the restart
+    for &(x, y) in [(10, 10), (70, 10), (70, 70), (10, 70), (40, 40)].iter() {
+        window.click(raw_position(Position { x, y }));
+    }
+    assert!(calibration.with_data(Option::is_some));
+}
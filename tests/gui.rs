@@ -0,0 +1,119 @@
+use backend_embedded_graphics::{
+    snapshot::TestWindow,
+    themes::default::{button::StyledButton, DefaultTheme},
+    widgets::label::{ascii::LabelConstructor, LabelStyle},
+};
+use embedded_graphics::{mono_font::MonoTextStyle, pixelcolor::BinaryColor};
+use embedded_gui::{
+    data::BoundData,
+    geometry::Position,
+    gui,
+    prelude::*,
+    widgets::{label::Label, layouts::absolute::Anchor},
+};
+
+#[test]
+fn bindings_and_callbacks() {
+    let counter = BoundData::new(0, |_| ());
+
+    let mut window = TestWindow::<_, BinaryColor, 64, 48>::new(gui! {
+        Column(spacing(1)) {
+            Label::new("zero") => {
+                bind(&counter),
+                on_data_changed(|label, count| {
+                    label.text = if *count == 0 { "zero" } else { "not zero" }
+                }),
+            },
+            Row {
+                #[weight(1)]
+                BinaryColor::primary_button("Add") => {
+                    bind(&counter),
+                    on_clicked(|count| *count += 1),
+                },
+                #[weight(1)]
+                BinaryColor::secondary_button("Sub") => {
+                    bind(&counter),
+                    on_clicked(|count| *count -= 1),
+                },
+            },
+        }
+    });
+
+    window.click_text("Add");
+    window.click_text("Add");
+    window.click_text("Sub");
+    assert_eq!(counter.with_data(|count| *count), 1);
+    assert!(window.window.find_text("not zero").is_some());
+}
+
+#[test]
+fn matches_builder_calls() {
+    let mut built = TestWindow::<_, BinaryColor, 64, 32>::new(gui! {
+        Frame {
+            Label::new("background"),
+            #[anchor(Anchor::BottomRight)]
+            Flow(horizontal_gap(2)) {
+                Label::new("a"),
+                Label::new("b"),
+            },
+        }
+    });
+
+    let mut expected = TestWindow::<_, BinaryColor, 64, 32>::new(
+        embedded_gui::widgets::layouts::frame::Frame::new()
+            .add_layer(Label::new("background"))
+            .add_layer(
+                embedded_gui::widgets::layouts::flow::Flow::new()
+                    .horizontal_gap(2)
+                    .add(Label::new("a"))
+                    .add(Label::new("b")),
+            )
+            .anchor(Anchor::BottomRight),
+    );
+
+    built.render();
+    expected.render();
+    assert_eq!(built.snapshot(), expected.snapshot());
+
+    let position = built
+        .window
+        .find_text("b")
+        .map(|idx| built.widget_center(idx));
+    assert!(matches!(position, Some(Position { x, y }) if x > 32 && y > 16));
+}
+
+type Text = Label<&'static str, LabelStyle<MonoTextStyle<'static, BinaryColor>>>;
+
+gui! {
+    type Toolbar = Column {
+        Text,
+        #[weight]
+        Row(spacing) {
+            #[weight]
+            StyledButton<'static, BinaryColor>,
+            StyledButton<'static, BinaryColor>,
+        },
+    }
+}
+
+fn toolbar() -> Toolbar {
+    gui! {
+        Column {
+            Label::new("Title"),
+            #[weight(1)]
+            Row(spacing(2)) {
+                #[weight(1)]
+                BinaryColor::primary_button("OK"),
+                BinaryColor::secondary_button("Cancel"),
+            },
+        }
+    }
+}
+
+#[test]
+fn type_alias() {
+    let mut window = TestWindow::<_, BinaryColor, 64, 32>::new(toolbar());
+    window.layout();
+
+    assert!(window.window.find_text("Cancel").is_some());
+}
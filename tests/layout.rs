@@ -0,0 +1,516 @@
+use backend_embedded_graphics::{
+    snapshot::TestWindow,
+    themes::default::DefaultTheme,
+    widgets::{
+        border::BorderStyle,
+        label::{ascii::LabelConstructor, LabelStyling, MonoFontLabelStyling},
+    },
+};
+use embedded_graphics::{mono_font::ascii::FONT_10X20, pixelcolor::BinaryColor};
+use embedded_gui::{
+    data::BoundData,
+    geometry::{
+        measurement::{MeasureConstraint, MeasureSpec},
+        BoundingBox, MeasuredSize, Position,
+    },
+    input::controller::InputController,
+    prelude::*,
+    widgets::{
+        border::Border,
+        label::Label,
+        layouts::{
+            absolute::{Absolute, Anchor},
+            flow::Flow,
+            frame::Frame,
+            linear::{Column, CrossAxisAlignment, MainAxisAlignment, Row},
+        },
+        size_constraint::SizeConstraint,
+        spacing::Spacing,
+        visibility::{Visibility, VisibilityMode},
+        Widget,
+    },
+    Canvas, WidgetRenderer, Window,
+};
+
+fn bounds<C, W, I>(window: &Window<C, W, I>, index: usize) -> BoundingBox
+where
+    C: Canvas,
+    W: Widget + WidgetRenderer<C>,
+    I: InputController,
+{
+    window.widget(index).unwrap().bounding_box()
+}
+
+fn x_positions(alignment: MainAxisAlignment) -> [i32; 3] {
+    // Each label is 6 px wide, leaving 22 px of free space
+    let mut window = TestWindow::<_, BinaryColor, 40, 10>::new(
+        Row::new()
+            .add(Label::new("a"))
+            .add(Label::new("b"))
+            .add(Label::new("c"))
+            .main_axis_alignment(alignment),
+    );
+    window.layout();
+
+    let x = |index| bounds(&window.window, index).position.x;
+
+    [x(1), x(2), x(3)]
+}
+
+#[test]
+fn main_axis_alignment() {
+    assert_eq!(x_positions(MainAxisAlignment::Start), [0, 6, 12]);
+    assert_eq!(x_positions(MainAxisAlignment::Center), [11, 17, 23]);
+    assert_eq!(x_positions(MainAxisAlignment::End), [22, 28, 34]);
+    assert_eq!(x_positions(MainAxisAlignment::SpaceBetween), [0, 17, 34]);
+    assert_eq!(x_positions(MainAxisAlignment::SpaceAround), [3, 17, 31]);
+}
+
+#[test]
+fn cross_axis_alignment() {
+    let mut window = TestWindow::<_, BinaryColor, 40, 40>::new(
+        Column::new()
+            .add(Label::new("ab"))
+            .add(Label::new("ab"))
+            .align_cell(CrossAxisAlignment::End)
+            .add(Label::new("ab"))
+            .align_cell(CrossAxisAlignment::Stretch)
+            .add(Label::new("ab"))
+            .align_cell(CrossAxisAlignment::Start)
+            .cross_axis_alignment(CrossAxisAlignment::Center),
+    );
+    window.layout();
+
+    let window = &window.window;
+    assert_eq!(bounds(window, 0).size.width, 40);
+    assert_eq!(bounds(window, 1).position.x, 14);
+    assert_eq!(bounds(window, 2).position.x, 28);
+    assert_eq!(
+        bounds(window, 3),
+        BoundingBox {
+            position: Position { x: 0, y: 20 },
+            size: MeasuredSize {
+                width: 40,
+                height: 10
+            }
+        }
+    );
+    assert_eq!(bounds(window, 4).position.x, 0);
+}
+
+#[test]
+fn baseline_alignment() {
+    let mut window = TestWindow::<_, BinaryColor, 40, 40>::new(
+        Row::new()
+            .add(Label::new("a"))
+            .add(Label::new("B").font(&FONT_10X20))
+            .add(Label::new("c"))
+            .align_cell(CrossAxisAlignment::Start)
+            .cross_axis_alignment(CrossAxisAlignment::Baseline),
+    );
+    window.layout();
+
+    let window = &window.window;
+    let baseline = |index| {
+        let widget = window.widget(index).unwrap();
+        widget.bounding_box().position.y + widget.baseline().unwrap() as i32
+    };
+
+    assert_eq!(baseline(1), baseline(2));
+    assert_ne!(bounds(window, 1).position.y, bounds(window, 2).position.y);
+    assert_eq!(bounds(window, 3).position.y, 0);
+    assert_eq!(bounds(window, 0).size.height, 20);
+}
+
+#[test]
+fn baseline_alignment_of_decorated_labels() {
+    let mut window = TestWindow::<_, BinaryColor, 40, 40>::new(
+        Row::new()
+            .add(Label::new("a"))
+            .add(Spacing::new(Label::new("b")).top(5))
+            .add(Border::<_, BorderStyle<BinaryColor>>::new(Label::new("c")))
+            .cross_axis_alignment(CrossAxisAlignment::Baseline),
+    );
+    window.layout();
+
+    let window = &window.window;
+
+    // The labels are lined up, the decorations extend above them
+    assert_eq!(bounds(window, 1).position.y, 5);
+    assert_eq!(bounds(window, 3).position.y, 5);
+    assert_eq!(bounds(window, 5).position.y, 5);
+    assert_eq!(bounds(window, 2).position.y, 0);
+    assert_eq!(bounds(window, 4).position.y, 4);
+}
+
+#[test]
+fn flow_wraps_lines() {
+    // 6 px wide, 10 px tall labels fit 3 in a line with the gaps
+    let mut window = TestWindow::<_, BinaryColor, 22, 40>::new(
+        Column::new().add(
+            Flow::new()
+                .horizontal_gap(2)
+                .vertical_gap(1)
+                .add(Label::new("a"))
+                .add(Label::new("b"))
+                .add(Label::new("c"))
+                .add(Label::new("d"))
+                .add(Label::new("e")),
+        ),
+    );
+    window.layout();
+
+    let window = &window.window;
+    let position = |index| bounds(window, index).position;
+
+    assert_eq!(position(2), Position { x: 0, y: 0 });
+    assert_eq!(position(4), Position { x: 16, y: 0 });
+    assert_eq!(position(5), Position { x: 0, y: 11 });
+    assert_eq!(position(6), Position { x: 8, y: 11 });
+    assert_eq!(
+        bounds(window, 1).size,
+        MeasuredSize {
+            width: 22,
+            height: 21
+        }
+    );
+}
+
+#[test]
+fn flow_line_alignment() {
+    let mut window = TestWindow::<_, BinaryColor, 20, 40>::new(
+        Flow::new()
+            .line_alignment(MainAxisAlignment::End)
+            .add(Label::new("ab"))
+            .add(Label::new("c"))
+            .add(Label::new("d")),
+    );
+    window.layout();
+
+    let window = &window.window;
+    let position = |index| bounds(window, index).position;
+
+    assert_eq!(position(1), Position { x: 2, y: 0 });
+    assert_eq!(position(2), Position { x: 14, y: 0 });
+    assert_eq!(position(3), Position { x: 14, y: 10 });
+    assert_eq!(bounds(window, 0).size.width, 20);
+}
+
+#[test]
+fn absolute_placement() {
+    let mut window = TestWindow::<_, BinaryColor, 40, 30>::new(
+        Absolute::new()
+            .add(Label::new("a"))
+            .offset(2, 3)
+            .add(Label::new("b"))
+            .anchor(Anchor::TopRight)
+            .offset(4, 4)
+            .add(Label::new("c"))
+            .anchor(Anchor::Center)
+            .size(10, 12)
+            .add(Label::new("d"))
+            .anchor(Anchor::BottomCenter),
+    );
+    window.layout();
+
+    let window = &window.window;
+    let position = |index| bounds(window, index).position;
+
+    assert_eq!(position(1), Position { x: 2, y: 3 });
+    assert_eq!(position(2), Position { x: 30, y: 4 });
+    assert_eq!(
+        bounds(window, 3),
+        BoundingBox {
+            position: Position { x: 15, y: 9 },
+            size: MeasuredSize {
+                width: 10,
+                height: 12
+            }
+        }
+    );
+    assert_eq!(position(4), Position { x: 17, y: 20 });
+}
+
+#[test]
+fn absolute_input_prefers_top_widget() {
+    let clicked = BoundData::new(0, |_| ());
+
+    let mut window = TestWindow::<_, BinaryColor, 40, 30>::new(
+        Absolute::new()
+            .add(
+                BinaryColor::primary_button("1")
+                    .bind(&clicked)
+                    .on_clicked(|clicked| *clicked = 1),
+            )
+            .size(30, 20)
+            .add(
+                BinaryColor::primary_button("2")
+                    .bind(&clicked)
+                    .on_clicked(|clicked| *clicked = 2),
+            )
+            .offset(10, 10)
+            .size(30, 20),
+    );
+
+    window.layout();
+    window.click(Position { x: 5, y: 5 });
+    assert_eq!(clicked.with_data(|clicked| *clicked), 1);
+
+    window.click(Position { x: 15, y: 15 });
+    window.layout();
+    assert_eq!(clicked.with_data(|clicked| *clicked), 2);
+}
+
+#[test]
+fn size_constraint_limits() {
+    let mut window = TestWindow::<_, BinaryColor, 40, 40>::new(
+        Column::new()
+            .add(SizeConstraint::new(Label::new("a")).min_width(20))
+            .add(
+                SizeConstraint::new(
+                    Column::new()
+                        .add(Label::new("0"))
+                        .add(Label::new("1"))
+                        .add(Label::new("2")),
+                )
+                .max_height(15),
+            )
+            .add(
+                SizeConstraint::new(Label::new("abcdefgh"))
+                    .width(12)
+                    .height(5),
+            ),
+    );
+    window.layout();
+
+    let window = &window.window;
+    let size = |index| bounds(window, index).size;
+
+    assert_eq!(
+        size(1),
+        MeasuredSize {
+            width: 20,
+            height: 10
+        }
+    );
+    assert_eq!(size(2), size(1));
+    assert_eq!(size(3).height, 15);
+    assert_eq!(
+        size(8),
+        MeasuredSize {
+            width: 12,
+            height: 5
+        }
+    );
+}
+
+#[test]
+fn size_constraint_aspect_ratio() {
+    let mut window = TestWindow::<_, BinaryColor, 40, 40>::new(
+        Column::new().add(
+            SizeConstraint::new(Label::new("a"))
+                .max_width(30)
+                .aspect_ratio(2, 1),
+        ),
+    );
+    window.layout();
+
+    assert_eq!(
+        bounds(&window.window, 1).size,
+        MeasuredSize {
+            width: 30,
+            height: 15
+        }
+    );
+}
+
+#[test]
+fn size_constraint_aspect_ratio_within_limits() {
+    let size = |mut widget: SizeConstraint<_>, width, height| {
+        widget.measure(MeasureSpec {
+            width: MeasureConstraint::AtMost(width),
+            height: MeasureConstraint::AtMost(height),
+        });
+        widget.bounding_box().size
+    };
+
+    // The minimum height requires a wider widget than the parent offers
+    assert_eq!(
+        size(
+            SizeConstraint::new(Label::new("a").text_color(BinaryColor::On))
+                .min_height(30)
+                .aspect_ratio(1, 1),
+            20,
+            100
+        ),
+        MeasuredSize {
+            width: 30,
+            height: 30
+        }
+    );
+
+    // The maximum height limits the width
+    assert_eq!(
+        size(
+            SizeConstraint::new(Label::new("a").text_color(BinaryColor::On))
+                .max_height(20)
+                .aspect_ratio(3, 2),
+            100,
+            100
+        ),
+        MeasuredSize {
+            width: 30,
+            height: 20
+        }
+    );
+
+    // Conflicting limits win over the ratio
+    assert_eq!(
+        size(
+            SizeConstraint::new(Label::new("a").text_color(BinaryColor::On))
+                .min_width(48)
+                .max_height(20)
+                .aspect_ratio(1, 1),
+            100,
+            100
+        ),
+        MeasuredSize {
+            width: 48,
+            height: 20
+        }
+    );
+}
+
+#[test]
+fn size_constraint_unspecified() {
+    let unspecified = MeasureSpec {
+        width: MeasureConstraint::Unspecified,
+        height: MeasureConstraint::Unspecified,
+    };
+
+    let mut limited =
+        SizeConstraint::new(Label::new("abcdef").text_color(BinaryColor::On)).max_width(24);
+    limited.measure(unspecified);
+    assert_eq!(limited.bounding_box().size.width, 24);
+
+    let mut free =
+        SizeConstraint::new(Label::new("abcdef").text_color(BinaryColor::On)).min_height(12);
+    free.measure(unspecified);
+    assert_eq!(
+        free.bounding_box().size,
+        MeasuredSize {
+            width: 36,
+            height: 12
+        }
+    );
+
+    let mut square =
+        SizeConstraint::new(Label::new("ab").text_color(BinaryColor::On)).aspect_ratio(1, 1);
+    square.measure(unspecified);
+    assert_eq!(
+        square.bounding_box().size,
+        MeasuredSize {
+            width: 12,
+            height: 12
+        }
+    );
+}
+
+#[test]
+fn frame_layers() {
+    let mut window = TestWindow::<_, BinaryColor, 40, 30>::new(
+        Frame::new()
+            .add_layer(Label::new("a"))
+            .add_layer(Label::new("b"))
+            .width_percent(50)
+            .height_percent(50)
+            .anchor(Anchor::Center)
+            .add_layer(Label::new("c"))
+            .anchor(Anchor::BottomRight),
+    );
+    window.layout();
+
+    let window = &window.window;
+
+    assert_eq!(bounds(window, 1).position, Position { x: 0, y: 0 });
+    assert_eq!(
+        bounds(window, 2),
+        BoundingBox {
+            position: Position { x: 10, y: 7 },
+            size: MeasuredSize {
+                width: 20,
+                height: 15
+            }
+        }
+    );
+    assert_eq!(
+        bounds(window, 0).size,
+        MeasuredSize {
+            width: 40,
+            height: 30
+        }
+    );
+    assert_eq!(bounds(window, 3).position, Position { x: 34, y: 20 });
+}
+
+#[test]
+fn frame_anchors_in_weighted_cell() {
+    let mut window = TestWindow::<_, BinaryColor, 40, 30>::new(
+        Column::new()
+            .add(
+                Frame::new()
+                    .add_layer(Label::new("a"))
+                    .add_layer(Label::new("b"))
+                    .anchor(Anchor::Center)
+                    .add_layer(Label::new("c"))
+                    .anchor(Anchor::BottomRight),
+            )
+            .weight(1),
+    );
+    window.layout();
+
+    let window = &window.window;
+
+    // Anchored layers keep their size, even though the frame is measured exactly
+    assert_eq!(
+        bounds(window, 3).size,
+        MeasuredSize {
+            width: 6,
+            height: 10
+        }
+    );
+    assert_eq!(bounds(window, 3).position, Position { x: 17, y: 10 });
+    assert_eq!(bounds(window, 4).position, Position { x: 34, y: 20 });
+}
+
+#[test]
+fn visibility_modes() {
+    let mode = BoundData::new(VisibilityMode::Visible, |_| ());
+
+    let mut window = TestWindow::<_, BinaryColor, 40, 10>::new(
+        Row::new()
+            .add(Label::new("a"))
+            .add(
+                Visibility::new(Label::new("b"))
+                    .bind(&mode)
+                    .on_data_changed(|widget, mode| widget.set_mode(*mode)),
+            )
+            .add(Label::new("c")),
+    );
+
+    let mut layout = |visibility| {
+        mode.update(|mode| *mode = visibility);
+        window.layout();
+
+        let window = &window.window;
+        (
+            bounds(window, 5).position.x,
+            window.root.hit_test(Position { x: 7, y: 5 }),
+        )
+    };
+
+    assert_eq!(layout(VisibilityMode::Visible), (12, Some(4)));
+    // Only the wrapper that binds the mode is hit
+    assert_eq!(layout(VisibilityMode::Hidden), (12, Some(2)));
+    assert_eq!(layout(VisibilityMode::Collapsed), (6, Some(5)));
+}
@@ -0,0 +1,152 @@
+use std::{io::Cursor, vec::Vec};
+
+use backend_embedded_graphics::{
+    framebuffer::FrameBuffer, widgets::label::ascii::LabelConstructor, EgCanvas,
+};
+use embedded_graphics::pixelcolor::BinaryColor;
+use embedded_gui::{
+    data::BoundData,
+    geometry::Position,
+    input::{
+        controller::DefaultInputController,
+        event::{InputEvent, Key, KeyEvent, Modifier, PointerEvent, ScrollEvent, Touch},
+        recording::{Player, Record, RecordingController, ReplayClock},
+    },
+    prelude::*,
+    time::Instant,
+    widgets::{label::Label, layouts::linear::Column, scroll::Scroll},
+};
+
+#[test]
+fn records_survive_round_trip() {
+    let records = [
+        Record::Update(Instant::from_millis(0)),
+        Record::Event(
+            Instant::from_millis(12),
+            InputEvent::PointerEvent(Position { x: -3, y: 20 }, PointerEvent::Down),
+        ),
+        Record::Event(
+            Instant::from_millis(20),
+            InputEvent::TouchEvent(
+                Touch::new(1, Position { x: 10, y: 21 }).pressure(300),
+                PointerEvent::Drag,
+            ),
+        ),
+        Record::Event(
+            Instant::from_millis(30),
+            InputEvent::KeyEvent(KeyEvent::KeyDown(Key::N5, Modifier::CtrlShift, 2)),
+        ),
+        Record::Event(
+            Instant::from_millis(31),
+            InputEvent::KeyEvent(KeyEvent::KeyUp(Key::Minus, Modifier::None)),
+        ),
+        Record::Event(
+            Instant::from_millis(40),
+            InputEvent::ScrollEvent(ScrollEvent::VerticalScroll(-3)),
+        ),
+        Record::Event(Instant::from_millis(50), InputEvent::Cancel),
+    ];
+
+    let mut text = Vec::new();
+    for record in records.iter() {
+        record.write(&mut text).unwrap();
+    }
+
+    let mut player = Player::new(Cursor::new(&text));
+    let mut rewritten = Vec::new();
+    while let Some(record) = player.next_record().unwrap() {
+        record.write(&mut rewritten).unwrap();
+    }
+
+    assert_eq!(
+        String::from_utf8(rewritten).unwrap(),
+        String::from_utf8(text).unwrap()
+    );
+    assert!(Record::parse("10 K Down Escape None 0").is_err());
+    assert!(Record::parse("10 K Down A None -1").is_err());
+    assert!(Record::parse("10 K Down A None 4294967295").is_ok());
+}
+
+macro_rules! scrolling_list {
+    ($offset:expr) => {
+        Scroll::vertical(
+            Column::new()
+                .add(Label::new("0"))
+                .add(Label::new("1"))
+                .add(Label::new("2"))
+                .add(Label::new("3"))
+                .add(Label::new("4"))
+                .add(Label::new("5"))
+                .add(Label::new("6"))
+                .add(Label::new("7")),
+        )
+        .bind($offset)
+        .on_scroll_changed(|offset, scroll| *offset = scroll.offset)
+    };
+}
+
+#[test]
+fn replay_reproduces_fling() {
+    let clock = ReplayClock::new();
+
+    // Record a fling
+    let offset = BoundData::new(0, |_| ());
+    let mut window = Window::new(
+        EgCanvas::new(FrameBuffer::<BinaryColor, 32, 16>::new(BinaryColor::Off)),
+        scrolling_list!(&offset),
+    )
+    .with_input_controller(RecordingController::new(
+        DefaultInputController::new(),
+        &clock,
+        Vec::new(),
+    ));
+
+    let frame = |window: &mut Window<_, _, _>, millis| {
+        clock.set(Instant::from_millis(millis));
+        window.update();
+        window.measure();
+        window.arrange();
+    };
+
+    frame(&mut window, 0);
+    let events = [
+        (Position { x: 3, y: 14 }, PointerEvent::Hover),
+        (Position { x: 3, y: 14 }, PointerEvent::Down),
+        (Position { x: 3, y: 10 }, PointerEvent::Drag),
+        (Position { x: 3, y: 2 }, PointerEvent::Drag),
+        (Position { x: 3, y: 2 }, PointerEvent::Up),
+    ];
+    let mut recorded = Vec::new();
+    for (millis, (position, event)) in (1..).zip(events.iter()) {
+        clock.set(Instant::from_millis(millis * 10));
+        window.input_event(InputEvent::PointerEvent(*position, *event));
+        frame(&mut window, millis * 10 + 5);
+        recorded.push(offset.with_data(|offset| *offset));
+    }
+    for millis in 6..20 {
+        frame(&mut window, millis * 10);
+        recorded.push(offset.with_data(|offset| *offset));
+    }
+
+    let recording = window.input_controller.finish().unwrap();
+
+    // Replay it on a fresh window
+    let offset = BoundData::new(0, |_| ());
+    let mut window = Window::new(
+        EgCanvas::new(FrameBuffer::<BinaryColor, 32, 16>::new(BinaryColor::Off)),
+        scrolling_list!(&offset),
+    );
+
+    let mut player = Player::new(Cursor::new(recording));
+    // The initial frame
+    assert!(player.replay_frame(&mut window, &clock).unwrap());
+
+    let mut replayed = Vec::new();
+    while player.replay_frame(&mut window, &clock).unwrap() {
+        replayed.push(offset.with_data(|offset| *offset));
+    }
+
+    assert_eq!(replayed, recorded);
+    // The list kept moving after the pointer was released
+    assert_ne!(recorded[4], recorded[recorded.len() - 1]);
+}
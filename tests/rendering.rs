@@ -0,0 +1,163 @@
+use std::{
+    env, fs,
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+};
+
+use backend_embedded_graphics::{
+    snapshot::TestWindow, themes::default::DefaultTheme, widgets::label::ascii::LabelConstructor,
+};
+use embedded_graphics::pixelcolor::{BinaryColor, Rgb888};
+use embedded_gui::{
+    data::BoundData,
+    geometry::{BoundingBox, MeasuredSize, Position},
+    input::event::{InputEvent, PointerEvent},
+    prelude::*,
+    widgets::{
+        button::Button,
+        enabled::Enabled,
+        label::Label,
+        layouts::linear::{Column, Row},
+        spacing::Spacing,
+    },
+};
+
+macro_rules! snapshot {
+    ($name:literal) => {
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/snapshots/",
+            $name,
+            ".txt"
+        )
+    };
+}
+
+#[test]
+fn label() {
+    let mut window = TestWindow::<_, BinaryColor, 40, 10>::new(Label::new("Hello"));
+
+    window.render();
+    window.assert_snapshot(snapshot!("label"));
+}
+
+#[test]
+fn missing_snapshot_fails() {
+    if env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        // The snapshot would be created
+        return;
+    }
+
+    let mut window = TestWindow::<_, BinaryColor, 40, 10>::new(Label::new("Hello"));
+    window.render();
+
+    let path = Path::new(snapshot!("missing"));
+    let result = panic::catch_unwind(AssertUnwindSafe(|| window.assert_snapshot(path)));
+
+    assert!(result.is_err());
+    assert!(!path.exists());
+    fs::remove_file(path.with_extension("new")).unwrap();
+}
+
+#[test]
+fn labels_are_clipped_by_layout() {
+    let mut window = TestWindow::<_, BinaryColor, 40, 10>::new(
+        Row::new()
+            .add(Label::new("Clipped"))
+            .weight(1)
+            .add(Label::new("Text"))
+            .weight(1),
+    );
+
+    window.render();
+    window.assert_snapshot(snapshot!("clipped_labels"));
+}
+
+#[test]
+fn button_states() {
+    let mut window =
+        TestWindow::<_, Rgb888, 48, 16>::new(Spacing::new(Rgb888::primary_button("Press")).all(1));
+
+    window.render();
+    let state = window.window.root.inner.fields.state;
+    assert!(state.has_state(Button::STATE_IDLE));
+    window.assert_snapshot(snapshot!("button_idle"));
+
+    window.press(Position { x: 24, y: 8 });
+    window.render();
+    let state = window.window.root.inner.fields.state;
+    assert!(state.has_state(Button::STATE_PRESSED));
+    window.assert_snapshot(snapshot!("button_pressed"));
+
+    window.input_event(InputEvent::PointerEvent(
+        Position { x: 24, y: 8 },
+        PointerEvent::Up,
+    ));
+    window.render();
+    let state = window.window.root.inner.fields.state;
+    assert!(state.has_state(Button::STATE_HOVERED));
+    window.assert_snapshot(snapshot!("button_hovered"));
+}
+
+#[test]
+fn rgb_theme() {
+    let mut window = TestWindow::<_, Rgb888, 48, 32>::new(
+        Column::new()
+            .add(Rgb888::primary_button("One"))
+            .add(Rgb888::secondary_button("Two")),
+    );
+
+    window.render();
+    window.assert_snapshot(snapshot!("rgb_buttons"));
+}
+
+#[test]
+fn disabled_subtree() {
+    let mut window = TestWindow::<_, Rgb888, 48, 32>::new(
+        Enabled::new(
+            Column::new()
+                .add(Rgb888::primary_button("One"))
+                .add(Rgb888::secondary_button("Two")),
+        )
+        .enabled(false),
+    );
+
+    window.render();
+    window.assert_snapshot(snapshot!("rgb_buttons_disabled"));
+
+    window.window.root.set_enabled(true);
+    window.render();
+    window.assert_snapshot(snapshot!("rgb_buttons"));
+}
+
+#[test]
+fn viewport() {
+    let counter = BoundData::new(0, |_| ());
+    let mut window = TestWindow::<_, BinaryColor, 48, 24>::new(
+        Row::new()
+            .add(
+                BinaryColor::primary_button("+")
+                    .bind(&counter)
+                    .on_clicked(|count| *count += 1),
+            )
+            .add(Label::new("Clipped")),
+    );
+    window.window.set_viewport(BoundingBox {
+        position: Position { x: 8, y: 6 },
+        size: MeasuredSize {
+            width: 32,
+            height: 12,
+        },
+    });
+
+    // The widgets are laid out in, and clipped to the viewport
+    window.render();
+    window.assert_snapshot(snapshot!("viewport"));
+
+    // Input positions are canvas coordinates
+    window.click(Position { x: 2, y: 2 });
+    assert_eq!(counter.with_data(|count| *count), 0);
+
+    window.click(Position { x: 10, y: 10 });
+    assert_eq!(counter.with_data(|count| *count), 1);
+}
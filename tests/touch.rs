@@ -0,0 +1,88 @@
+use backend_embedded_graphics::{snapshot::TestWindow, widgets::label::ascii::LabelConstructor};
+use embedded_graphics::pixelcolor::BinaryColor;
+use embedded_gui::{
+    data::BoundData,
+    geometry::Position,
+    input::event::{InputEvent, PointerEvent, Touch},
+    prelude::*,
+    widgets::{label::Label, zoom::Zoom},
+};
+
+fn touch(x: i32, y: i32, id: u8, pe: PointerEvent) -> InputEvent {
+    InputEvent::TouchEvent(Touch::new(id, Position { x, y }), pe)
+}
+
+#[test]
+fn zoom_levels_are_at_least_one_percent() {
+    let mut window = TestWindow::<_, BinaryColor, 64, 48>::new(
+        Zoom::new(Label::new("Zoom")).min_zoom(0).zoom(0),
+    );
+    assert_eq!(window.window.root.fields.zoom, 1);
+    assert_eq!(window.window.root.fields.min_zoom, 1);
+
+    window.window.root.fields.set_zoom(100);
+    window.layout();
+
+    window.input_event(touch(10, 20, 0, PointerEvent::Down));
+    window.input_event(touch(50, 20, 1, PointerEvent::Down));
+
+    // Pinching the fingers together doesn't zoom out to nothing
+    window.input_event(touch(10, 20, 1, PointerEvent::Drag));
+    assert_eq!(window.window.root.fields.zoom, 1);
+
+    window.input_event(touch(30, 20, 1, PointerEvent::Drag));
+    assert_eq!(window.window.root.fields.zoom, 50);
+}
+
+#[test]
+fn pinch_zooms() {
+    let level = BoundData::new(100, |_| ());
+    let mut window = TestWindow::<_, BinaryColor, 64, 48>::new(
+        Zoom::new(Label::new("Zoom"))
+            .bind(&level)
+            .on_zoom_changed(|level, zoom| *level = zoom),
+    );
+    window.layout();
+
+    window.input_event(touch(20, 20, 0, PointerEvent::Down));
+    window.input_event(touch(40, 20, 1, PointerEvent::Down));
+    window.input_event(touch(60, 20, 1, PointerEvent::Drag));
+    assert_eq!(window.window.root.fields.zoom, 200);
+    assert_eq!(level.with_data(|level| *level), 200);
+
+    // The zoom level is limited
+    window.input_event(touch(0, 20, 0, PointerEvent::Drag));
+    assert_eq!(window.window.root.fields.zoom, 300);
+    window.input_event(touch(100, 20, 1, PointerEvent::Drag));
+    assert_eq!(window.window.root.fields.zoom, 400);
+
+    // Lifting a finger ends the gesture, the other finger doesn't zoom on its own
+    window.input_event(touch(100, 20, 1, PointerEvent::Up));
+    window.input_event(touch(30, 20, 0, PointerEvent::Drag));
+    window.input_event(touch(30, 20, 0, PointerEvent::Up));
+    assert_eq!(level.with_data(|level| *level), 400);
+}
+
+#[test]
+fn pinch_is_applied_once_per_event() {
+    // (zoom level, number of zoom changes)
+    let level = BoundData::new((100, 0), |_| ());
+    let mut window = TestWindow::<_, BinaryColor, 64, 48>::new(
+        Zoom::new(Label::new("Zoom"))
+            .bind(&level)
+            .on_zoom_changed(|(level, changes), zoom| {
+                *level = zoom;
+                *changes += 1;
+            }),
+    );
+    window.layout();
+
+    // The fingers are on the label, so the zoom sees the events on their way to it and back
+    window.input_event(touch(2, 2, 0, PointerEvent::Down));
+    window.input_event(touch(12, 2, 1, PointerEvent::Down));
+    window.input_event(touch(22, 2, 1, PointerEvent::Drag));
+    window.input_event(touch(32, 2, 1, PointerEvent::Drag));
+    window.input_event(touch(32, 2, 1, PointerEvent::Up));
+
+    assert_eq!(level.with_data(|data| *data), (300, 2));
+}